@@ -24,3 +24,10 @@ pub struct CloseTroveEvent {
     pub collateral: u64,
     pub debt: u64,
 }
+
+pub struct AutoDeleverageSetEvent {
+    pub user: Identity,
+    pub asset_id: AssetId,
+    pub trigger_icr: u64,
+    pub target_icr: u64,
+}
@@ -15,8 +15,8 @@ mod events;
 
 use standards::{src3::SRC3,};
 use ::data_structures::{AssetContracts, LocalVariablesAdjustTrove, LocalVariablesOpenTrove};
-use ::events::{AdjustTroveEvent, CloseTroveEvent, OpenTroveEvent};
-use libraries::trove_manager_interface::data_structures::Status;
+use ::events::{AdjustTroveEvent, AutoDeleverageSetEvent, CloseTroveEvent, OpenTroveEvent};
+use libraries::trove_manager_interface::data_structures::{AutoDeleverageSettings, Status};
 use libraries::active_pool_interface::ActivePool;
 use libraries::token_interface::Token;
 use libraries::trove_manager_interface::TroveManager;
@@ -54,6 +54,7 @@ storage {
     active_pool_contract: ContractId = ContractId::zero(),
     protocol_manager_contract: ContractId = ContractId::zero(),
     sorted_troves_contract: ContractId = ContractId::zero(),
+    stability_pool_contract: ContractId = ContractId::zero(),
     usdf_asset_id: AssetId = AssetId::zero(),
     is_initialized: bool = false,
     is_paused: bool = false, // paused protocol still allows trove operations which do not increase trove debt
@@ -70,6 +71,7 @@ impl BorrowOperations for Contract {
         coll_surplus_pool_contract: ContractId,
         active_pool_contract: ContractId,
         sorted_troves_contract: ContractId,
+        stability_pool_contract: ContractId,
     ) {
         require(
             msg_sender()
@@ -90,6 +92,7 @@ impl BorrowOperations for Contract {
             .write(coll_surplus_pool_contract);
         storage.active_pool_contract.write(active_pool_contract);
         storage.sorted_troves_contract.write(sorted_troves_contract);
+        storage.stability_pool_contract.write(stability_pool_contract);
         storage
             .usdf_asset_id
             .write(AssetId::new(usdf_contract, SubId::zero()));
@@ -137,7 +140,13 @@ impl BorrowOperations for Contract {
         vars.net_debt = usdf_amount;
         vars.price = oracle.get_price();
         require_trove_is_not_active(sender, asset_contracts.trove_manager);
-        vars.usdf_fee = internal_trigger_borrowing_fee(vars.net_debt, usdf_contract, fpt_staking_contract);
+        require_sorted_troves_not_full(asset_contract, sorted_troves_contract);
+        vars.usdf_fee = internal_trigger_borrowing_fee(
+            vars.net_debt,
+            usdf_contract,
+            fpt_staking_contract,
+            asset_contracts.trove_manager,
+        );
         vars.net_debt += vars.usdf_fee;
         require_at_least_min_net_debt(vars.net_debt);
         vars.icr = fm_compute_cr(msg_amount(), vars.net_debt, vars.price);
@@ -184,6 +193,22 @@ impl BorrowOperations for Contract {
             msg_asset_id(),
         );
     }
+    // Add a depositor's Stability Pool asset gain to their trove, only callable by the Stability Pool
+    #[storage(read, write), payable]
+    fn move_asset_gain_to_trove(borrower: Identity, upper_hint: Identity, lower_hint: Identity) {
+        require_is_stability_pool();
+        require_valid_asset_id();
+        internal_adjust_trove(
+            borrower,
+            msg_amount(),
+            0,
+            0,
+            false,
+            upper_hint,
+            lower_hint,
+            msg_asset_id(),
+        );
+    }
     // Withdraw collateral from an existing trove
     #[storage(read, write)]
     fn withdraw_coll(
@@ -302,6 +327,39 @@ impl BorrowOperations for Contract {
         });
         storage.lock_close_trove.write(false);
     }
+    // Opt the caller's trove in to keeper deleveraging once its ICR falls below the trigger, zero ratios opt out
+    #[storage(read)]
+    fn set_auto_deleverage(trigger_icr: u64, target_icr: u64, asset_contract: AssetId) {
+        require(
+            storage
+                .valid_asset_ids
+                .get(asset_contract)
+                .try_read()
+                .unwrap_or(false),
+            "Borrow Operations: Invalid asset",
+        );
+        let trove_manager_contract = storage.asset_contracts.get(asset_contract).read().trove_manager;
+        let borrower = msg_sender().unwrap();
+        require_trove_is_active(borrower, trove_manager_contract);
+        require(
+            (trigger_icr == 0 && target_icr == 0) || (trigger_icr > MCR && target_icr > trigger_icr),
+            "Borrow Operations: Deleverage trigger must be above the MCR and below the target",
+        );
+        let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+        trove_manager.set_auto_deleverage(
+            borrower,
+            AutoDeleverageSettings {
+                trigger_icr,
+                target_icr,
+            },
+        );
+        log(AutoDeleverageSetEvent {
+            user: borrower,
+            asset_id: asset_contract,
+            trigger_icr,
+            target_icr,
+        });
+    }
     // Claim collateral from liquidations
     #[storage(read)]
     fn claim_collateral(asset: AssetId) {
@@ -346,24 +404,47 @@ impl BorrowOperations for Contract {
 }
 
 // --- Internal Functions ---
-// Note: flat borrowing fee
+// Note: flat borrowing fee, the asset's reserve share funds its bad debt reserve
 fn internal_trigger_borrowing_fee(
     usdf_amount: u64,
     usdf_contract: ContractId,
     fpt_staking_contract: ContractId,
+    trove_manager_contract: ContractId,
 ) -> u64 {
     let usdf = abi(SRC3, usdf_contract.bits());
     let fpt_staking = abi(FPTStaking, fpt_staking_contract.bits());
+    let trove_manager = abi(TroveManager, trove_manager_contract.bits());
     let usdf_fee = fm_compute_borrow_fee(usdf_amount);
-
-    //increase fpt staking rewards
-    fpt_staking.increase_f_usdf(usdf_fee);
-    // Mint usdf to fpt staking contract
-    usdf.mint(
-        Identity::ContractId(fpt_staking_contract),
-        Some(SubId::zero()),
+    let reserve_fee = fm_multiply_ratio(
         usdf_fee,
+        trove_manager
+            .get_reserve_fee_share(),
+        DECIMAL_PRECISION,
     );
+    let staking_fee = usdf_fee - reserve_fee;
+
+    if staking_fee > 0 {
+        //increase fpt staking rewards
+        fpt_staking.increase_f_usdf(staking_fee);
+        // Mint usdf to fpt staking contract
+        usdf.mint(
+            Identity::ContractId(fpt_staking_contract),
+            Some(SubId::zero()),
+            staking_fee,
+        );
+    }
+    if reserve_fee > 0 {
+        usdf.mint(
+            Identity::ContractId(ContractId::this()),
+            Some(SubId::zero()),
+            reserve_fee,
+        );
+        trove_manager
+            .fund_reserve {
+                coins: reserve_fee,
+                asset_id: AssetId::new(usdf_contract, SubId::zero()).bits(),
+            }();
+    }
 
     return usdf_fee
 }
@@ -414,6 +495,7 @@ fn internal_adjust_trove(
             vars.net_debt_change,
             usdf_contract_cache,
             fpt_staking_contract_cache,
+            asset_contracts_cache.trove_manager,
         );
         vars.net_debt_change = vars.net_debt_change + vars.usdf_fee;
     }
@@ -493,6 +575,15 @@ fn require_is_protocol_manager() {
     );
 }
 #[storage(read)]
+fn require_is_stability_pool() {
+    let stability_pool = Identity::ContractId(storage.stability_pool_contract.read());
+    require(
+        msg_sender()
+            .unwrap() == stability_pool,
+        "Borrow Operations: Caller is not the stability pool",
+    );
+}
+#[storage(read)]
 fn require_is_pauser() {
     require(
         msg_sender()
@@ -519,6 +610,13 @@ fn require_trove_is_not_active(borrower: Identity, trove_manager: ContractId) {
         "Borrow Operations: User already has an active Trove",
     );
 }
+fn require_sorted_troves_not_full(asset: AssetId, sorted_troves_contract: ContractId) {
+    let sorted_troves = abi(SortedTroves, sorted_troves_contract.bits());
+    require(
+        !sorted_troves.is_full(asset),
+        "Borrow Operations: Sorted troves list is full for this asset",
+    );
+}
 fn require_trove_is_active(borrower: Identity, trove_manage_contract: ContractId) {
     let trove_manager = abi(TroveManager, trove_manage_contract.bits());
     let status = trove_manager.get_trove_status(borrower);
@@ -1,11 +1,12 @@
 use fuels::{prelude::*, types::Identity};
 
 use test_utils::{
-    data_structures::PRECISION,
+    data_structures::{ContractInstance, PRECISION},
     interfaces::{
         active_pool::active_pool_abi,
-        borrow_operations::borrow_operations_abi,
+        borrow_operations::{borrow_operations_abi, BorrowOperations},
         oracle::oracle_abi,
+        protocol_manager::protocol_manager_abi,
         pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
         sorted_troves::sorted_troves_abi,
         token::token_abi,
@@ -556,3 +557,133 @@ async fn fails_incorrect_token_as_collateral_or_repayment() {
         "Borrow operation: Should not be able to repay with incorrect token as repayment"
     );
 }
+
+#[tokio::test]
+async fn fails_open_trove_when_sorted_troves_full() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+
+    let wallet1 = wallets.pop().unwrap();
+
+    let balance = 5000 * PRECISION;
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        balance,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        balance,
+        Identity::Address(wallet1.address().into()),
+    )
+    .await;
+
+    let borrow_operations_wallet1 = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            wallet1.clone(),
+        ),
+        contracts.borrow_operations.implementation_id.clone(),
+    );
+
+    protocol_manager_abi::set_sorted_troves_asset_max_size(
+        &contracts.protocol_manager,
+        &contracts.sorted_troves,
+        contracts.asset_contracts[0].asset_id,
+        1,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    let col_amount = 1_200 * PRECISION;
+    let debt_amount = 600 * PRECISION;
+
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        col_amount,
+        debt_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let remaining = sorted_troves_abi::get_remaining_capacity(
+        &contracts.sorted_troves,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(remaining, 0);
+
+    let res = borrow_operations_abi::open_trove(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        col_amount,
+        debt_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+
+    assert!(
+        res.is_err(),
+        "Borrow operation: Should not be able to open trove when the sorted troves list is full"
+    );
+    let err = format!("{:?}", res.unwrap_err());
+    assert!(err.contains("Sorted troves list is full for this asset"));
+
+    // Raising the cap lets the second borrower in
+    protocol_manager_abi::set_sorted_troves_asset_max_size(
+        &contracts.protocol_manager,
+        &contracts.sorted_troves,
+        contracts.asset_contracts[0].asset_id,
+        2,
+    )
+    .await
+    .unwrap();
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_wallet1,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        col_amount,
+        debt_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+}
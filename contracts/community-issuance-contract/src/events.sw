@@ -0,0 +1,16 @@
+library;
+
+use libraries::community_issuance_interface::EmissionPhase;
+
+pub struct FPTIssuedEvent {
+    pub issuance: u64,
+    pub total_fpt_issued: u64,
+    pub supply_cap: u64,
+    pub timestamp: u64,
+}
+
+pub struct EmissionScheduleUpdatedEvent {
+    pub phases: Vec<EmissionPhase>,
+    pub start_time: u64,
+    pub start_supply_cap: u64,
+}
@@ -14,9 +14,11 @@ contract;
 // It also handles the transition period between different issuance rates, allowing for
 // a smooth change in the token distribution strategy over time.
 
+mod events;
 mod utils;
 
-use libraries::community_issuance_interface::CommunityIssuance;
+use ::events::{EmissionScheduleUpdatedEvent, FPTIssuedEvent};
+use libraries::community_issuance_interface::{CommunityIssuance, EmissionPhase};
 use libraries::fluid_math::{dec_pow, DECIMAL_PRECISION, fm_multiply_ratio};
 use ::utils::*;
 use std::{
@@ -35,6 +37,7 @@ use std::{
         balance_of,
         msg_amount,
     },
+    storage::storage_vec::*,
 };
 use sway_libs::ownership::*;
 use standards::{src5::*,};
@@ -58,6 +61,11 @@ storage {
     has_transitioned_rewards: bool = false,
     time_transition_started: u64 = 0,
     total_transition_time_seconds: u64 = 0,
+    // Owner configured emission schedule, supersedes the rewards increase transition once set
+    has_emission_schedule: bool = false,
+    emission_phases: StorageVec<EmissionPhase> = StorageVec {},
+    schedule_start_time: u64 = 0,
+    schedule_start_supply_cap: u64 = 0,
 }
 
 /// @title Community Issuance Contract
@@ -123,27 +131,116 @@ impl CommunityIssuance for Contract {
     fn issue_fpt() -> u64 {
         internal_require_caller_is_stability_pool();
 
-        let latest_total_fpt_issued = fm_multiply_ratio(
-            internal_get_fpt_supply_cap(
-                storage
-                    .time_transition_started
-                    .read(),
-                storage
-                    .total_transition_time_seconds
-                    .read(),
-                internal_get_current_time(),
-                storage
-                    .has_transitioned_rewards
-                    .read(),
-            ),
-            internal_get_cumulative_issuance_fraction(internal_get_current_time(), storage.deployment_time.read()),
-            DECIMAL_PRECISION,
-        );
+        let current_time = internal_get_current_time();
+        let latest_total_fpt_issued = internal_get_total_fpt_issued_at(current_time);
         let issuance = latest_total_fpt_issued - storage.total_fpt_issued.read();
         storage.total_fpt_issued.write(latest_total_fpt_issued);
+
+        log(FPTIssuedEvent {
+            issuance,
+            total_fpt_issued: latest_total_fpt_issued,
+            supply_cap: internal_get_supply_cap_at(current_time),
+            timestamp: current_time,
+        });
         return issuance
     }
 
+    /// @notice Replaces the supply cap curve with an emission schedule made of consecutive phases
+    /// @dev The schedule starts now from the current supply cap, each phase ramps the cap linearly to its target
+    /// over its duration. Setting a schedule ends the rewards increase transition mechanism
+    /// @param phases The phases of the schedule, in order
+    /// @custom:throws "CommunityIssuance: Emission schedule must have at least one phase" if phases is empty
+    /// @custom:throws "CommunityIssuance: Phase duration must be greater than 0" if a phase has no duration
+    /// @custom:throws "CommunityIssuance: Phase target cannot decrease the supply cap" if a target is below the previous cap
+    /// @custom:throws "CommunityIssuance: Phase target exceeds the FPT supply cap" if a target is above FPT_SUPPLY_CAP
+    /// @custom:access-control Admin only
+    #[storage(read, write)]
+    fn set_emission_schedule(phases: Vec<EmissionPhase>) {
+        only_owner();
+        require(
+            phases
+                .len() > 0,
+            "CommunityIssuance: Emission schedule must have at least one phase",
+        );
+
+        let current_time = internal_get_current_time();
+        let start_supply_cap = internal_get_supply_cap_at(current_time);
+
+        let mut previous_supply_cap = start_supply_cap;
+        let mut i = 0;
+        while i < phases.len() {
+            let phase = phases.get(i).unwrap();
+            require(
+                phase
+                    .duration > 0,
+                "CommunityIssuance: Phase duration must be greater than 0",
+            );
+            require(
+                phase
+                    .target_supply_cap >= previous_supply_cap,
+                "CommunityIssuance: Phase target cannot decrease the supply cap",
+            );
+            require(
+                phase
+                    .target_supply_cap <= FPT_SUPPLY_CAP,
+                "CommunityIssuance: Phase target exceeds the FPT supply cap",
+            );
+            previous_supply_cap = phase.target_supply_cap;
+            i += 1;
+        }
+
+        storage.emission_phases.clear();
+        let mut i = 0;
+        while i < phases.len() {
+            storage.emission_phases.push(phases.get(i).unwrap());
+            i += 1;
+        }
+        storage.has_emission_schedule.write(true);
+        storage.has_transitioned_rewards.write(true);
+        storage.schedule_start_time.write(current_time);
+        storage.schedule_start_supply_cap.write(start_supply_cap);
+
+        log(EmissionScheduleUpdatedEvent {
+            phases,
+            start_time: current_time,
+            start_supply_cap,
+        });
+    }
+
+    /// @notice Retrieves the phases of the emission schedule
+    /// @return The phases, empty if no schedule has been set
+    #[storage(read)]
+    fn get_emission_schedule() -> Vec<EmissionPhase> {
+        storage.emission_phases.load_vec()
+    }
+
+    /// @notice Retrieves the current supply cap of the issuance curve
+    /// @return The supply cap at the current time
+    #[storage(read)]
+    fn get_supply_cap() -> u64 {
+        internal_get_supply_cap_at(internal_get_current_time())
+    }
+
+    /// @notice Retrieves the total amount of FPT issued so far
+    /// @return The total FPT issued at the last issue_fpt call
+    #[storage(read)]
+    fn get_total_fpt_issued() -> u64 {
+        storage.total_fpt_issued.read()
+    }
+
+    /// @notice Projects the total amount of FPT issued by a future time under the current schedule
+    /// @param time The timestamp to project to
+    /// @return The total FPT issued by the given time
+    /// @custom:throws "CommunityIssuance: Projection time cannot be in the past" if time is before the current time
+    #[storage(read)]
+    fn get_projected_issuance(time: u64) -> u64 {
+        require(
+            time >= internal_get_current_time(),
+            "CommunityIssuance: Projection time cannot be in the past",
+        );
+        internal_get_total_fpt_issued_at(time)
+    }
+
     /// @notice Sends FPT tokens to a specified account
     /// @dev Can only be called by the Stability Pool contract
     /// @param account The Identity of the account to receive the FPT tokens
@@ -268,3 +365,51 @@ fn internal_get_current_time() -> u64 {
         return timestamp();
     }
 }
+
+/// @notice Gets the supply cap of the issuance curve at a given time
+/// @dev Uses the emission schedule once set, otherwise the rewards increase transition curve
+/// @param time The timestamp, not before the start of the schedule or transition
+/// @return The supply cap at the given time
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read)]
+fn internal_get_supply_cap_at(time: u64) -> u64 {
+    if storage.has_emission_schedule.read() {
+        return internal_get_scheduled_supply_cap(
+            storage
+                .schedule_start_supply_cap
+                .read(),
+            storage
+                .emission_phases
+                .load_vec(),
+            storage
+                .schedule_start_time
+                .read(),
+            time,
+        );
+    }
+    internal_get_fpt_supply_cap(
+        storage
+            .time_transition_started
+            .read(),
+        storage
+            .total_transition_time_seconds
+            .read(),
+        time,
+        storage
+            .has_transitioned_rewards
+            .read(),
+    )
+}
+
+/// @notice Gets the total amount of FPT issued by a given time
+/// @param time The timestamp
+/// @return The supply cap at the given time scaled by the cumulative issuance fraction
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read)]
+fn internal_get_total_fpt_issued_at(time: u64) -> u64 {
+    fm_multiply_ratio(
+        internal_get_supply_cap_at(time),
+        internal_get_cumulative_issuance_fraction(time, storage.deployment_time.read()),
+        DECIMAL_PRECISION,
+    )
+}
@@ -1,5 +1,6 @@
 library;
 
+use libraries::community_issuance_interface::EmissionPhase;
 use libraries::fluid_math::{dec_pow, DECIMAL_PRECISION, fm_abs_diff, fm_min};
 use std::u128::U128;
 // 32_000_000 * 1_000_000_000
@@ -29,6 +30,30 @@ pub fn internal_get_fpt_supply_cap(
         return current_supply_cap_64;
     }
 }
+// Supply cap of an owner configured emission schedule, each phase ramps linearly from the cap at its start
+// to its target, and the cap stays at the last target once every phase has elapsed
+pub fn internal_get_scheduled_supply_cap(
+    start_supply_cap: u64,
+    phases: Vec<EmissionPhase>,
+    schedule_start_time: u64,
+    current_time: u64,
+) -> u64 {
+    let mut supply_cap = start_supply_cap;
+    let mut time_remaining = current_time - schedule_start_time;
+    let mut i = 0;
+    while i < phases.len() {
+        let phase = phases.get(i).unwrap();
+        if (time_remaining >= phase.duration) {
+            supply_cap = phase.target_supply_cap;
+            time_remaining -= phase.duration;
+        } else {
+            let increase = U128::from(phase.target_supply_cap - supply_cap) * U128::from(time_remaining) / U128::from(phase.duration);
+            return supply_cap + increase.as_u64().unwrap();
+        }
+        i += 1;
+    }
+    return supply_cap;
+}
 pub fn internal_get_cumulative_issuance_fraction(current_time: u64, deployment_time: u64) -> u64 {
     let time_passed_in_minutes = (current_time - deployment_time) / SECONDS_IN_ONE_MINUTE;
     let power = dec_pow(ISSUANCE_FACTOR, time_passed_in_minutes);
@@ -450,3 +475,31 @@ fn test_emissions_for_overflow() {
         has_transitioned_rewards,
     );
 }
+#[test]
+fn test_scheduled_supply_cap() {
+    let mut phases: Vec<EmissionPhase> = Vec::new();
+    phases.push(EmissionPhase {
+        target_supply_cap: FPT_SUPPLY_CAP * 3 / 4,
+        duration: 1_000,
+    });
+    phases.push(EmissionPhase {
+        target_supply_cap: FPT_SUPPLY_CAP,
+        duration: 2_000,
+    });
+    let start_supply_cap = FPT_SUPPLY_CAP / 2;
+    // start of the schedule
+    let supply_cap = internal_get_scheduled_supply_cap(start_supply_cap, phases, 100, 100);
+    assert(supply_cap == FPT_SUPPLY_CAP / 2);
+    // halfway through the first phase
+    let supply_cap = internal_get_scheduled_supply_cap(start_supply_cap, phases, 100, 600);
+    assert(supply_cap == FPT_SUPPLY_CAP * 5 / 8);
+    // end of the first phase
+    let supply_cap = internal_get_scheduled_supply_cap(start_supply_cap, phases, 100, 1_100);
+    assert(supply_cap == FPT_SUPPLY_CAP * 3 / 4);
+    // halfway through the second phase
+    let supply_cap = internal_get_scheduled_supply_cap(start_supply_cap, phases, 100, 2_100);
+    assert(supply_cap == FPT_SUPPLY_CAP * 7 / 8);
+    // after the last phase
+    let supply_cap = internal_get_scheduled_supply_cap(start_supply_cap, phases, 100, 1_000_000);
+    assert(supply_cap == FPT_SUPPLY_CAP);
+}
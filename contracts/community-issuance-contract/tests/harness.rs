@@ -4,14 +4,17 @@ use test_utils::{
     data_structures::{ContractInstance, PRECISION},
     interfaces::{
         borrow_operations::{borrow_operations_abi, BorrowOperations},
-        community_issuance::{community_issuance_abi, CommunityIssuance},
+        community_issuance::{community_issuance_abi, CommunityIssuance, EmissionPhase},
         oracle::oracle_abi,
         pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
         stability_pool::{stability_pool_abi, StabilityPool},
         token::token_abi,
     },
     setup::common::setup_protocol,
-    utils::print_response,
+    utils::{
+        get_fpt_supply_cap, get_scheduled_supply_cap, get_total_fpt_issued, print_response,
+        FPT_SUPPLY_CAP,
+    },
 };
 
 fn abs_dif(a: u64, b: u64) -> u64 {
@@ -688,3 +691,104 @@ async fn test_only_owner_can_start_rewards_increase_transition() {
         "Authorized user should be able to start rewards increase transition"
     );
 }
+
+#[tokio::test]
+async fn test_emission_schedule_matches_calculator() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, false).await;
+
+    let schedule_start = 60 * 60 * 24 * 30;
+    community_issuance_abi::set_current_time(&contracts.community_issuance, schedule_start).await;
+
+    let supply_cap = community_issuance_abi::get_supply_cap(&contracts.community_issuance)
+        .await
+        .value;
+    assert_eq!(
+        supply_cap,
+        get_fpt_supply_cap(0, 0, schedule_start, false),
+        "Supply cap before the schedule does not match the calculator"
+    );
+
+    let phases = vec![
+        EmissionPhase {
+            target_supply_cap: FPT_SUPPLY_CAP * 3 / 4,
+            duration: 60 * 60 * 24 * 90,
+        },
+        EmissionPhase {
+            target_supply_cap: FPT_SUPPLY_CAP,
+            duration: 60 * 60 * 24 * 180,
+        },
+    ];
+
+    // only the owner can set the schedule
+    let attacker = wallets.pop().unwrap();
+    let community_issuance_attacker = ContractInstance::new(
+        CommunityIssuance::new(
+            contracts.community_issuance.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.community_issuance.implementation_id,
+    );
+    community_issuance_abi::set_emission_schedule(&community_issuance_attacker, phases.clone())
+        .await
+        .expect_err("Unauthorized user should not be able to set the emission schedule");
+
+    // a phase cannot lower the supply cap below the current one
+    community_issuance_abi::set_emission_schedule(
+        &contracts.community_issuance,
+        vec![EmissionPhase {
+            target_supply_cap: FPT_SUPPLY_CAP / 4,
+            duration: 60 * 60 * 24,
+        }],
+    )
+    .await
+    .expect_err("Emission schedule lowering the supply cap was allowed");
+
+    community_issuance_abi::set_emission_schedule(&contracts.community_issuance, phases.clone())
+        .await
+        .unwrap();
+
+    let stored_phases =
+        community_issuance_abi::get_emission_schedule(&contracts.community_issuance)
+            .await
+            .value;
+    assert_eq!(stored_phases, phases);
+
+    // the schedule supersedes the rewards increase transition
+    community_issuance_abi::start_rewards_increase_transition(
+        &contracts.community_issuance,
+        604800 + 1,
+    )
+    .await
+    .expect_err("Rewards increase transition allowed after setting a schedule");
+
+    for time in [
+        schedule_start + 60 * 60 * 24 * 45,
+        schedule_start + 60 * 60 * 24 * 90,
+        schedule_start + 60 * 60 * 24 * 200,
+        schedule_start + 60 * 60 * 24 * 365 * 2,
+    ] {
+        let expected_supply_cap =
+            get_scheduled_supply_cap(FPT_SUPPLY_CAP / 2, &phases, schedule_start, time);
+        let projected =
+            community_issuance_abi::get_projected_issuance(&contracts.community_issuance, time)
+                .await
+                .value;
+        assert_eq!(
+            projected,
+            get_total_fpt_issued(expected_supply_cap, time, 0),
+            "Projected issuance does not match the calculator"
+        );
+    }
+
+    let time = schedule_start + 60 * 60 * 24 * 45;
+    community_issuance_abi::set_current_time(&contracts.community_issuance, time).await;
+
+    let supply_cap = community_issuance_abi::get_supply_cap(&contracts.community_issuance)
+        .await
+        .value;
+    assert_eq!(
+        supply_cap,
+        FPT_SUPPLY_CAP * 5 / 8,
+        "Supply cap halfway through the first phase is wrong"
+    );
+}
@@ -9,3 +9,31 @@ pub struct UnstakeEvent {
     pub user: Identity,
     pub amount: u64,
 }
+
+pub struct LockedStakeEvent {
+    pub user: Identity,
+    pub position_id: u64,
+    pub amount: u64,
+    pub weight: u64,
+    pub unlock_time: u64,
+}
+
+pub struct LockedUnstakeEvent {
+    pub user: Identity,
+    pub position_id: u64,
+    pub amount: u64,
+    pub penalty: u64,
+}
+
+pub struct LockedBoostExpiredEvent {
+    pub user: Identity,
+    pub position_id: u64,
+    pub weight: u64,
+}
+
+pub struct ClaimEvent {
+    pub user: Identity,
+    pub recipient: Identity,
+    pub asset_id: AssetId,
+    pub amount: u64,
+}
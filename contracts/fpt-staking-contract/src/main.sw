@@ -2,7 +2,14 @@ contract;
 
 mod events;
 
-use ::events::{StakeEvent, UnstakeEvent};
+use ::events::{
+    ClaimEvent,
+    LockedBoostExpiredEvent,
+    LockedStakeEvent,
+    LockedUnstakeEvent,
+    StakeEvent,
+    UnstakeEvent,
+};
 use libraries::fluid_math::{
     DECIMAL_PRECISION,
     fm_min,
@@ -10,10 +17,11 @@ use libraries::fluid_math::{
     null_contract,
     null_identity_address,
 };
-use libraries::fpt_staking_interface::{FPTStaking, ReadStorage};
+use libraries::fpt_staking_interface::{FPTStaking, LockedPosition, ReadStorage};
 use std::{
     asset::transfer,
     auth::msg_sender,
+    block::timestamp,
     call_frames::{
         msg_asset_id,
     },
@@ -24,6 +32,14 @@ use std::{
     storage::storage_vec::*,
 };
 
+const ONE_WEEK_IN_SECONDS: u64 = 604_800;
+const MIN_LOCK_WEEKS: u64 = 1;
+const MAX_LOCK_WEEKS: u64 = 52;
+// Boost of a MAX_LOCK_WEEKS lock, boosts scale linearly from 1x with the lock duration (2.5x)
+const MAX_LOCK_BOOST: u64 = 2_500_000_000;
+// Share of a locked position forfeited when exiting right after locking, scales down linearly to 0 at unlock (50%)
+const MAX_EARLY_EXIT_PENALTY: u64 = 500_000_000;
+
 configurable {
     /// Initializer identity
     INITIALIZER: Identity = Identity::Address(Address::zero()),
@@ -44,11 +60,27 @@ storage {
     is_initialized: bool = false,
     lock_stake: bool = false,
     lock_unstake: bool = false,
+    // FPT forfeited by early exits, per unit of weight
+    f_fpt: u64 = 0,
+    fpt_snapshot: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    // Time-locked positions, tracked apart from the liquid stake of each identity with their own snapshots
+    locked_positions: StorageMap<(Identity, u64), LockedPosition> = StorageMap::<(Identity, u64), LockedPosition> {},
+    next_position_id: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    locked_usdf_snapshot: StorageMap<(Identity, u64), u64> = StorageMap::<(Identity, u64), u64> {},
+    locked_asset_snapshot: StorageMap<(Identity, u64, AssetId), u64> = StorageMap::<(Identity, u64, AssetId), u64> {},
+    locked_fpt_snapshot: StorageMap<(Identity, u64), u64> = StorageMap::<(Identity, u64), u64> {},
+    total_locked_fpt: u64 = 0,
+    total_locked_weight: u64 = 0,
+    // timestamp is used for testing purposes only, as Fuel does not support timestamp currently in integration tests
+    debug: bool = false,
+    debug_timestamp: u64 = 0,
 }
 /// @title FPT Staking Contract
 /// @author Fluid Protocol
 /// @notice This contract allows users to stake FPT tokens and earn rewards in USDF and other assets
 /// @dev Implements the FPTStaking interface for staking, unstaking, and reward distribution
+/// @dev Fees are shared pro rata to weight: liquid stakes weigh their amount, time-locked positions
+///      weigh their amount times a boost growing with the lock duration, until they are unlocked
 impl FPTStaking for Contract {
     /// @notice Initializes the FPT Staking contract with essential addresses and tokens
     /// @dev Can only be called once, sets up the contract for staking operations
@@ -56,12 +88,14 @@ impl FPTStaking for Contract {
     /// @param borrower_operations_address The address of the borrower operations contract
     /// @param fpt_asset_id The asset ID of the FPT token
     /// @param usdf_asset_id The asset ID of the USDF token
+    /// @param debugging A boolean flag to enable or disable debug mode
     #[storage(read, write)]
     fn initialize(
         protocol_manager_address: ContractId,
         borrower_operations_address: ContractId,
         fpt_asset_id: AssetId,
         usdf_asset_id: AssetId,
+        debugging: bool,
     ) {
         require(
             msg_sender()
@@ -82,6 +116,7 @@ impl FPTStaking for Contract {
             .write(borrower_operations_address);
         storage.fpt_asset_id.write(fpt_asset_id);
         storage.usdf_asset_id.write(usdf_asset_id);
+        storage.debug.write(debugging);
         storage.is_initialized.write(true);
     }
 
@@ -109,6 +144,7 @@ impl FPTStaking for Contract {
         if (current_stake != 0) {
             let usdf_gain = internal_get_pending_usdf_gain(id);
             internal_send_usdf_gain_to_user(usdf_gain);
+            internal_send_fpt_gain_to_user(internal_get_pending_fpt_gain(id));
 
             internal_send_pending_asset_gain_to_user(id);
         }
@@ -148,6 +184,7 @@ impl FPTStaking for Contract {
 
         let usdf_gain = internal_get_pending_usdf_gain(id);
         internal_send_usdf_gain_to_user(usdf_gain);
+        internal_send_fpt_gain_to_user(internal_get_pending_fpt_gain(id));
         internal_send_pending_asset_gain_to_user(id);
 
         internal_update_user_snapshots(id);
@@ -181,6 +218,174 @@ impl FPTStaking for Contract {
         storage.lock_unstake.write(false);
     }
 
+    /// @notice Claims the pending gains of the selected assets without changing the stake
+    /// @dev Covers the liquid stake and every time-locked position of the caller. Only the snapshots of the
+    /// claimed assets are updated, so unclaimed assets keep accruing
+    /// @param assets The assets to claim, the FPT asset id selects the early exit penalty gains
+    /// @param include_usdf Whether to also claim the USDF gain
+    /// @custom:throws "FPTStaking: Asset is not valid" if an asset is neither an added asset nor FPT
+    #[storage(read, write)]
+    fn claim(assets: Vec<AssetId>, include_usdf: bool) {
+        let id = msg_sender().unwrap();
+        internal_claim(id, id, assets, include_usdf);
+    }
+
+    /// @notice Claims the pending gains of the selected assets and sends them to a recipient
+    /// @dev Same as claim, but the gains of the caller are transferred to the recipient
+    /// @param recipient The Identity receiving the gains
+    /// @param assets The assets to claim, the FPT asset id selects the early exit penalty gains
+    /// @param include_usdf Whether to also claim the USDF gain
+    /// @custom:throws "FPTStaking: Asset is not valid" if an asset is neither an added asset nor FPT
+    #[storage(read, write)]
+    fn claim_to(recipient: Identity, assets: Vec<AssetId>, include_usdf: bool) {
+        internal_claim(msg_sender().unwrap(), recipient, assets, include_usdf);
+    }
+
+    /// @notice Stakes FPT tokens in a new time-locked position with a boosted share of fees
+    /// @dev The position is tracked apart from the liquid stake, with its own snapshots
+    /// @param lock_weeks The lock duration in weeks, between MIN_LOCK_WEEKS and MAX_LOCK_WEEKS
+    /// @return The id of the new position
+    /// @custom:payable This function is payable and expects FPT tokens to be sent with the transaction
+    /// @custom:throws "FPTStaking: Lock duration is out of range" if lock_weeks is not in [MIN_LOCK_WEEKS, MAX_LOCK_WEEKS]
+    #[storage(read, write), payable]
+    fn stake_locked(lock_weeks: u64) -> u64 {
+        require(
+            storage
+                .lock_stake
+                .read() == false,
+            "FPTStaking: Stake is locked",
+        );
+        storage.lock_stake.write(true);
+
+        let id = msg_sender().unwrap();
+
+        require_fpt_is_valid_and_non_zero();
+        require(
+            lock_weeks >= MIN_LOCK_WEEKS && lock_weeks <= MAX_LOCK_WEEKS,
+            "FPTStaking: Lock duration is out of range",
+        );
+
+        let amount = msg_amount();
+        let weight = fm_multiply_ratio(amount, internal_get_lock_boost(lock_weeks), DECIMAL_PRECISION);
+        let position_id = storage.next_position_id.get(id).try_read().unwrap_or(0);
+        let lock_time = internal_get_current_time();
+        let unlock_time = lock_time + lock_weeks * ONE_WEEK_IN_SECONDS;
+
+        storage.next_position_id.insert(id, position_id + 1);
+        storage.locked_positions.insert(
+            (id, position_id),
+            LockedPosition {
+                position_id,
+                amount,
+                weight,
+                lock_time,
+                unlock_time,
+            },
+        );
+        internal_update_position_snapshots(id, position_id);
+
+        storage
+            .total_locked_fpt
+            .write(storage.total_locked_fpt.read() + amount);
+        storage
+            .total_locked_weight
+            .write(storage.total_locked_weight.read() + weight);
+
+        log(LockedStakeEvent {
+            user: id,
+            position_id,
+            amount,
+            weight,
+            unlock_time,
+        });
+        storage.lock_stake.write(false);
+        position_id
+    }
+
+    /// @notice Unstakes a time-locked position and pays out its pending gains
+    /// @dev Before the unlock time a penalty, proportional to the remaining lock time, is forfeited
+    ///      and redistributed to the remaining stakers pro rata to their weight
+    /// @param position_id The id of the position to unstake
+    /// @custom:throws "FPTStaking: Locked position does not exist" if the caller has no such position
+    #[storage(read, write)]
+    fn unstake_locked(position_id: u64) {
+        require(
+            storage
+                .lock_unstake
+                .read() == false,
+            "FPTStaking: Unstake is locked",
+        );
+        storage.lock_unstake.write(true);
+
+        let id = msg_sender().unwrap();
+
+        let position = storage.locked_positions.get((id, position_id)).try_read();
+        require(
+            position
+                .is_some(),
+            "FPTStaking: Locked position does not exist",
+        );
+
+        // An unlocked position settles the gains accrued at its boost before exiting at 1x
+        internal_expire_position_boost(id, id, position_id);
+        let position = storage.locked_positions.get((id, position_id)).read();
+
+        internal_send_usdf_gain_to_user(internal_get_pending_locked_usdf_gain(id, position_id));
+        internal_send_fpt_gain_to_user(internal_get_pending_locked_fpt_gain(id, position_id));
+        internal_send_pending_locked_asset_gain_to_user(id, position_id);
+
+        internal_remove_position(id, position_id);
+        storage
+            .total_locked_fpt
+            .write(storage.total_locked_fpt.read() - position.amount);
+        storage
+            .total_locked_weight
+            .write(storage.total_locked_weight.read() - position.weight);
+
+        // With no remaining stakers to receive it, the position exits without penalty
+        let mut penalty = internal_get_early_exit_penalty(position, internal_get_current_time());
+        let total_weight = internal_get_total_weight();
+        if (penalty > 0 && total_weight > 0) {
+            storage
+                .f_fpt
+                .write(storage.f_fpt.read() + fm_multiply_ratio(penalty, DECIMAL_PRECISION, total_weight));
+        } else {
+            penalty = 0;
+        }
+
+        let amount_to_withdraw = position.amount - penalty;
+        if (amount_to_withdraw > 0) {
+            transfer(id, storage.fpt_asset_id.read(), amount_to_withdraw);
+        }
+
+        log(LockedUnstakeEvent {
+            user: id,
+            position_id,
+            amount: amount_to_withdraw,
+            penalty,
+        });
+        storage.lock_unstake.write(false);
+    }
+
+    /// @notice Drops the weight of an unlocked time-locked position back to 1x
+    /// @dev Callable by anyone so that expired boosts stop diluting the other stakers. The gains accrued
+    ///      at the boosted weight are paid out to the owner of the position first
+    /// @param id The Identity owning the position
+    /// @param position_id The id of the position
+    /// @custom:throws "FPTStaking: Locked position does not exist" if the identity has no such position
+    #[storage(read, write)]
+    fn poke_locked_position(id: Identity, position_id: u64) {
+        require(
+            storage
+                .locked_positions
+                .get((id, position_id))
+                .try_read()
+                .is_some(),
+            "FPTStaking: Locked position does not exist",
+        );
+        internal_expire_position_boost(id, id, position_id);
+    }
+
     /// @notice Adds a new asset to the staking contract
     /// @dev Can only be called by the protocol manager, called in the `register_asset` fn
     /// @param asset_address The AssetId of the new asset to be added
@@ -220,21 +425,138 @@ impl FPTStaking for Contract {
         storage.stakes.get(id).try_read().unwrap_or(0)
     }
 
+    /// @notice Retrieves the pending FPT gain from early exit penalties for the liquid stake of a user
+    /// @param id The Identity of the user
+    /// @return The amount of pending FPT gain for the user
+    #[storage(read)]
+    fn get_pending_fpt_gain(id: Identity) -> u64 {
+        internal_get_pending_fpt_gain(id)
+    }
+
+    /// @notice Retrieves the open time-locked positions of a user
+    /// @param id The Identity of the user
+    /// @return The positions of the user, in order of creation
+    #[storage(read)]
+    fn get_locked_positions(id: Identity) -> Vec<LockedPosition> {
+        let mut positions: Vec<LockedPosition> = Vec::new();
+        let mut position_id = 0;
+        while position_id < storage.next_position_id.get(id).try_read().unwrap_or(0) {
+            match storage.locked_positions.get((id, position_id)).try_read() {
+                Some(position) => positions.push(position),
+                None => {},
+            }
+            position_id += 1;
+        }
+        positions
+    }
+
+    /// @notice Retrieves the pending USDF gain of a time-locked position
+    /// @param id The Identity of the user
+    /// @param position_id The id of the position
+    /// @return The amount of pending USDF gain for the position
+    #[storage(read)]
+    fn get_pending_locked_usdf_gain(id: Identity, position_id: u64) -> u64 {
+        internal_get_pending_locked_usdf_gain(id, position_id)
+    }
+
+    /// @notice Retrieves the pending asset gain of a time-locked position
+    /// @param id The Identity of the user
+    /// @param position_id The id of the position
+    /// @param asset_address The AssetId of the asset to check
+    /// @return The amount of pending asset gain for the position
+    #[storage(read)]
+    fn get_pending_locked_asset_gain(id: Identity, position_id: u64, asset_address: AssetId) -> u64 {
+        internal_get_pending_locked_asset_gain(id, position_id, asset_address)
+    }
+
+    /// @notice Retrieves the pending FPT gain from early exit penalties of a time-locked position
+    /// @param id The Identity of the user
+    /// @param position_id The id of the position
+    /// @return The amount of pending FPT gain for the position
+    #[storage(read)]
+    fn get_pending_locked_fpt_gain(id: Identity, position_id: u64) -> u64 {
+        internal_get_pending_locked_fpt_gain(id, position_id)
+    }
+
+    /// @notice Retrieves the weight of a user in the fee distribution
+    /// @dev Sum of the liquid stake and the boosted weight of each time-locked position
+    /// @param id The Identity of the user
+    /// @return The effective weight of the user
+    #[storage(read)]
+    fn get_effective_weight(id: Identity) -> u64 {
+        let mut weight = storage.stakes.get(id).try_read().unwrap_or(0);
+        let mut position_id = 0;
+        while position_id < storage.next_position_id.get(id).try_read().unwrap_or(0) {
+            match storage.locked_positions.get((id, position_id)).try_read() {
+                Some(position) => {
+                    weight += position.weight;
+                },
+                None => {},
+            }
+            position_id += 1;
+        }
+        weight
+    }
+
+    /// @notice Retrieves the total weight the fees are distributed over
+    /// @return The total liquid stake plus the total weight of time-locked positions
+    #[storage(read)]
+    fn get_total_weight() -> u64 {
+        internal_get_total_weight()
+    }
+
+    /// @notice Retrieves the boost multiplier of a lock duration
+    /// @param lock_weeks The lock duration in weeks
+    /// @return The boost multiplier in DECIMAL_PRECISION
+    fn get_lock_boost(lock_weeks: u64) -> u64 {
+        internal_get_lock_boost(fm_min(lock_weeks, MAX_LOCK_WEEKS))
+    }
+
+    /// @notice Retrieves the penalty a time-locked position would forfeit if unstaked now
+    /// @param id The Identity of the user
+    /// @param position_id The id of the position
+    /// @return The FPT penalty, 0 if the position does not exist or is unlocked
+    #[storage(read)]
+    fn get_early_exit_penalty(id: Identity, position_id: u64) -> u64 {
+        match storage.locked_positions.get((id, position_id)).try_read() {
+            Some(position) => internal_get_early_exit_penalty(position, internal_get_current_time()),
+            None => 0,
+        }
+    }
+
+    /// @notice Retrieves the current timestamp used by the contract
+    /// @dev Returns the debug timestamp if debugging is enabled, otherwise returns the current block timestamp
+    /// @return The current timestamp as a u64 value
+    #[storage(read)]
+    fn get_current_time() -> u64 {
+        internal_get_current_time()
+    }
+
+    /// @notice Sets the current time for debugging purposes
+    /// @dev This function can only be called when debugging is enabled
+    /// @param time The timestamp to set as the current time
+    /// @custom:throws "FPTStaking: Debugging must be enabled to set current time" if debugging is not enabled
+    #[storage(write, read)]
+    fn set_current_time(time: u64) {
+        require(
+            storage
+                .debug
+                .read(),
+            "FPTStaking: Debugging must be enabled to set current time",
+        );
+        storage.debug_timestamp.write(time);
+    }
+
     /// @notice Increases the F_USDF value based on USDF fee amount
     /// @dev Can only be called by the Borrower Operations contract
-    /// @dev If total FPT staked is greater than 0, calculates and adds USDF fee per FPT staked
+    /// @dev If the total weight is greater than 0, calculates and adds USDF fee per unit of weight
     /// @param usdf_fee_amount The amount of USDF fee to be distributed
     #[storage(read, write)]
     fn increase_f_usdf(usdf_fee_amount: u64) {
         require_is_borrower_operations();
-        if (storage.total_fpt_staked.read() > 0) {
-            let usdf_fee_per_fpt_staked = fm_multiply_ratio(
-                usdf_fee_amount,
-                DECIMAL_PRECISION,
-                storage
-                    .total_fpt_staked
-                    .read(),
-            );
+        let total_weight = internal_get_total_weight();
+        if (total_weight > 0) {
+            let usdf_fee_per_fpt_staked = fm_multiply_ratio(usdf_fee_amount, DECIMAL_PRECISION, total_weight);
             storage
                 .f_usdf
                 .write(storage.f_usdf.read() + usdf_fee_per_fpt_staked);
@@ -243,20 +565,15 @@ impl FPTStaking for Contract {
 
     /// @notice Increases the F_Asset value for a specific asset based on the asset fee amount
     /// @dev Can only be called by the Protocol Manager contract
-    /// @dev If total FPT staked is greater than 0, calculates and adds asset fee per FPT staked
+    /// @dev If the total weight is greater than 0, calculates and adds asset fee per unit of weight
     /// @param asset_fee_amount The amount of asset fee to be distributed
     /// @param asset_address The AssetId of the asset for which the fee is being distributed
     #[storage(read, write)]
     fn increase_f_asset(asset_fee_amount: u64, asset_address: AssetId) {
         require_is_protocol_manager(); // we have redeem function in protocol manager, not trove manager in liquity
-        if (storage.total_fpt_staked.read() > 0) {
-            let asset_fee_per_fpt_staked = fm_multiply_ratio(
-                asset_fee_amount,
-                DECIMAL_PRECISION,
-                storage
-                    .total_fpt_staked
-                    .read(),
-            );
+        let total_weight = internal_get_total_weight();
+        if (total_weight > 0) {
+            let asset_fee_per_fpt_staked = fm_multiply_ratio(asset_fee_amount, DECIMAL_PRECISION, total_weight);
             let mut new_f_asset = storage.f_asset.get(asset_address).read() + asset_fee_per_fpt_staked;
             storage.f_asset.insert(asset_address, new_f_asset);
         }
@@ -331,6 +648,7 @@ fn internal_get_pending_usdf_gain(id: Identity) -> u64 {
 #[storage(read, write)]
 fn internal_update_user_snapshots(id: Identity) {
     storage.usdf_snapshot.insert(id, storage.f_usdf.read());
+    storage.fpt_snapshot.insert(id, storage.f_fpt.read());
 
     let mut ind = 0;
     while ind < storage.valid_assets.len() {
@@ -432,3 +750,379 @@ fn internal_send_usdf_gain_to_user(amount: u64) {
         transfer(msg_sender().unwrap(), storage.usdf_asset_id.read(), amount);
     }
 }
+
+/// @notice Sends accumulated FPT gains from early exit penalties to a user
+/// @dev Transfers FPT tokens to the user if the amount is greater than zero
+/// @param amount The amount of FPT to send to the user
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read)]
+fn internal_send_fpt_gain_to_user(amount: u64) {
+    if (amount > 0) {
+        transfer(msg_sender().unwrap(), storage.fpt_asset_id.read(), amount);
+    }
+}
+
+/// @notice Calculates the pending FPT gain from early exit penalties for the liquid stake of a user
+/// @param id The Identity of the user
+/// @return The pending FPT gain for the user
+#[storage(read)]
+fn internal_get_pending_fpt_gain(id: Identity) -> u64 {
+    let f_fpt_snapshot = storage.fpt_snapshot.get(id).try_read().unwrap_or(0);
+    fm_multiply_ratio(
+        storage
+            .stakes
+            .get(id)
+            .try_read()
+            .unwrap_or(0),
+        storage
+            .f_fpt
+            .read() - f_fpt_snapshot,
+        DECIMAL_PRECISION,
+    )
+}
+
+/// @notice Calculates the pending USDF gain of a time-locked position
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+/// @return The pending USDF gain for the position
+#[storage(read)]
+fn internal_get_pending_locked_usdf_gain(id: Identity, position_id: u64) -> u64 {
+    let f_usdf_snapshot = storage.locked_usdf_snapshot.get((id, position_id)).try_read().unwrap_or(0);
+    fm_multiply_ratio(
+        internal_get_position_weight(id, position_id),
+        storage
+            .f_usdf
+            .read() - f_usdf_snapshot,
+        DECIMAL_PRECISION,
+    )
+}
+
+/// @notice Calculates the pending asset gain of a time-locked position
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+/// @param asset_address The AssetId of the asset for which to calculate the gain
+/// @return The pending asset gain for the position
+#[storage(read)]
+fn internal_get_pending_locked_asset_gain(id: Identity, position_id: u64, asset_address: AssetId) -> u64 {
+    let f_asset_snapshot = storage.locked_asset_snapshot.get((id, position_id, asset_address)).try_read().unwrap_or(0);
+    fm_multiply_ratio(
+        internal_get_position_weight(id, position_id),
+        storage
+            .f_asset
+            .get(asset_address)
+            .try_read()
+            .unwrap_or(0) - f_asset_snapshot,
+        DECIMAL_PRECISION,
+    )
+}
+
+/// @notice Calculates the pending FPT gain from early exit penalties of a time-locked position
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+/// @return The pending FPT gain for the position
+#[storage(read)]
+fn internal_get_pending_locked_fpt_gain(id: Identity, position_id: u64) -> u64 {
+    let f_fpt_snapshot = storage.locked_fpt_snapshot.get((id, position_id)).try_read().unwrap_or(0);
+    fm_multiply_ratio(
+        internal_get_position_weight(id, position_id),
+        storage
+            .f_fpt
+            .read() - f_fpt_snapshot,
+        DECIMAL_PRECISION,
+    )
+}
+
+/// @notice Gets the weight of a time-locked position, 0 if it does not exist
+#[storage(read)]
+fn internal_get_position_weight(id: Identity, position_id: u64) -> u64 {
+    match storage.locked_positions.get((id, position_id)).try_read() {
+        Some(position) => position.weight,
+        None => 0,
+    }
+}
+
+/// @notice Updates the snapshots of USDF, FPT and asset gains for a time-locked position
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+#[storage(read, write)]
+fn internal_update_position_snapshots(id: Identity, position_id: u64) {
+    storage
+        .locked_usdf_snapshot
+        .insert((id, position_id), storage.f_usdf.read());
+    storage
+        .locked_fpt_snapshot
+        .insert((id, position_id), storage.f_fpt.read());
+
+    let mut ind = 0;
+    while ind < storage.valid_assets.len() {
+        let current_asset_address = storage.valid_assets.get(ind).unwrap().read();
+        let f_asset = storage.f_asset.get(current_asset_address).try_read().unwrap_or(0);
+        storage
+            .locked_asset_snapshot
+            .insert((id, position_id, current_asset_address), f_asset);
+        ind += 1;
+    }
+}
+
+/// @notice Drops the weight of a time-locked position back to its amount once it is unlocked
+/// @dev The gains accrued at the boosted weight are paid out before the weight drops, so the snapshots only
+///      cover the 1x weight from then on. No-op if the position does not exist, is still locked or is already at 1x
+/// @param id The Identity owning the position
+/// @param recipient The Identity receiving the settled gains
+/// @param position_id The id of the position
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read, write)]
+fn internal_expire_position_boost(id: Identity, recipient: Identity, position_id: u64) {
+    let position = storage.locked_positions.get((id, position_id)).try_read();
+    if (position.is_none()) {
+        return;
+    }
+    let mut position = position.unwrap();
+    if (internal_get_current_time() < position.unlock_time || position.weight <= position.amount) {
+        return;
+    }
+
+    internal_transfer_claimed_gain(
+        id,
+        recipient,
+        storage
+            .usdf_asset_id
+            .read(),
+        internal_get_pending_locked_usdf_gain(id, position_id),
+    );
+    internal_transfer_claimed_gain(
+        id,
+        recipient,
+        storage
+            .fpt_asset_id
+            .read(),
+        internal_get_pending_locked_fpt_gain(id, position_id),
+    );
+    let mut ind = 0;
+    while ind < storage.valid_assets.len() {
+        let current_asset_address = storage.valid_assets.get(ind).unwrap().read();
+        internal_transfer_claimed_gain(
+            id,
+            recipient,
+            current_asset_address,
+            internal_get_pending_locked_asset_gain(id, position_id, current_asset_address),
+        );
+        ind += 1;
+    }
+    internal_update_position_snapshots(id, position_id);
+
+    storage
+        .total_locked_weight
+        .write(storage.total_locked_weight.read() - (position.weight - position.amount));
+    position.weight = position.amount;
+    storage.locked_positions.insert((id, position_id), position);
+
+    log(LockedBoostExpiredEvent {
+        user: id,
+        position_id,
+        weight: position.weight,
+    });
+}
+
+/// @notice Removes a time-locked position and its snapshots
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+#[storage(read, write)]
+fn internal_remove_position(id: Identity, position_id: u64) {
+    let _ = storage.locked_positions.remove((id, position_id));
+    let _ = storage.locked_usdf_snapshot.remove((id, position_id));
+    let _ = storage.locked_fpt_snapshot.remove((id, position_id));
+
+    let mut ind = 0;
+    while ind < storage.valid_assets.len() {
+        let current_asset_address = storage.valid_assets.get(ind).unwrap().read();
+        let _ = storage
+            .locked_asset_snapshot
+            .remove((id, position_id, current_asset_address));
+        ind += 1;
+    }
+}
+
+/// @notice Sends the accumulated asset gains of a time-locked position to a user
+/// @param id The Identity of the user
+/// @param position_id The id of the position
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read)]
+fn internal_send_pending_locked_asset_gain_to_user(id: Identity, position_id: u64) {
+    let mut ind = 0;
+    while ind < storage.valid_assets.len() {
+        let current_asset_address = storage.valid_assets.get(ind).unwrap().read();
+        let asset_gain = internal_get_pending_locked_asset_gain(id, position_id, current_asset_address);
+        if (asset_gain > 0) {
+            transfer(msg_sender().unwrap(), current_asset_address, asset_gain);
+        }
+        ind += 1;
+    }
+}
+
+/// @notice Claims the pending gains of the selected assets of a user and sends them to a recipient
+/// @dev Updates only the snapshots of the claimed assets, for the liquid stake and every time-locked position
+/// @param id The Identity of the user whose gains are claimed
+/// @param recipient The Identity receiving the gains
+/// @param assets The assets to claim, the FPT asset id selects the early exit penalty gains
+/// @param include_usdf Whether to also claim the USDF gain
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read, write)]
+fn internal_claim(
+    id: Identity,
+    recipient: Identity,
+    assets: Vec<AssetId>,
+    include_usdf: bool,
+) {
+    let next_position_id = storage.next_position_id.get(id).try_read().unwrap_or(0);
+
+    // Unlocked positions drop to 1x first, settling every gain accrued at their boost
+    let mut position_id = 0;
+    while position_id < next_position_id {
+        internal_expire_position_boost(id, recipient, position_id);
+        position_id += 1;
+    }
+
+    if (include_usdf) {
+        let mut usdf_gain = internal_get_pending_usdf_gain(id);
+        storage.usdf_snapshot.insert(id, storage.f_usdf.read());
+
+        let mut position_id = 0;
+        while position_id < next_position_id {
+            if (storage.locked_positions.get((id, position_id)).try_read().is_some()) {
+                usdf_gain += internal_get_pending_locked_usdf_gain(id, position_id);
+                storage
+                    .locked_usdf_snapshot
+                    .insert((id, position_id), storage.f_usdf.read());
+            }
+            position_id += 1;
+        }
+
+        internal_transfer_claimed_gain(id, recipient, storage.usdf_asset_id.read(), usdf_gain);
+    }
+
+    let fpt_asset_id = storage.fpt_asset_id.read();
+    let mut i = 0;
+    while i < assets.len() {
+        let asset_id = assets.get(i).unwrap();
+        let mut gain = 0;
+
+        if (asset_id == fpt_asset_id) {
+            gain = internal_get_pending_fpt_gain(id);
+            storage.fpt_snapshot.insert(id, storage.f_fpt.read());
+
+            let mut position_id = 0;
+            while position_id < next_position_id {
+                if (storage.locked_positions.get((id, position_id)).try_read().is_some()) {
+                    gain += internal_get_pending_locked_fpt_gain(id, position_id);
+                    storage
+                        .locked_fpt_snapshot
+                        .insert((id, position_id), storage.f_fpt.read());
+                }
+                position_id += 1;
+            }
+        } else {
+            require_is_valid_asset(asset_id);
+            let f_asset = storage.f_asset.get(asset_id).try_read().unwrap_or(0);
+            gain = internal_get_pending_asset_gain(id, asset_id);
+            storage.asset_snapshot.insert((id, asset_id), f_asset);
+
+            let mut position_id = 0;
+            while position_id < next_position_id {
+                if (storage.locked_positions.get((id, position_id)).try_read().is_some()) {
+                    gain += internal_get_pending_locked_asset_gain(id, position_id, asset_id);
+                    storage
+                        .locked_asset_snapshot
+                        .insert((id, position_id, asset_id), f_asset);
+                }
+                position_id += 1;
+            }
+        }
+
+        internal_transfer_claimed_gain(id, recipient, asset_id, gain);
+        i += 1;
+    }
+}
+
+/// @notice Transfers a claimed gain to the recipient and logs it
+/// @param id The Identity of the user whose gain is claimed
+/// @param recipient The Identity receiving the gain
+/// @param asset_id The asset of the gain
+/// @param amount The amount of the gain
+/// @custom:internal This function is intended for internal use within the contract
+fn internal_transfer_claimed_gain(
+    id: Identity,
+    recipient: Identity,
+    asset_id: AssetId,
+    amount: u64,
+) {
+    if (amount > 0) {
+        transfer(recipient, asset_id, amount);
+        log(ClaimEvent {
+            user: id,
+            recipient,
+            asset_id,
+            amount,
+        });
+    }
+}
+
+/// @notice Checks if an asset has been added to the staking contract
+/// @param asset_id The AssetId to check
+/// @custom:throws "FPTStaking: Asset is not valid" if the asset has not been added
+#[storage(read)]
+fn require_is_valid_asset(asset_id: AssetId) {
+    let mut ind = 0;
+    while ind < storage.valid_assets.len() {
+        if (storage.valid_assets.get(ind).unwrap().read() == asset_id) {
+            return;
+        }
+        ind += 1;
+    }
+    require(false, "FPTStaking: Asset is not valid");
+}
+
+/// @notice Gets the total weight the fees are distributed over
+/// @return The total liquid stake plus the total weight of time-locked positions
+#[storage(read)]
+fn internal_get_total_weight() -> u64 {
+    storage.total_fpt_staked.read() + storage.total_locked_weight.read()
+}
+
+/// @notice Gets the boost multiplier of a lock duration
+/// @dev Grows linearly from 1x for no lock to MAX_LOCK_BOOST for MAX_LOCK_WEEKS
+/// @param lock_weeks The lock duration in weeks
+/// @return The boost multiplier in DECIMAL_PRECISION
+fn internal_get_lock_boost(lock_weeks: u64) -> u64 {
+    DECIMAL_PRECISION + fm_multiply_ratio(MAX_LOCK_BOOST - DECIMAL_PRECISION, lock_weeks, MAX_LOCK_WEEKS)
+}
+
+/// @notice Gets the penalty forfeited by unstaking a time-locked position at the given time
+/// @dev MAX_EARLY_EXIT_PENALTY of the amount right after locking, decreasing linearly to 0 at unlock
+/// @param position The time-locked position
+/// @param current_time The time of the exit
+/// @return The FPT penalty
+fn internal_get_early_exit_penalty(position: LockedPosition, current_time: u64) -> u64 {
+    if (current_time >= position.unlock_time) {
+        return 0;
+    }
+    let max_penalty = fm_multiply_ratio(position.amount, MAX_EARLY_EXIT_PENALTY, DECIMAL_PRECISION);
+    fm_multiply_ratio(
+        max_penalty,
+        position.unlock_time - current_time,
+        position.unlock_time - position.lock_time,
+    )
+}
+
+/// @notice Gets the current timestamp for the contract
+/// @dev Returns the debug timestamp if debugging is enabled, otherwise returns the current block timestamp
+/// @return The current timestamp as a u64 value
+/// @custom:internal This function is intended for internal use within the contract
+#[storage(read)]
+fn internal_get_current_time() -> u64 {
+    if storage.debug.read() {
+        return storage.debug_timestamp.read();
+    } else {
+        return timestamp();
+    }
+}
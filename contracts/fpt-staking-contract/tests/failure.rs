@@ -38,3 +38,51 @@ async fn fails_unstake_wrong_amount() {
     .await
     .expect_err("Unstake incorrect amount allowed");
 }
+
+#[tokio::test]
+async fn fails_locked_stake_invalid_duration_and_position() {
+    let (contracts, admin, mut _wallets) = setup_protocol(4, false, true).await;
+
+    let mock_token = Token::new(
+        contracts.fpt_token.contract.contract_id().clone(),
+        _wallets.pop().unwrap().clone(),
+    );
+    token_abi::mint_to_id(
+        &mock_token,
+        5_000 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+    let mock_token_asset_id = mock_token.contract_id().asset_id(&AssetId::zeroed().into());
+
+    fpt_staking_abi::stake_locked(&contracts.fpt_staking, mock_token_asset_id, PRECISION, 0)
+        .await
+        .expect_err("Lock of zero weeks allowed");
+
+    fpt_staking_abi::stake_locked(&contracts.fpt_staking, mock_token_asset_id, PRECISION, 53)
+        .await
+        .expect_err("Lock longer than the maximum allowed");
+
+    fpt_staking_abi::unstake_locked(
+        &contracts.fpt_staking,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        &mock_token,
+        0,
+    )
+    .await
+    .expect_err("Unstaked a locked position that does not exist");
+}
+
+#[tokio::test]
+async fn fails_claim_invalid_asset() {
+    let (contracts, _admin, _wallets) = setup_protocol(4, false, true).await;
+
+    fpt_staking_abi::claim(
+        &contracts.fpt_staking,
+        vec![AssetId::from([7u8; 32])],
+        false,
+    )
+    .await
+    .expect_err("Claimed an asset that was never added");
+}
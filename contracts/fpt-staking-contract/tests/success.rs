@@ -242,6 +242,8 @@ async fn proper_staking_multiple_positions() {
         0,
         None,
         None,
+        PRECISION,
+        vec![],
         &contracts.usdf,
         &contracts.fpt_staking,
         &contracts.coll_surplus_pool,
@@ -250,7 +252,8 @@ async fn proper_staking_multiple_positions() {
         &contracts.sorted_troves,
         &contracts.asset_contracts,
     )
-    .await;
+    .await
+    .unwrap();
 
     let asset_in_staking_balance = provider
         .get_contract_asset_balance(
@@ -365,3 +368,417 @@ async fn proper_staking_multiple_positions() {
         "Users did not receive exactly all the asset staking rewards"
     );
 }
+
+#[tokio::test]
+async fn proper_locked_staking_weight_and_early_exit_penalty() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, true).await;
+
+    let provider = admin.provider().unwrap();
+    let fpt_asset_id = contracts.fpt_asset_id;
+    let liquid_staker = wallets.pop().unwrap();
+
+    let mock_token = Token::new(
+        contracts.fpt_token.contract.contract_id().clone(),
+        liquid_staker.clone(),
+    );
+    token_abi::mint_to_id(
+        &mock_token,
+        5 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+    token_abi::mint_to_id(
+        &mock_token,
+        5 * PRECISION,
+        Identity::Address(liquid_staker.address().into()),
+    )
+    .await;
+
+    let fpt_staking_liquid_staker = ContractInstance::new(
+        FPTStaking::new(
+            contracts.fpt_staking.contract.contract_id().clone(),
+            liquid_staker.clone(),
+        ),
+        contracts.fpt_staking.implementation_id,
+    );
+
+    fpt_staking_abi::stake(&fpt_staking_liquid_staker, fpt_asset_id, 1 * PRECISION)
+        .await
+        .unwrap();
+
+    let boost = fpt_staking_abi::get_lock_boost(&contracts.fpt_staking, 52)
+        .await
+        .value;
+    assert_eq!(boost, 2_500_000_000, "Max lock boost is wrong");
+
+    let position_id =
+        fpt_staking_abi::stake_locked(&contracts.fpt_staking, fpt_asset_id, 1 * PRECISION, 52)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(position_id, 0);
+
+    let positions = fpt_staking_abi::get_locked_positions(
+        &contracts.fpt_staking,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].amount, 1 * PRECISION);
+    assert_eq!(positions[0].weight, 2_500_000_000);
+
+    let total_weight = fpt_staking_abi::get_total_weight(&contracts.fpt_staking)
+        .await
+        .value;
+    assert_eq!(total_weight, 3_500_000_000, "Total weight is wrong");
+
+    // Halfway through the lock the penalty is half of the maximum 50%
+    fpt_staking_abi::set_current_time(&contracts.fpt_staking, 26 * 7 * 24 * 60 * 60).await;
+
+    let penalty = fpt_staking_abi::get_early_exit_penalty(
+        &contracts.fpt_staking,
+        Identity::Address(admin.address().into()),
+        position_id,
+    )
+    .await
+    .value;
+    assert_eq!(penalty, PRECISION / 4, "Early exit penalty is wrong");
+
+    fpt_staking_abi::unstake_locked(
+        &contracts.fpt_staking,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        &mock_token,
+        position_id,
+    )
+    .await
+    .unwrap();
+
+    let fpt_balance = provider
+        .get_asset_balance(admin.address().into(), fpt_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        fpt_balance,
+        4 * PRECISION + 3 * PRECISION / 4,
+        "Penalty was not deducted"
+    );
+
+    let pending_fpt_gain = fpt_staking_abi::get_pending_fpt_gain(
+        &contracts.fpt_staking,
+        Identity::Address(liquid_staker.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(
+        pending_fpt_gain,
+        PRECISION / 4,
+        "Penalty was not redistributed to remaining stakers"
+    );
+}
+
+#[tokio::test]
+async fn proper_selective_claim_and_claim_to() {
+    let (contracts, _admin, mut wallets) = setup_protocol(4, false, true).await;
+
+    let staker = wallets.pop().unwrap();
+    let recipient = wallets.pop().unwrap();
+    let borrower = wallets.pop().unwrap();
+    let provider = staker.provider().unwrap();
+    let usdf_asset_id = contracts.usdf_asset_id;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    let mock_token = Token::new(
+        contracts.fpt_token.contract.contract_id().clone(),
+        staker.clone(),
+    );
+    token_abi::mint_to_id(
+        &mock_token,
+        5 * PRECISION,
+        Identity::Address(staker.address().into()),
+    )
+    .await;
+
+    let fpt_staking_staker = ContractInstance::new(
+        FPTStaking::new(
+            contracts.fpt_staking.contract.contract_id().clone(),
+            staker.clone(),
+        ),
+        contracts.fpt_staking.implementation_id,
+    );
+    fpt_staking_abi::stake(&fpt_staking_staker, contracts.fpt_asset_id, 1 * PRECISION)
+        .await
+        .unwrap();
+
+    // open a trove and redeem against it to generate USDF and asset fees
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        40_000 * PRECISION,
+        Identity::Address(borrower.address().into()),
+    )
+    .await;
+
+    let borrow_operations_borrower = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_borrower,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        40_000 * PRECISION,
+        20_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let protocol_manager_borrower = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    protocol_manager_abi::redeem_collateral(
+        &protocol_manager_borrower,
+        10_000 * PRECISION,
+        10,
+        0,
+        None,
+        None,
+        PRECISION,
+        vec![],
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await
+    .unwrap();
+
+    let staker_identity = Identity::Address(staker.address().into());
+    let pending_usdf =
+        fpt_staking_abi::get_pending_usdf_gain(&contracts.fpt_staking, staker_identity.clone())
+            .await
+            .value;
+    let pending_asset = fpt_staking_abi::get_pending_asset_gain(
+        &contracts.fpt_staking,
+        staker_identity.clone(),
+        asset_id,
+    )
+    .await
+    .value;
+    assert!(pending_usdf > 0 && pending_asset > 0);
+
+    // claiming only the asset leaves the USDF gain accruing
+    fpt_staking_abi::claim(&fpt_staking_staker, vec![asset_id], false)
+        .await
+        .unwrap();
+
+    let asset_balance = provider
+        .get_asset_balance(staker.address().into(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(asset_balance, pending_asset, "Asset gain was not claimed");
+
+    let pending_asset_after = fpt_staking_abi::get_pending_asset_gain(
+        &contracts.fpt_staking,
+        staker_identity.clone(),
+        asset_id,
+    )
+    .await
+    .value;
+    assert_eq!(pending_asset_after, 0);
+
+    let pending_usdf_after =
+        fpt_staking_abi::get_pending_usdf_gain(&contracts.fpt_staking, staker_identity.clone())
+            .await
+            .value;
+    assert_eq!(pending_usdf_after, pending_usdf, "USDF gain was claimed");
+
+    fpt_staking_abi::claim_to(
+        &fpt_staking_staker,
+        Identity::Address(recipient.address().into()),
+        vec![],
+        true,
+    )
+    .await
+    .unwrap();
+
+    let recipient_usdf_balance = provider
+        .get_asset_balance(recipient.address().into(), usdf_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        recipient_usdf_balance, pending_usdf,
+        "Recipient did not receive the USDF gain"
+    );
+
+    let pending_usdf_after =
+        fpt_staking_abi::get_pending_usdf_gain(&contracts.fpt_staking, staker_identity)
+            .await
+            .value;
+    assert_eq!(pending_usdf_after, 0);
+}
+
+#[tokio::test]
+async fn proper_locked_boost_expiry_after_unlock() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, true).await;
+
+    let fpt_asset_id = contracts.fpt_asset_id;
+    let liquid_staker = wallets.pop().unwrap();
+    let borrower = wallets.pop().unwrap();
+    let admin_identity = Identity::Address(admin.address().into());
+    let liquid_staker_identity = Identity::Address(liquid_staker.address().into());
+
+    let mock_token = Token::new(
+        contracts.fpt_token.contract.contract_id().clone(),
+        liquid_staker.clone(),
+    );
+    token_abi::mint_to_id(&mock_token, 5 * PRECISION, admin_identity.clone()).await;
+    token_abi::mint_to_id(&mock_token, 5 * PRECISION, liquid_staker_identity.clone()).await;
+
+    let fpt_staking_liquid_staker = ContractInstance::new(
+        FPTStaking::new(
+            contracts.fpt_staking.contract.contract_id().clone(),
+            liquid_staker.clone(),
+        ),
+        contracts.fpt_staking.implementation_id,
+    );
+    fpt_staking_abi::stake(&fpt_staking_liquid_staker, fpt_asset_id, 1 * PRECISION)
+        .await
+        .unwrap();
+
+    let position_id =
+        fpt_staking_abi::stake_locked(&contracts.fpt_staking, fpt_asset_id, 1 * PRECISION, 52)
+            .await
+            .unwrap()
+            .value;
+
+    // Past the unlock time the boost is only dropped once the position is touched
+    fpt_staking_abi::set_current_time(&contracts.fpt_staking, 52 * 7 * 24 * 60 * 60).await;
+
+    let weight =
+        fpt_staking_abi::get_effective_weight(&contracts.fpt_staking, admin_identity.clone())
+            .await
+            .value;
+    assert_eq!(weight, 2_500_000_000, "Boost dropped before being touched");
+
+    fpt_staking_abi::poke_locked_position(
+        &fpt_staking_liquid_staker,
+        admin_identity.clone(),
+        position_id,
+    )
+    .await
+    .unwrap();
+
+    let positions =
+        fpt_staking_abi::get_locked_positions(&contracts.fpt_staking, admin_identity.clone())
+            .await
+            .value;
+    assert_eq!(
+        positions[0].weight,
+        1 * PRECISION,
+        "Position weight is not back to 1x"
+    );
+
+    let weight =
+        fpt_staking_abi::get_effective_weight(&contracts.fpt_staking, admin_identity.clone())
+            .await
+            .value;
+    assert_eq!(weight, 1 * PRECISION, "Effective weight is not back to 1x");
+
+    let total_weight = fpt_staking_abi::get_total_weight(&contracts.fpt_staking)
+        .await
+        .value;
+    assert_eq!(total_weight, 2 * PRECISION, "Total weight was not reduced");
+
+    // open a trove to generate a borrowing fee, now split evenly with the liquid stake
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        40_000 * PRECISION,
+        Identity::Address(borrower.address().into()),
+    )
+    .await;
+
+    let borrow_operations_borrower = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            borrower.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_abi::open_trove(
+        &borrow_operations_borrower,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        40_000 * PRECISION,
+        20_000 * PRECISION,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let borrowing_fee = 20_000 * PRECISION / 200;
+
+    let locked_usdf_gain = fpt_staking_abi::get_pending_locked_usdf_gain(
+        &contracts.fpt_staking,
+        admin_identity.clone(),
+        position_id,
+    )
+    .await
+    .value;
+    let liquid_usdf_gain =
+        fpt_staking_abi::get_pending_usdf_gain(&contracts.fpt_staking, liquid_staker_identity)
+            .await
+            .value;
+    assert_eq!(
+        locked_usdf_gain, liquid_usdf_gain,
+        "Unlocked position still earns a boosted share"
+    );
+    assert_eq!(
+        locked_usdf_gain,
+        borrowing_fee / 2,
+        "Fee share of the unlocked position is wrong"
+    );
+}
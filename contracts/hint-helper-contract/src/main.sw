@@ -7,6 +7,8 @@ contract;
 // - Initializing the contract with the SortedTroves contract ID
 // - Providing a function to get an approximate hint for the closest trove
 // - Using a random seed to ensure different hints for different inputs
+// - Walking from an approximate hint to the exact insert position of a NICR
+// - Computing the redemption hints for a given amount of USDF, mirroring Liquity's HintHelpers
 //
 // To the auditor: This contract is not used in the system. It is only used for querying the system.
 
@@ -39,6 +41,22 @@ abi HintHelper {
         num_trials: u64,
         input_random_seed: u64,
     ) -> (Identity, u64, u64);
+    #[storage(read)]
+    fn get_exact_insert_position(
+        asset: AssetId,
+        trove_manager_contract: ContractId,
+        nicr: u64,
+        approx_hint: Identity,
+        max_steps: u64,
+    ) -> (Identity, Identity);
+    #[storage(read)]
+    fn get_redemption_hints(
+        asset: AssetId,
+        trove_manager_contract: ContractId,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+    ) -> (Identity, u64, u64);
 }
 impl HintHelper for Contract {
     #[storage(read, write)]
@@ -95,6 +113,105 @@ impl HintHelper for Contract {
 
         return (hint_address, diff, latest_random_seed);
     }
+    // Walks the list from `approx_hint` until the (prev, next) pair surrounding `nicr` is found.
+    // If the hint is not in the list the walk starts from the head. A `max_steps` of 0 means no limit,
+    // otherwise the closest pair reached within `max_steps` is returned.
+    #[storage(read)]
+    fn get_exact_insert_position(
+        asset: AssetId,
+        trove_manager_contract: ContractId,
+        nicr: u64,
+        approx_hint: Identity,
+        max_steps: u64,
+    ) -> (Identity, Identity) {
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+        let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+
+        if sorted_troves.is_empty(asset) {
+            return (null_identity_address(), null_identity_address());
+        }
+
+        let mut start_id = approx_hint;
+        if start_id == null_identity_address() || !sorted_troves.contains(start_id, asset) {
+            start_id = sorted_troves.get_first(asset);
+        }
+
+        let mut steps = 0;
+        if nicr <= trove_manager.get_nominal_icr(start_id) {
+            // Descend towards the tail while the next node still has a higher NICR
+            let mut prev_id = start_id;
+            let mut next_id = sorted_troves.get_next(prev_id, asset);
+            while next_id != null_identity_address() && trove_manager.get_nominal_icr(next_id) > nicr && (max_steps == 0 || steps < max_steps) {
+                prev_id = next_id;
+                next_id = sorted_troves.get_next(prev_id, asset);
+                steps += 1;
+            }
+            return (prev_id, next_id);
+        }
+
+        // Ascend towards the head while the previous node still has a lower NICR
+        let mut next_id = start_id;
+        let mut prev_id = sorted_troves.get_prev(next_id, asset);
+        while prev_id != null_identity_address() && trove_manager.get_nominal_icr(prev_id) < nicr && (max_steps == 0 || steps < max_steps) {
+            next_id = prev_id;
+            prev_id = sorted_troves.get_prev(next_id, asset);
+            steps += 1;
+        }
+        return (prev_id, next_id);
+    }
+    // Returns the first trove to redeem from (lowest ICR at or above MCR), the NICR the last trove
+    // will have after a partial redemption (0 if none), and the amount of USDF that can actually
+    // be redeemed. A `max_iterations` of 0 means no limit.
+    #[storage(read)]
+    fn get_redemption_hints(
+        asset: AssetId,
+        trove_manager_contract: ContractId,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+    ) -> (Identity, u64, u64) {
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+        let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+
+        let mut remaining_usdf = usdf_amount;
+        let mut current_borrower = sorted_troves.get_last(asset);
+
+        while current_borrower != null_identity_address() && trove_manager.get_current_icr(current_borrower, price) < MCR {
+            current_borrower = sorted_troves.get_prev(current_borrower, asset);
+        }
+
+        let first_redemption_hint = current_borrower;
+        let mut partial_redemption_hint_nicr = 0;
+        let mut remaining_iterations = max_iterations;
+        if remaining_iterations == 0 {
+            remaining_iterations = u64::max();
+        }
+
+        while current_borrower != null_identity_address() && remaining_usdf > 0 && remaining_iterations > 0 {
+            remaining_iterations -= 1;
+            let (debt, coll, _, _) = trove_manager.get_entire_debt_and_coll(current_borrower);
+
+            if debt > remaining_usdf {
+                if debt > MIN_NET_DEBT {
+                    let max_redeemable_usdf = fm_min(remaining_usdf, debt - MIN_NET_DEBT);
+                    let new_coll = coll - fm_multiply_ratio(max_redeemable_usdf, DECIMAL_PRECISION, price);
+                    let new_debt = debt - max_redeemable_usdf;
+                    partial_redemption_hint_nicr = fm_compute_nominal_cr(new_coll, new_debt);
+                    remaining_usdf -= max_redeemable_usdf;
+                }
+                break;
+            }
+
+            remaining_usdf -= debt;
+            current_borrower = sorted_troves.get_prev(current_borrower, asset);
+        }
+
+        return (
+            first_redemption_hint,
+            partial_redemption_hint_nicr,
+            usdf_amount - remaining_usdf,
+        );
+    }
 }
 
 fn decompose(val: b256) -> (u64, u64, u64, u64) {
@@ -1,11 +1,14 @@
-use fuels::types::Identity;
-use test_utils::interfaces::borrow_operations::borrow_operations_utils;
+use fuels::types::{Address, Identity};
+use test_utils::interfaces::borrow_operations::{borrow_operations_abi, borrow_operations_utils};
 use test_utils::interfaces::oracle::oracle_abi;
 use test_utils::interfaces::pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP};
+use test_utils::interfaces::token::token_abi;
+use test_utils::off_chain_hints::{compute_nominal_cr, OffChainHintEngine};
 use test_utils::{
-    data_structures::PRECISION,
-    interfaces::hint_helper::hint_helper_abi,
-    setup::common::{deploy_hint_helper, setup_protocol},
+    data_structures::{MIN_NET_DEBT, PRECISION},
+    interfaces::hint_helper::{hint_helper_abi, hint_helper_utils},
+    setup::common::{deploy_hint_helper, deploy_multi_trove_getter, setup_protocol},
+    utils::{calculate_icr, with_min_borrow_fee},
 };
 
 #[ignore = "MemoryWriteOverlap Fuel Error in current version"]
@@ -93,3 +96,301 @@ async fn proper_hint_generations() {
     let id = res.value.0;
     assert_eq!(id, target_address2);
 }
+
+#[tokio::test]
+async fn proper_exact_insert_position_and_redemption_hints() {
+    let (contracts, _admin, mut wallets) = setup_protocol(10, false, false).await;
+    let wallet = wallets.pop().unwrap();
+
+    let hint_helper = deploy_hint_helper(&wallet).await;
+
+    hint_helper_abi::initialize(
+        &hint_helper,
+        contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    // create 5 troves each with 600 USDF debt and n * 1000 collateral
+    let usdf_amount = 600 * PRECISION;
+    let mut borrowers: Vec<Identity> = vec![];
+    for i in 1..=5 {
+        let wallet = wallets.pop().unwrap();
+        let amount = i * 1000 * PRECISION;
+        borrowers.push(Identity::Address(wallet.address().into()));
+
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[0],
+            &contracts.borrow_operations,
+            &contracts.usdf,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            amount,
+            usdf_amount,
+        )
+        .await;
+    }
+    let debt = with_min_borrow_fee(usdf_amount);
+
+    // NICR between the 2nd and 3rd troves, starting from the tail
+    let nicr = calculate_icr(2500 * PRECISION, debt);
+    let (prev_id, next_id) = hint_helper_abi::get_exact_insert_position(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        nicr,
+        borrowers[0].clone(),
+        0,
+    )
+    .await
+    .value;
+    assert_eq!(prev_id, borrowers[2]);
+    assert_eq!(next_id, borrowers[1]);
+
+    // Same position when walking down from the head
+    let (prev_id, next_id) = hint_helper_abi::get_exact_insert_position(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        nicr,
+        borrowers[4].clone(),
+        0,
+    )
+    .await
+    .value;
+    assert_eq!(prev_id, borrowers[2]);
+    assert_eq!(next_id, borrowers[1]);
+
+    // Higher than every trove goes before the head
+    let (prev_id, next_id) = hint_helper_abi::get_exact_insert_position(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        calculate_icr(10_000 * PRECISION, debt),
+        borrowers[0].clone(),
+        0,
+    )
+    .await
+    .value;
+    assert_eq!(prev_id, Identity::Address(Address::zeroed()));
+    assert_eq!(next_id, borrowers[4]);
+
+    // The walk stops after max_steps
+    let (prev_id, next_id) = hint_helper_abi::get_exact_insert_position(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        nicr,
+        borrowers[4].clone(),
+        1,
+    )
+    .await
+    .value;
+    assert_eq!(prev_id, borrowers[3]);
+    assert_eq!(next_id, borrowers[2]);
+
+    // Redeem the whole first trove and part of the second one
+    let redemption_amount = debt + 200 * PRECISION;
+    let price = PRECISION;
+    let (first_hint, partial_nicr, truncated_amount) = hint_helper_abi::get_redemption_hints(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        redemption_amount,
+        price,
+        0,
+    )
+    .await
+    .value;
+
+    // The second trove can only go down to the minimum net debt
+    let partial_redeemed = debt - MIN_NET_DEBT;
+    assert_eq!(first_hint, borrowers[0]);
+    assert_eq!(truncated_amount, debt + partial_redeemed);
+    let partial_nicr_full = calculate_icr(2000 * PRECISION - partial_redeemed, MIN_NET_DEBT);
+    assert_eq!(partial_nicr, partial_nicr_full);
+
+    // With a single iteration only the first trove is redeemed
+    let (first_hint, partial_nicr, truncated_amount) = hint_helper_abi::get_redemption_hints(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        redemption_amount,
+        price,
+        1,
+    )
+    .await
+    .value;
+    assert_eq!(first_hint, borrowers[0]);
+    assert_eq!(partial_nicr, 0);
+    assert_eq!(truncated_amount, debt);
+
+    let hints = hint_helper_utils::get_redemption_and_insert_hints(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        redemption_amount,
+        price,
+        0,
+        1,
+        0,
+    )
+    .await;
+    assert_eq!(hints.first_redemption_hint, borrowers[0]);
+    assert_eq!(hints.truncated_usdf_amount, debt + partial_redeemed);
+    // The partially redeemed trove moves up, between the 3rd trove and its current position
+    assert_eq!(hints.partial_redemption_hint_nicr, partial_nicr_full);
+    assert_eq!(hints.upper_partial_hint, borrowers[2]);
+    assert_eq!(hints.lower_partial_hint, borrowers[1]);
+}
+
+#[tokio::test]
+async fn proper_off_chain_hints_match_on_chain() {
+    let (contracts, admin, mut wallets) = setup_protocol(10, false, false).await;
+    let wallet = wallets.pop().unwrap();
+
+    let hint_helper = deploy_hint_helper(&wallet).await;
+    hint_helper_abi::initialize(
+        &hint_helper,
+        contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await
+    .unwrap();
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &wallet,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    let usdf_amount = 600 * PRECISION;
+    for i in 1..=5 {
+        let wallet = wallets.pop().unwrap();
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[0],
+            &contracts.borrow_operations,
+            &contracts.usdf,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            i * 1000 * PRECISION,
+            usdf_amount,
+        )
+        .await;
+    }
+
+    let asset_id = contracts.asset_contracts[0].asset_id;
+    let mut engine = OffChainHintEngine::new();
+    engine
+        .sync_from_chain(
+            &multi_trove_getter,
+            &contracts.asset_contracts[0].trove_manager,
+            &contracts.sorted_troves,
+            &asset_id,
+        )
+        .await;
+    assert_eq!(engine.list(&asset_id).unwrap().len(), 5);
+
+    // Insert positions match the on-chain walk
+    let debt = with_min_borrow_fee(usdf_amount);
+    for coll in [500, 2500, 4000, 9000] {
+        let nicr = compute_nominal_cr(coll * PRECISION, debt);
+        let on_chain = hint_helper_abi::get_exact_insert_position(
+            &hint_helper,
+            &contracts.asset_contracts[0].trove_manager,
+            &contracts.sorted_troves,
+            &asset_id,
+            nicr,
+            Identity::Address(Address::zeroed()),
+            0,
+        )
+        .await
+        .value;
+        assert_eq!(engine.find_insert_position(&asset_id, nicr), on_chain);
+    }
+
+    // Open a trove from the admin wallet using the off-chain hints and track it from the logs
+    let coll = 3500 * PRECISION;
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        coll,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+    let (prev_id, next_id) = engine.find_insert_position(&asset_id, compute_nominal_cr(coll, debt));
+    let response = borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        coll,
+        usdf_amount,
+        prev_id,
+        next_id,
+    )
+    .await
+    .unwrap();
+    engine.apply_borrow_operations_logs(&response);
+
+    let mut on_chain_engine = OffChainHintEngine::new();
+    on_chain_engine
+        .sync_from_chain(
+            &multi_trove_getter,
+            &contracts.asset_contracts[0].trove_manager,
+            &contracts.sorted_troves,
+            &asset_id,
+        )
+        .await;
+    assert_eq!(
+        engine.list(&asset_id).unwrap().troves(),
+        on_chain_engine.list(&asset_id).unwrap().troves()
+    );
+
+    // Redemption hints match the hint helper
+    let redemption_amount = 2 * debt + 50 * PRECISION;
+    let on_chain = hint_helper_abi::get_redemption_hints(
+        &hint_helper,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &asset_id,
+        redemption_amount,
+        PRECISION,
+        0,
+    )
+    .await
+    .value;
+    let off_chain = engine.get_redemption_hints(&asset_id, redemption_amount, PRECISION, 0);
+    assert_eq!(off_chain.first_redemption_hint, on_chain.0);
+    assert_eq!(off_chain.partial_redemption_hint_nicr, on_chain.1);
+    assert_eq!(off_chain.truncated_usdf_amount, on_chain.2);
+}
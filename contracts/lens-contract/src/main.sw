@@ -0,0 +1,215 @@
+contract;
+// This contract, Lens, aggregates the state of the protocol into a couple of read-only calls.
+// It is used to query global and per-user stats for frontends without calling every contract.
+//
+// Key functionalities include:
+// - Per-asset totals: collateral and debt in the active and default pools, trove count, price and TCR
+// - Stability pool, FPT staking and USDF supply totals as well as the pause status
+// - Per-user view: troves, stability pool deposit and gains, FPT stake and pending gains,
+//   claimable collateral surplus and redeemable vested tokens
+//
+// To the auditor: This contract is not used in the system. It is only used for querying the system for frontend purposes.
+
+use libraries::trove_manager_interface::TroveManager;
+use libraries::trove_manager_interface::data_structures::Status;
+use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::borrow_operations_interface::BorrowOperations;
+use libraries::stability_pool_interface::StabilityPool;
+use libraries::fpt_staking_interface::FPTStaking;
+use libraries::active_pool_interface::ActivePool;
+use libraries::default_pool_interface::DefaultPool;
+use libraries::coll_surplus_pool_interface::CollSurplusPool;
+use libraries::oracle_interface::Oracle;
+use libraries::fluid_math::*;
+use standards::src20::SRC20;
+
+configurable {
+    PROTOCOL_MANAGER_CONTRACT: ContractId = ContractId::zero(),
+    BORROW_OPERATIONS_CONTRACT: ContractId = ContractId::zero(),
+    STABILITY_POOL_CONTRACT: ContractId = ContractId::zero(),
+    FPT_STAKING_CONTRACT: ContractId = ContractId::zero(),
+    USDF_TOKEN_CONTRACT: ContractId = ContractId::zero(),
+    ACTIVE_POOL_CONTRACT: ContractId = ContractId::zero(),
+    DEFAULT_POOL_CONTRACT: ContractId = ContractId::zero(),
+    COLL_SURPLUS_POOL_CONTRACT: ContractId = ContractId::zero(),
+    VESTING_CONTRACT: ContractId = ContractId::zero(),
+}
+
+// Subset of the vesting contract ABI, the full interface lives in the vesting contract
+abi VestingContract {
+    #[storage(read)]
+    fn get_redeemable_amount(timestamp: u64, address: Identity) -> u64;
+    #[storage(read)]
+    fn get_current_time() -> u64;
+}
+
+abi Lens {
+    #[storage(read)]
+    fn get_system_overview() -> SystemOverview;
+    #[storage(read)]
+    fn get_user_overview(user: Identity) -> UserOverview;
+}
+
+struct AssetOverview {
+    asset_id: AssetId,
+    trove_manager: ContractId,
+    active_pool_collateral: u64,
+    default_pool_collateral: u64,
+    total_collateral: u64,
+    active_pool_debt: u64,
+    default_pool_debt: u64,
+    total_debt: u64,
+    trove_count: u64,
+    price: u64,
+    tcr: u64,
+    stability_pool_usdf_deposits: u64,
+    stability_pool_asset_gains: u64,
+}
+
+struct SystemOverview {
+    assets: Vec<AssetOverview>,
+    stability_pool_total_usdf_deposits: u64,
+    total_fpt_staked: u64,
+    total_fpt_weight: u64,
+    usdf_total_supply: u64,
+    is_paused: bool,
+}
+
+struct UserTrove {
+    asset_id: AssetId,
+    collateral: u64,
+    debt: u64,
+    icr: u64,
+}
+
+struct UserAssetGains {
+    asset_id: AssetId,
+    stability_pool_gain: u64,
+    fpt_staking_gain: u64,
+    coll_surplus: u64,
+}
+
+struct UserOverview {
+    troves: Vec<UserTrove>,
+    asset_gains: Vec<UserAssetGains>,
+    stability_pool_deposit: u64,
+    stability_pool_fpt_gain: u64,
+    fpt_staked: u64,
+    fpt_pending_usdf_gain: u64,
+    fpt_pending_fpt_gain: u64,
+    vesting_redeemable: u64,
+}
+
+impl Lens for Contract {
+    #[storage(read)]
+    fn get_system_overview() -> SystemOverview {
+        let protocol_manager = abi(ProtocolManager, PROTOCOL_MANAGER_CONTRACT.bits());
+        let borrow_operations = abi(BorrowOperations, BORROW_OPERATIONS_CONTRACT.bits());
+        let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+        let fpt_staking = abi(FPTStaking, FPT_STAKING_CONTRACT.bits());
+        let active_pool = abi(ActivePool, ACTIVE_POOL_CONTRACT.bits());
+        let default_pool = abi(DefaultPool, DEFAULT_POOL_CONTRACT.bits());
+        let usdf = abi(SRC20, USDF_TOKEN_CONTRACT.bits());
+
+        let registered_assets = protocol_manager.get_registered_assets();
+        let mut assets: Vec<AssetOverview> = Vec::new();
+
+        let mut i = 0;
+        while i < registered_assets.len() {
+            let registered_asset = registered_assets.get(i).unwrap();
+            let asset = registered_asset.asset;
+            let trove_manager = abi(TroveManager, registered_asset.trove_manager.bits());
+            let oracle = abi(Oracle, registered_asset.oracle.bits());
+
+            let active_pool_collateral = active_pool.get_asset(asset);
+            let default_pool_collateral = default_pool.get_asset(asset);
+            let active_pool_debt = active_pool.get_usdf_debt(asset);
+            let default_pool_debt = default_pool.get_usdf_debt(asset);
+            let total_collateral = active_pool_collateral + default_pool_collateral;
+            let total_debt = active_pool_debt + default_pool_debt;
+            let price = oracle.get_price();
+
+            assets.push(AssetOverview {
+                asset_id: asset,
+                trove_manager: registered_asset.trove_manager,
+                active_pool_collateral,
+                default_pool_collateral,
+                total_collateral,
+                active_pool_debt,
+                default_pool_debt,
+                total_debt,
+                trove_count: trove_manager.get_trove_owners_count(),
+                price,
+                tcr: fm_compute_cr(total_collateral, total_debt, price),
+                stability_pool_usdf_deposits: stability_pool.get_total_usdf_deposits_for_asset(asset),
+                stability_pool_asset_gains: stability_pool.get_asset(asset),
+            });
+            i += 1;
+        }
+
+        let usdf_asset_id = borrow_operations.get_usdf_asset_id();
+
+        return SystemOverview {
+            assets,
+            stability_pool_total_usdf_deposits: stability_pool.get_total_usdf_deposits(),
+            total_fpt_staked: fpt_staking.get_storage().total_fpt_staked,
+            total_fpt_weight: fpt_staking.get_total_weight(),
+            usdf_total_supply: usdf.total_supply(usdf_asset_id).unwrap_or(0),
+            is_paused: borrow_operations.get_is_paused(),
+        };
+    }
+    #[storage(read)]
+    fn get_user_overview(user: Identity) -> UserOverview {
+        let protocol_manager = abi(ProtocolManager, PROTOCOL_MANAGER_CONTRACT.bits());
+        let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+        let fpt_staking = abi(FPTStaking, FPT_STAKING_CONTRACT.bits());
+        let coll_surplus_pool = abi(CollSurplusPool, COLL_SURPLUS_POOL_CONTRACT.bits());
+
+        let registered_assets = protocol_manager.get_registered_assets();
+        let mut troves: Vec<UserTrove> = Vec::new();
+        let mut asset_gains: Vec<UserAssetGains> = Vec::new();
+
+        let mut i = 0;
+        while i < registered_assets.len() {
+            let registered_asset = registered_assets.get(i).unwrap();
+            let asset = registered_asset.asset;
+            let trove_manager = abi(TroveManager, registered_asset.trove_manager.bits());
+
+            if trove_manager.get_trove_status(user) == Status::Active {
+                let oracle = abi(Oracle, registered_asset.oracle.bits());
+                let (debt, collateral, _, _) = trove_manager.get_entire_debt_and_coll(user);
+                troves.push(UserTrove {
+                    asset_id: asset,
+                    collateral,
+                    debt,
+                    icr: fm_compute_cr(collateral, debt, oracle.get_price()),
+                });
+            }
+
+            asset_gains.push(UserAssetGains {
+                asset_id: asset,
+                stability_pool_gain: stability_pool.get_depositor_asset_gain(user, asset),
+                fpt_staking_gain: fpt_staking.get_pending_asset_gain(user, asset),
+                coll_surplus: coll_surplus_pool.get_collateral(user, asset),
+            });
+            i += 1;
+        }
+
+        let mut vesting_redeemable = 0;
+        if VESTING_CONTRACT != ContractId::zero() {
+            let vesting = abi(VestingContract, VESTING_CONTRACT.bits());
+            vesting_redeemable = vesting.get_redeemable_amount(vesting.get_current_time(), user);
+        }
+
+        return UserOverview {
+            troves,
+            asset_gains,
+            stability_pool_deposit: stability_pool.get_compounded_usdf_deposit(user),
+            stability_pool_fpt_gain: stability_pool.get_depositor_fpt_gain(user),
+            fpt_staked: fpt_staking.get_staking_balance(user),
+            fpt_pending_usdf_gain: fpt_staking.get_pending_usdf_gain(user),
+            fpt_pending_fpt_gain: fpt_staking.get_pending_fpt_gain(user),
+            vesting_redeemable,
+        };
+    }
+}
@@ -0,0 +1,130 @@
+use fuels::types::{Address, Identity};
+use test_utils::{
+    data_structures::PRECISION,
+    interfaces::{
+        borrow_operations::borrow_operations_abi,
+        lens::lens_abi,
+        oracle::oracle_abi,
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP},
+        stability_pool::stability_pool_abi,
+        token::token_abi,
+    },
+    setup::common::{deploy_lens, setup_protocol},
+    utils::{calculate_cr, with_min_borrow_fee},
+};
+
+#[tokio::test]
+async fn proper_system_and_user_overview() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, true, false).await;
+    let other_wallet = wallets.pop().unwrap();
+
+    let lens = deploy_lens(&admin, &contracts).await;
+
+    for asset_contracts in contracts.asset_contracts.iter() {
+        oracle_abi::set_debug_timestamp(&asset_contracts.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset_contracts.mock_pyth_oracle, pyth_price_feed(1))
+            .await;
+    }
+
+    token_abi::mint_to_id(
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+        Identity::Address(admin.address().into()),
+    )
+    .await;
+
+    let coll_amount = 1_200 * PRECISION;
+    let debt_amount = 600 * PRECISION;
+    borrow_operations_abi::open_trove(
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        coll_amount,
+        debt_amount,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let deposit_amount = 300 * PRECISION;
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        deposit_amount,
+    )
+    .await
+    .unwrap();
+
+    let total_debt = with_min_borrow_fee(debt_amount);
+
+    let overview = lens_abi::get_system_overview(&lens, &contracts).await.value;
+    assert_eq!(overview.assets.len(), 2);
+
+    let asset_overview = &overview.assets[0];
+    assert_eq!(
+        asset_overview.asset_id,
+        contracts.asset_contracts[0].asset_id
+    );
+    assert_eq!(asset_overview.active_pool_collateral, coll_amount);
+    assert_eq!(asset_overview.default_pool_collateral, 0);
+    assert_eq!(asset_overview.total_collateral, coll_amount);
+    assert_eq!(asset_overview.total_debt, total_debt);
+    assert_eq!(asset_overview.trove_count, 1);
+    assert_eq!(
+        asset_overview.tcr,
+        calculate_cr(asset_overview.price, coll_amount, total_debt)
+    );
+
+    let empty_asset_overview = &overview.assets[1];
+    assert_eq!(
+        empty_asset_overview.asset_id,
+        contracts.asset_contracts[1].asset_id
+    );
+    assert_eq!(empty_asset_overview.total_collateral, 0);
+    assert_eq!(empty_asset_overview.trove_count, 0);
+
+    assert_eq!(overview.stability_pool_total_usdf_deposits, deposit_amount);
+    assert_eq!(overview.usdf_total_supply, total_debt);
+    assert_eq!(overview.total_fpt_staked, 0);
+    assert!(!overview.is_paused);
+
+    let user_overview =
+        lens_abi::get_user_overview(&lens, &contracts, Identity::Address(admin.address().into()))
+            .await
+            .value;
+    assert_eq!(user_overview.troves.len(), 1);
+    assert_eq!(
+        user_overview.troves[0].asset_id,
+        contracts.asset_contracts[0].asset_id
+    );
+    assert_eq!(user_overview.troves[0].collateral, coll_amount);
+    assert_eq!(user_overview.troves[0].debt, total_debt);
+    assert_eq!(user_overview.asset_gains.len(), 2);
+    assert_eq!(user_overview.asset_gains[0].stability_pool_gain, 0);
+    assert_eq!(user_overview.asset_gains[0].coll_surplus, 0);
+    assert_eq!(user_overview.stability_pool_deposit, deposit_amount);
+    assert_eq!(user_overview.fpt_staked, 0);
+    assert_eq!(user_overview.vesting_redeemable, 0);
+
+    // Users without any position get an empty overview instead of a revert
+    let empty_user_overview = lens_abi::get_user_overview(
+        &lens,
+        &contracts,
+        Identity::Address(other_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(empty_user_overview.troves.len(), 0);
+    assert_eq!(empty_user_overview.stability_pool_deposit, 0);
+    assert_eq!(empty_user_overview.vesting_redeemable, 0);
+}
@@ -0,0 +1,65 @@
+contract;
+// This contract, MockSwapAdapter, is a mock implementation of the SwapAdapter interface.
+// It swaps at fixed rates set by the tests and pays out of its own pre-funded balance.
+//
+// To the auditor: This contract is not used in the system. It is only used for testing.
+
+use libraries::swap_adapter_interface::SwapAdapter;
+use libraries::fluid_math::DECIMAL_PRECISION;
+use std::{
+    asset::transfer,
+    call_frames::msg_asset_id,
+    context::{
+        msg_amount,
+        this_balance,
+    },
+    hash::Hash,
+    u128::U128,
+};
+
+abi MockSwapAdapter {
+    // Testing only, amount out = amount in * rate / DECIMAL_PRECISION
+    #[storage(write)]
+    fn set_rate(asset_in: AssetId, asset_out: AssetId, rate: u64);
+}
+
+storage {
+    rates: StorageMap<(AssetId, AssetId), u64> = StorageMap::<(AssetId, AssetId), u64> {},
+}
+
+impl SwapAdapter for Contract {
+    #[storage(read), payable]
+    fn swap(asset_out: AssetId, min_amount_out: u64, recipient: Identity) -> u64 {
+        let amount_out = internal_get_amount_out(msg_asset_id(), asset_out, msg_amount());
+        require(
+            amount_out >= min_amount_out,
+            "MockSwapAdapter: Insufficient amount out",
+        );
+        require(
+            this_balance(asset_out) >= amount_out,
+            "MockSwapAdapter: Insufficient liquidity",
+        );
+        if amount_out > 0 {
+            transfer(recipient, asset_out, amount_out);
+        }
+        amount_out
+    }
+
+    #[storage(read)]
+    fn get_amount_out(asset_in: AssetId, asset_out: AssetId, amount_in: u64) -> u64 {
+        internal_get_amount_out(asset_in, asset_out, amount_in)
+    }
+}
+
+impl MockSwapAdapter for Contract {
+    #[storage(write)]
+    fn set_rate(asset_in: AssetId, asset_out: AssetId, rate: u64) {
+        storage.rates.insert((asset_in, asset_out), rate);
+    }
+}
+
+#[storage(read)]
+fn internal_get_amount_out(asset_in: AssetId, asset_out: AssetId, amount_in: u64) -> u64 {
+    let rate = storage.rates.get((asset_in, asset_out)).try_read().unwrap_or(0);
+    (U128::from(amount_in) * U128::from(rate) / U128::from(DECIMAL_PRECISION)).as_u64().unwrap()
+}
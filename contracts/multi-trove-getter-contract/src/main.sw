@@ -5,7 +5,10 @@ contract;
 // To the auditor: This contract is not used in the system. It is only used for querying the system for frontend purposes.
 
 use libraries::trove_manager_interface::TroveManager;
+use libraries::trove_manager_interface::data_structures::Status;
 use libraries::sorted_troves_interface::SortedTroves;
+use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::oracle_interface::Oracle;
 use libraries::fluid_math::*;
 use std::{
     asset::transfer,
@@ -31,6 +34,33 @@ abi MultiTroveGetter {
         start_indx: u64,
         count: u8,
     ) -> Vec<CombinedTroveData>;
+    // Returns up to `count` troves starting after `cursor` (from the tail or head if the cursor is null)
+    // together with the cursor for the next page, which is null once the end of the list is reached
+    #[storage(read)]
+    fn get_sorted_troves_after(
+        trove_manager_contract: ContractId,
+        asset_id: AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+    ) -> (Vec<TroveData>, Identity);
+    // Same as `get_sorted_troves_after` but only returns troves with `min_icr <= icr <= max_icr`,
+    // out of range troves are skipped so a page may walk up to the end of the list
+    #[storage(read)]
+    fn get_sorted_troves_in_icr_range(
+        trove_manager_contract: ContractId,
+        asset_id: AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+        min_icr: u64,
+        max_icr: u64,
+    ) -> (Vec<TroveData>, Identity);
+    // Returns the active troves of `user` for every asset registered in the protocol manager
+    #[storage(read)]
+    fn get_user_troves(protocol_manager_contract: ContractId, user: Identity) -> Vec<TroveData>;
 }
 
 struct CombinedTroveData {
@@ -41,6 +71,19 @@ struct CombinedTroveData {
     debt_rewards: u64,
 }
 
+struct TroveData {
+    address: Identity,
+    asset_id: AssetId,
+    status: Status,
+    collateral: u64,
+    collateral_rewards: u64,
+    debt: u64,
+    debt_rewards: u64,
+    stake: u64,
+    icr: u64,
+    nicr: u64,
+}
+
 impl MultiTroveGetter for Contract {
     #[storage(read)]
     fn get_multiple_sorted_troves(
@@ -51,6 +94,72 @@ impl MultiTroveGetter for Contract {
     ) -> Vec<CombinedTroveData> {
         internal_get_multiple_sorted_troves(trove_manager_contract, asset_id, start_indx, count)
     }
+    #[storage(read)]
+    fn get_sorted_troves_after(
+        trove_manager_contract: ContractId,
+        asset_id: AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+    ) -> (Vec<TroveData>, Identity) {
+        internal_get_sorted_troves_in_icr_range(
+            trove_manager_contract,
+            asset_id,
+            cursor,
+            ascending,
+            count,
+            price,
+            0,
+            u64::max(),
+        )
+    }
+    #[storage(read)]
+    fn get_sorted_troves_in_icr_range(
+        trove_manager_contract: ContractId,
+        asset_id: AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+        min_icr: u64,
+        max_icr: u64,
+    ) -> (Vec<TroveData>, Identity) {
+        internal_get_sorted_troves_in_icr_range(
+            trove_manager_contract,
+            asset_id,
+            cursor,
+            ascending,
+            count,
+            price,
+            min_icr,
+            max_icr,
+        )
+    }
+    #[storage(read)]
+    fn get_user_troves(protocol_manager_contract: ContractId, user: Identity) -> Vec<TroveData> {
+        let protocol_manager = abi(ProtocolManager, protocol_manager_contract.bits());
+        let registered_assets = protocol_manager.get_registered_assets();
+        let mut troves: Vec<TroveData> = Vec::new();
+
+        let mut i = 0;
+        while i < registered_assets.len() {
+            let registered_asset = registered_assets.get(i).unwrap();
+            let trove_manager = abi(TroveManager, registered_asset.trove_manager.bits());
+            if trove_manager.get_trove_status(user) == Status::Active {
+                let oracle = abi(Oracle, registered_asset.oracle.bits());
+                troves.push(get_full_trove_data(
+                    registered_asset.trove_manager,
+                    registered_asset.asset,
+                    user,
+                    oracle.get_price(),
+                ));
+            }
+            i += 1;
+        }
+
+        return troves;
+    }
 }
 
 #[storage(read)]
@@ -84,6 +193,95 @@ fn internal_get_multiple_sorted_troves(
     return troves;
 }
 
+#[storage(read)]
+fn internal_get_sorted_troves_in_icr_range(
+    trove_manager_contract: ContractId,
+    asset_id: AssetId,
+    cursor: Identity,
+    ascending: bool,
+    count: u8,
+    price: u64,
+    min_icr: u64,
+    max_icr: u64,
+) -> (Vec<TroveData>, Identity) {
+    let sorted_troves = abi(SortedTroves, SORTED_TROVES_CONTRACT.bits());
+    let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+    let null_address = Identity::Address(Address::zero());
+
+    // The list is sorted by NICR from head to tail, ascending walks from the tail towards the head
+    let mut current_trove_owner = if cursor == null_address {
+        if ascending {
+            sorted_troves.get_last(asset_id)
+        } else {
+            sorted_troves.get_first(asset_id)
+        }
+    } else {
+        require(
+            sorted_troves
+                .contains(cursor, asset_id),
+            "MultiTroveGetter: Cursor is not in the list",
+        );
+        next_in_direction(cursor, asset_id, ascending)
+    };
+
+    let mut troves: Vec<TroveData> = Vec::new();
+    let mut current_count: u8 = 0;
+
+    while current_count < count && current_trove_owner != null_address {
+        // The list is sorted by NICR, pending rewards can make the ICR non monotonic along it,
+        // so troves out of range are skipped rather than ending the walk
+        let icr = trove_manager.get_current_icr(current_trove_owner, price);
+        if icr >= min_icr && icr <= max_icr {
+            troves.push(get_full_trove_data(trove_manager_contract, asset_id, current_trove_owner, price));
+            current_count += 1;
+        }
+        current_trove_owner = next_in_direction(current_trove_owner, asset_id, ascending);
+    }
+
+    if current_trove_owner == null_address {
+        return (troves, null_address);
+    }
+    if troves.is_empty() {
+        return (troves, cursor);
+    }
+
+    // Cursor for the next page is the last trove of this page
+    let last = troves.get(troves.len() - 1).unwrap();
+    return (troves, last.address);
+}
+
+fn next_in_direction(id: Identity, asset_id: AssetId, ascending: bool) -> Identity {
+    let sorted_troves = abi(SortedTroves, SORTED_TROVES_CONTRACT.bits());
+    if ascending {
+        sorted_troves.get_prev(id, asset_id)
+    } else {
+        sorted_troves.get_next(id, asset_id)
+    }
+}
+
+fn get_full_trove_data(
+    trove_manager_contract: ContractId,
+    asset_id: AssetId,
+    trove_owner: Identity,
+    price: u64,
+) -> TroveData {
+    let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+
+    let (debt, coll, debt_rewards, collateral_rewards) = trove_manager.get_entire_debt_and_coll(trove_owner);
+    return TroveData {
+        address: trove_owner,
+        asset_id,
+        status: trove_manager.get_trove_status(trove_owner),
+        collateral: coll,
+        collateral_rewards,
+        debt,
+        debt_rewards,
+        stake: trove_manager.get_trove_stake(trove_owner),
+        icr: fm_compute_cr(coll, debt, price),
+        nicr: fm_compute_nominal_cr(coll, debt),
+    };
+}
+
 fn get_trove_data(trove_manager_contract: ContractId, trove_owner: Identity) -> CombinedTroveData {
     let trove_manager = abi(TroveManager, trove_manager_contract.bits());
 
@@ -1,10 +1,14 @@
+use fuels::prelude::WalletUnlocked;
+use fuels::types::{Address, Identity};
 use test_utils::interfaces::borrow_operations::borrow_operations_utils;
+use test_utils::interfaces::multi_trove_getter::Status;
 use test_utils::interfaces::oracle::oracle_abi;
 use test_utils::interfaces::pyth_oracle::{pyth_oracle_abi, pyth_price_feed, PYTH_TIMESTAMP};
 use test_utils::{
     data_structures::PRECISION,
     interfaces::multi_trove_getter::multi_trove_getter_abi,
     setup::common::{deploy_multi_trove_getter, setup_protocol},
+    utils::{calculate_icr, with_min_borrow_fee},
 };
 
 #[tokio::test]
@@ -69,3 +73,185 @@ async fn test_get_multiple_sorted_troves() {
         );
     }
 }
+
+#[tokio::test]
+async fn test_cursor_pagination_icr_filter_and_user_troves() {
+    let (contracts, _admin, mut wallets) = setup_protocol(10, true, false).await;
+    let wallet = wallets.pop().unwrap();
+
+    let multi_trove_getter = deploy_multi_trove_getter(
+        &wallet,
+        &contracts.sorted_troves.contract.contract_id().into(),
+    )
+    .await;
+
+    for asset_contracts in contracts.asset_contracts.iter() {
+        oracle_abi::set_debug_timestamp(&asset_contracts.oracle, PYTH_TIMESTAMP).await;
+        pyth_oracle_abi::update_price_feeds(&asset_contracts.mock_pyth_oracle, pyth_price_feed(1))
+            .await;
+    }
+
+    // create 5 troves each with 600 USDF debt and n * 1000 collateral
+    let usdf_amount = 600 * PRECISION;
+    let mut borrowers: Vec<WalletUnlocked> = vec![];
+    for i in 1..=5 {
+        let wallet = wallets.pop().unwrap();
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[0],
+            &contracts.borrow_operations,
+            &contracts.usdf,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            i * 1000 * PRECISION,
+            usdf_amount,
+        )
+        .await;
+        borrowers.push(wallet);
+    }
+    let borrower_ids: Vec<Identity> = borrowers
+        .iter()
+        .map(|wallet| Identity::Address(wallet.address().into()))
+        .collect();
+    let null_id = Identity::Address(Address::zeroed());
+    let price = PRECISION;
+
+    // Ascending pages of 2 walk from the tail
+    let (page, cursor) = multi_trove_getter_abi::get_sorted_troves_after(
+        &multi_trove_getter,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        null_id.clone(),
+        true,
+        2,
+        price,
+    )
+    .await
+    .value;
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].address, borrower_ids[0]);
+    assert_eq!(page[1].address, borrower_ids[1]);
+    assert_eq!(cursor, borrower_ids[1]);
+
+    let debt = with_min_borrow_fee(usdf_amount);
+    assert_eq!(page[0].debt, debt);
+    assert_eq!(page[0].collateral, 1000 * PRECISION);
+    assert_eq!(page[0].icr, calculate_icr(1000 * PRECISION, debt));
+    assert_eq!(page[0].nicr, calculate_icr(1000 * PRECISION, debt));
+    assert_eq!(page[0].stake, 1000 * PRECISION);
+    assert_eq!(page[0].status, Status::Active);
+
+    let (page, cursor) = multi_trove_getter_abi::get_sorted_troves_after(
+        &multi_trove_getter,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        cursor,
+        true,
+        2,
+        price,
+    )
+    .await
+    .value;
+    assert_eq!(page[0].address, borrower_ids[2]);
+    assert_eq!(page[1].address, borrower_ids[3]);
+
+    let (page, cursor) = multi_trove_getter_abi::get_sorted_troves_after(
+        &multi_trove_getter,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        cursor,
+        true,
+        2,
+        price,
+    )
+    .await
+    .value;
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].address, borrower_ids[4]);
+    assert_eq!(cursor, null_id);
+
+    // Descending walks from the head
+    let (page, _) = multi_trove_getter_abi::get_sorted_troves_after(
+        &multi_trove_getter,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        borrower_ids[4].clone(),
+        false,
+        10,
+        price,
+    )
+    .await
+    .value;
+    assert_eq!(page.len(), 4);
+    assert_eq!(page[0].address, borrower_ids[3]);
+    assert_eq!(page[3].address, borrower_ids[0]);
+
+    // Only troves with 2000 <= coll <= 4000 are in range
+    let (page, cursor) = multi_trove_getter_abi::get_sorted_troves_in_icr_range(
+        &multi_trove_getter,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].asset_id,
+        null_id.clone(),
+        true,
+        10,
+        price,
+        calculate_icr(2000 * PRECISION, debt),
+        calculate_icr(4000 * PRECISION, debt),
+    )
+    .await
+    .value;
+    assert_eq!(page.len(), 3);
+    assert_eq!(page[0].address, borrower_ids[1]);
+    assert_eq!(page[2].address, borrower_ids[3]);
+    assert_eq!(cursor, null_id);
+
+    // The first borrower also opens a trove on the second asset
+    borrow_operations_utils::mint_token_and_open_trove(
+        borrowers[0].clone(),
+        &contracts.asset_contracts[1],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        3000 * PRECISION,
+        usdf_amount,
+    )
+    .await;
+
+    let user_troves = multi_trove_getter_abi::get_user_troves(
+        &multi_trove_getter,
+        &contracts.protocol_manager,
+        &contracts.asset_contracts,
+        borrower_ids[0].clone(),
+    )
+    .await
+    .value;
+    assert_eq!(user_troves.len(), 2);
+    assert_eq!(
+        user_troves[0].asset_id,
+        contracts.asset_contracts[0].asset_id
+    );
+    assert_eq!(user_troves[0].collateral, 1000 * PRECISION);
+    assert_eq!(
+        user_troves[1].asset_id,
+        contracts.asset_contracts[1].asset_id
+    );
+    assert_eq!(user_troves[1].collateral, 3000 * PRECISION);
+
+    let user_troves = multi_trove_getter_abi::get_user_troves(
+        &multi_trove_getter,
+        &contracts.protocol_manager,
+        &contracts.asset_contracts,
+        borrower_ids[1].clone(),
+    )
+    .await
+    .value;
+    assert_eq!(user_troves.len(), 1);
+}
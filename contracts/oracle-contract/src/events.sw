@@ -0,0 +1,21 @@
+library;
+
+use libraries::oracle_interface::RedstoneConfig;
+
+pub struct RedstoneConfigSetEvent {
+    pub config: RedstoneConfig,
+}
+
+pub struct RedstoneConfigUpdateProposedEvent {
+    pub config: Option<RedstoneConfig>,
+    pub effective_time: u64,
+}
+
+pub struct RedstoneConfigUpdateCancelledEvent {
+    pub config: Option<RedstoneConfig>,
+}
+
+pub struct RedstoneConfigUpdatedEvent {
+    pub old_config: Option<RedstoneConfig>,
+    pub new_config: Option<RedstoneConfig>,
+}
@@ -13,6 +13,10 @@ contract;
 //
 // This prioritization ensures that the most reliable and recent price data is used,
 // enhancing the overall stability and accuracy of the Fluid Protocol.
+//
+// Redstone Configuration:
+// The Redstone fallback can be set once by the initializer. Afterwards the owner can replace or remove it
+// through a two-step process (propose, then execute) separated by REDSTONE_CONFIG_TIMELOCK seconds.
 // 
 // IMPORTANT: The price returned by this contract should be the price in USD for 1_000_000_000 units of the asset in the Fuel network.
 // Example 1: if the price of ETH is $3,000 USD with a 9 decimal representation in the fuel network, then the price of ETH is 3_000_000_000_000.
@@ -20,6 +24,14 @@ contract;
 // But 1_000_000_000 units of BTC in the fuelvm with 8 decimal precision is 10 BTC, so the price returned by this contract should be 600_000_000_000_000 for 10 BTC.
 // Refer to https://github.com/FuelLabs/verified-assets/blob/main/assets.json for the number of units that 1_000_000_000 units of an asset is in the fuelvm.
 
+mod events;
+
+use ::events::{
+    RedstoneConfigSetEvent,
+    RedstoneConfigUpdateCancelledEvent,
+    RedstoneConfigUpdatedEvent,
+    RedstoneConfigUpdateProposedEvent,
+};
 use libraries::{
     fluid_math::{
         convert_precision,
@@ -28,11 +40,14 @@ use libraries::{
     oracle_interface::RedstoneCore,
     oracle_interface::{
         Oracle,
+        PendingRedstoneConfig,
         RedstoneConfig,
     },
 };
-use std::{block::timestamp, constants::ZERO_B256,};
+use std::{block::timestamp, constants::ZERO_B256, logging::log,};
 use pyth_interface::{data_structures::price::{Price, PriceFeedId}, PythCore};
+use sway_libs::ownership::*;
+use standards::{src5::*,};
 
 // // Hack: Sway does not provide a downcast to u64
 // // If redstone provides a strangely high u256 which shouldn't be cast down
@@ -52,6 +67,8 @@ configurable {
 }
 // Timeout period for considering oracle data as stale (10 minutes in seconds)
 const TIMEOUT: u64 = 600;
+// Delay between proposing and executing a Redstone config change (2 days in seconds)
+const REDSTONE_CONFIG_TIMELOCK: u64 = 172_800;
 
 storage {
     /// The last valid price from either Pyth or Redstone
@@ -64,6 +81,9 @@ storage {
     // Used for simulating different timestamps during testing
     debug_timestamp: u64 = 0,
     redstone_config: Option<RedstoneConfig> = None,
+    // Set once a Redstone config has been written, so removal cannot reopen the initializer path
+    is_redstone_config_initialized: bool = false,
+    pending_redstone_config: Option<PendingRedstoneConfig> = None,
 }
 
 impl Oracle for Contract {
@@ -78,10 +98,7 @@ impl Oracle for Contract {
             return pyth_price.price;
         }
         // Determine the current timestamp based on debug mode
-        let current_time = match DEBUG {
-            true => storage.debug_timestamp.read(),
-            false => timestamp(),
-        };
+        let current_time = internal_get_current_time();
         // Read the last stored valid price
         let last_price = storage.last_good_price.read();
         // Check if Pyth data is stale or outside confidence
@@ -153,6 +170,22 @@ impl Oracle for Contract {
         storage.debug_timestamp.write(timestamp);
     }
 
+    #[storage(read, write)]
+    fn initialize(owner: Identity) {
+        require(
+            msg_sender()
+                .unwrap() == INITIALIZER,
+            "ORACLE: Only initializer can initialize",
+        );
+        initialize_ownership(owner);
+    }
+
+    #[storage(read, write)]
+    fn transfer_owner(new_owner: Identity) {
+        only_owner();
+        transfer_ownership(new_owner);
+    }
+
     #[storage(read, write)]
     fn set_redstone_config(config: RedstoneConfig) {
         require(
@@ -164,12 +197,114 @@ impl Oracle for Contract {
             storage
                 .redstone_config
                 .read()
-                .is_none(),
+                .is_none() && !storage
+                .is_redstone_config_initialized
+                .read(),
             "ORACLE: Redstone config already set",
         );
         storage.redstone_config.write(Some(config));
+        storage.is_redstone_config_initialized.write(true);
+
+        log(RedstoneConfigSetEvent { config });
+    }
+
+    #[storage(read, write)]
+    fn propose_redstone_config(config: RedstoneConfig) {
+        only_owner();
+        internal_propose_redstone_config(Some(config));
+    }
+
+    #[storage(read, write)]
+    fn propose_redstone_config_removal() {
+        only_owner();
+        require(
+            storage
+                .redstone_config
+                .read()
+                .is_some(),
+            "ORACLE: Redstone config not set",
+        );
+        internal_propose_redstone_config(None);
+    }
+
+    #[storage(read, write)]
+    fn execute_redstone_config_update() {
+        only_owner();
+        let pending = storage.pending_redstone_config.read();
+        require(pending.is_some(), "ORACLE: No pending Redstone config");
+        let pending = pending.unwrap();
+        require(
+            internal_get_current_time() >= pending.effective_time,
+            "ORACLE: Redstone config timelock not expired",
+        );
+
+        let old_config = storage.redstone_config.read();
+        storage.redstone_config.write(pending.config);
+        storage.is_redstone_config_initialized.write(true);
+        storage.pending_redstone_config.write(None);
+
+        log(RedstoneConfigUpdatedEvent {
+            old_config,
+            new_config: pending.config,
+        });
+    }
+
+    #[storage(read, write)]
+    fn cancel_redstone_config_update() {
+        only_owner();
+        let pending = storage.pending_redstone_config.read();
+        require(pending.is_some(), "ORACLE: No pending Redstone config");
+        storage.pending_redstone_config.write(None);
+
+        log(RedstoneConfigUpdateCancelledEvent {
+            config: pending.unwrap().config,
+        });
+    }
+
+    #[storage(read)]
+    fn get_redstone_config() -> Option<RedstoneConfig> {
+        storage.redstone_config.read()
+    }
+
+    #[storage(read)]
+    fn get_pending_redstone_config() -> Option<PendingRedstoneConfig> {
+        storage.pending_redstone_config.read()
+    }
+}
+
+impl SRC5 for Contract {
+    #[storage(read)]
+    fn owner() -> State {
+        _owner()
+    }
+}
+
+// Queues a Redstone config change, replacing any change that is already pending
+// A config of None removes the Redstone fallback once executed
+#[storage(read, write)]
+fn internal_propose_redstone_config(config: Option<RedstoneConfig>) {
+    let effective_time = internal_get_current_time() + REDSTONE_CONFIG_TIMELOCK;
+    storage
+        .pending_redstone_config
+        .write(Some(PendingRedstoneConfig {
+            config,
+            effective_time,
+        }));
+
+    log(RedstoneConfigUpdateProposedEvent {
+        config,
+        effective_time,
+    });
+}
+
+#[storage(read)]
+fn internal_get_current_time() -> u64 {
+    match DEBUG {
+        true => storage.debug_timestamp.read(),
+        false => timestamp(),
     }
 }
+
 // Assets in the fuel VM can have a different decimal representation
 // This function adjusts the price to align with the decimal representation of the Fuel VM
 fn pyth_price_with_fuel_vm_precision_adjustment(pyth_price: Price, fuel_vm_decimals: u32) -> Price {
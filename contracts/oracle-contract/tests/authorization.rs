@@ -2,7 +2,7 @@ use fuels::{prelude::*, types::Identity};
 use test_utils::{
     data_structures::ContractInstance,
     interfaces::{
-        oracle::{oracle_abi, Oracle, RedstoneConfig},
+        oracle::{oracle_abi, Oracle, RedstoneConfig, REDSTONE_CONFIG_TIMELOCK},
         pyth_oracle::{pyth_oracle_abi, Price, PythCore, DEFAULT_PYTH_PRICE_ID},
     },
     setup::common::{deploy_mock_pyth_oracle, deploy_mock_redstone_oracle, deploy_oracle},
@@ -133,3 +133,165 @@ async fn test_get_price_pyth_only() {
         "Oracle should return last good price when Pyth is stale"
     );
 }
+
+#[tokio::test]
+async fn test_update_redstone_config_authorization_and_timelock() {
+    let (oracle, _, deployer_wallet, attacker_wallet) = setup().await;
+    let redstone = deploy_mock_redstone_oracle(&deployer_wallet).await;
+    let start_timestamp = 1234567890;
+    oracle_abi::set_debug_timestamp(&oracle, start_timestamp).await;
+
+    let initial_config = RedstoneConfig {
+        contract_id: redstone.contract_id().into(),
+        price_id: [2u8; 32].into(),
+        precision: 6,
+    };
+    let updated_config = RedstoneConfig {
+        contract_id: redstone.contract_id().into(),
+        price_id: [3u8; 32].into(),
+        precision: 8,
+    };
+
+    oracle_abi::set_redstone_config(&oracle, &redstone, initial_config.clone())
+        .await
+        .unwrap();
+
+    // Test 1: Proposing before ownership is initialized fails
+    let result = oracle_abi::propose_redstone_config(&oracle, updated_config.clone()).await;
+    assert!(
+        result.is_err(),
+        "Proposing without an owner should not be possible"
+    );
+
+    // Test 2: Only the initializer can initialize ownership
+    let oracle_attacker = ContractInstance::new(
+        Oracle::new(
+            oracle.contract.contract_id().clone(),
+            attacker_wallet.clone(),
+        ),
+        oracle.implementation_id,
+    );
+    let result = oracle_abi::initialize(
+        &oracle_attacker,
+        Identity::Address(attacker_wallet.address().into()),
+    )
+    .await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to initialize the oracle"
+    );
+    if let Err(error) = result {
+        assert!(
+            error
+                .to_string()
+                .contains("Only initializer can initialize"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+
+    oracle_abi::initialize(&oracle, Identity::Address(deployer_wallet.address().into()))
+        .await
+        .unwrap();
+
+    // Test 3: Unauthorized propose
+    let result =
+        oracle_abi::propose_redstone_config(&oracle_attacker, updated_config.clone()).await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to propose a Redstone config"
+    );
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("NotOwner"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+
+    // Test 4: Execution is blocked until the timelock expires
+    oracle_abi::propose_redstone_config(&oracle, updated_config.clone())
+        .await
+        .unwrap();
+    let pending = oracle_abi::get_pending_redstone_config(&oracle)
+        .await
+        .value
+        .unwrap();
+    assert_eq!(pending.config, Some(updated_config.clone()));
+    assert_eq!(
+        pending.effective_time,
+        start_timestamp + REDSTONE_CONFIG_TIMELOCK
+    );
+
+    let result = oracle_abi::execute_redstone_config_update(&oracle).await;
+    assert!(result.is_err(), "Timelock should block execution");
+    if let Err(error) = result {
+        assert!(
+            error
+                .to_string()
+                .contains("Redstone config timelock not expired"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+
+    oracle_abi::set_debug_timestamp(&oracle, start_timestamp + REDSTONE_CONFIG_TIMELOCK).await;
+
+    let result = oracle_abi::execute_redstone_config_update(&oracle_attacker).await;
+    assert!(
+        result.is_err(),
+        "Unauthorized user should not be able to execute a Redstone config update"
+    );
+
+    oracle_abi::execute_redstone_config_update(&oracle)
+        .await
+        .unwrap();
+    assert_eq!(
+        oracle_abi::get_redstone_config(&oracle).await.value,
+        Some(updated_config.clone())
+    );
+    assert_eq!(
+        oracle_abi::get_pending_redstone_config(&oracle).await.value,
+        None
+    );
+
+    // Test 5: Cancelled proposals cannot be executed
+    oracle_abi::propose_redstone_config_removal(&oracle)
+        .await
+        .unwrap();
+    oracle_abi::cancel_redstone_config_update(&oracle)
+        .await
+        .unwrap();
+    let result = oracle_abi::execute_redstone_config_update(&oracle).await;
+    assert!(result.is_err(), "Cancelled update should not be executable");
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("No pending Redstone config"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+
+    // Test 6: Removal goes through the timelock and cannot be bypassed by the initializer
+    oracle_abi::propose_redstone_config_removal(&oracle)
+        .await
+        .unwrap();
+    oracle_abi::set_debug_timestamp(&oracle, start_timestamp + 2 * REDSTONE_CONFIG_TIMELOCK).await;
+    oracle_abi::execute_redstone_config_update(&oracle)
+        .await
+        .unwrap();
+    assert_eq!(oracle_abi::get_redstone_config(&oracle).await.value, None);
+
+    let result = oracle_abi::set_redstone_config(&oracle, &redstone, initial_config.clone()).await;
+    assert!(
+        result.is_err(),
+        "Initializer should not be able to set Redstone config after removal"
+    );
+    if let Err(error) = result {
+        assert!(
+            error.to_string().contains("Redstone config already set"),
+            "Unexpected error message: {}",
+            error
+        );
+    }
+}
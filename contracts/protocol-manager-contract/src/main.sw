@@ -11,14 +11,19 @@ mod data_structures;
 use ::data_structures::{AssetContracts, AssetInfo, RedemptionTotals};
 use libraries::stability_pool_interface::StabilityPool;
 use libraries::trove_manager_interface::TroveManager;
-use libraries::trove_manager_interface::data_structures::SingleRedemptionValues;
+use libraries::trove_manager_interface::data_structures::{
+    AuctionConfig,
+    AutoDeleverageParams,
+    LiquidationParams,
+    SingleRedemptionValues,
+};
 use libraries::borrow_operations_interface::BorrowOperations;
 use libraries::sorted_troves_interface::SortedTroves;
 use libraries::active_pool_interface::ActivePool;
 use libraries::default_pool_interface::DefaultPool;
 use libraries::coll_surplus_pool_interface::CollSurplusPool;
 use libraries::oracle_interface::Oracle;
-use libraries::protocol_manager_interface::ProtocolManager;
+use libraries::protocol_manager_interface::{ProtocolManager, RegisteredAsset};
 use libraries::usdf_token_interface::USDFToken;
 use libraries::fpt_staking_interface::FPTStaking;
 use libraries::fluid_math::*;
@@ -136,6 +141,8 @@ impl ProtocolManager for Contract {
         partial_redemption_hint: u64,
         upper_partial_hint: Identity,
         lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+        min_collateral_out: Vec<(AssetId, u64)>,
     ) {
         require(
             storage
@@ -150,6 +157,10 @@ impl ProtocolManager for Contract {
             msg_amount() > 0,
             "ProtocolManager: Redemption amount must be greater than 0",
         );
+        require(
+            max_fee_percentage <= DECIMAL_PRECISION,
+            "ProtocolManager: Max fee percentage must be at most 100%",
+        );
         let usdf_contract_cache = storage.usdf_token_contract.read();
         let fpt_staking_contract_cache = storage.fpt_staking_contract.read();
         let usdf = abi(SRC3, usdf_contract_cache.bits());
@@ -193,12 +204,17 @@ impl ProtocolManager for Contract {
             totals.total_asset_drawn += single_redemption.asset_lot;
             remaining_usdf -= single_redemption.usdf_lot;
 
-            let mut next_cr = u64::max();
-            if (next_user_to_check != null_identity_address()) {
-                next_cr = trove_manager_contract.get_current_icr(next_user_to_check, price);
-            }
+            // Troves below the MCR further up the list stay exempt, pending rewards can push them out of ICR order
+            let (next_redeemable_borrower, next_cr) = get_first_redeemable_borrower(
+                next_user_to_check,
+                contracts_cache
+                    .asset_address,
+                contracts_cache
+                    .trove_manager,
+                price,
+            );
             assets_info.current_crs.set(index, next_cr);
-            assets_info.current_borrowers.set(index, next_user_to_check);
+            assets_info.current_borrowers.set(index, next_redeemable_borrower);
             assets_info.redemption_totals.set(index, totals);
             let next_borrower = find_min_borrower(assets_info.current_borrowers, assets_info.current_crs);
             current_borrower = next_borrower.0;
@@ -215,13 +231,26 @@ impl ProtocolManager for Contract {
             let mut totals = assets_info.redemption_totals.get(ind).unwrap();
 
             if (totals.total_usdf_to_redeem == 0) {
+                require_min_collateral_received(0, contracts_cache.asset_address, min_collateral_out);
                 ind += 1;
                 continue;
             }
 
             // Calculate redemption fee and amount to send to redeemer
             totals.asset_fee = fm_compute_redemption_fee(totals.total_asset_drawn);
+            require(
+                totals
+                    .asset_fee <= fm_multiply_ratio(totals.total_asset_drawn, max_fee_percentage, DECIMAL_PRECISION),
+                "ProtocolManager: Fee exceeded provided maximum",
+            );
             totals.asset_to_send_to_redeemer = totals.total_asset_drawn - totals.asset_fee;
+            require_min_collateral_received(
+                totals
+                    .asset_to_send_to_redeemer,
+                contracts_cache
+                    .asset_address,
+                min_collateral_out,
+            );
 
             // Send redemption fee to FPT stakers
             active_pool.send_asset(
@@ -269,11 +298,138 @@ impl ProtocolManager for Contract {
 
         storage.lock_redeem_collateral.write(false);
     }
+    // Creates an isolated stability sub-pool covering the given sorted assets
+    #[storage(read, write)]
+    fn create_stability_sub_pool(assets: Vec<AssetId>) {
+        only_owner();
+        let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().bits());
+        stability_pool.create_sub_pool(assets);
+    }
+    // Sets the stability pool two-step withdrawal delay and execution window, a zero delay disables it
+    #[storage(read, write)]
+    fn set_stability_pool_withdrawal_cooldown(delay: u64, window: u64) {
+        only_owner();
+        let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().bits());
+        stability_pool.set_withdrawal_cooldown(delay, window);
+    }
+    // Lets a depositor such as the stability pool vault withdraw without a request
+    #[storage(read, write)]
+    fn set_stability_pool_withdrawal_cooldown_exempt(depositor: Identity, exempt: bool) {
+        only_owner();
+        let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().bits());
+        stability_pool.set_withdrawal_cooldown_exempt(depositor, exempt);
+    }
+    #[storage(read, write)]
+    fn set_sorted_troves_max_size(size: u64) {
+        only_owner();
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+        sorted_troves.set_max_size(size);
+    }
+    #[storage(read, write)]
+    fn set_sorted_troves_asset_max_size(asset: AssetId, size: u64) {
+        only_owner();
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+        sorted_troves.set_asset_max_size(asset, size);
+    }
+    // Sets the collateral auction backstop used when the stability pool can't cover liquidated debt
+    #[storage(read, write)]
+    fn set_liquidation_auction_config(asset: AssetId, config: AuctionConfig) {
+        only_owner();
+        let asset_contracts = storage.asset_contracts.get(asset).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "ProtocolManager: Asset is not registered",
+        );
+        let trove_manager = abi(TroveManager, asset_contracts.unwrap().trove_manager.bits());
+        trove_manager.set_auction_config(config);
+    }
+    // Sets the partial liquidation target and the split of the liquidation penalty for an asset
+    #[storage(read, write)]
+    fn set_liquidation_params(asset: AssetId, params: LiquidationParams) {
+        only_owner();
+        let asset_contracts = storage.asset_contracts.get(asset).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "ProtocolManager: Asset is not registered",
+        );
+        let trove_manager = abi(TroveManager, asset_contracts.unwrap().trove_manager.bits());
+        trove_manager.set_liquidation_params(params);
+    }
+    // Sets the keeper fee and the swap venue used to deleverage opted-in troves of an asset
+    #[storage(read, write)]
+    fn set_auto_deleverage_params(asset: AssetId, params: AutoDeleverageParams) {
+        only_owner();
+        let asset_contracts = storage.asset_contracts.get(asset).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "ProtocolManager: Asset is not registered",
+        );
+        let trove_manager = abi(TroveManager, asset_contracts.unwrap().trove_manager.bits());
+        trove_manager.set_auto_deleverage_params(params);
+    }
+    // Sets the share of an asset's borrowing fees that funds its bad debt reserve
+    #[storage(read, write)]
+    fn set_reserve_fee_share(asset: AssetId, share: u64) {
+        only_owner();
+        let asset_contracts = storage.asset_contracts.get(asset).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "ProtocolManager: Asset is not registered",
+        );
+        let trove_manager = abi(TroveManager, asset_contracts.unwrap().trove_manager.bits());
+        trove_manager.set_reserve_fee_share(share);
+    }
+    // Pays an asset's outstanding bad debt back from its reserve, to the stability pool and then the given troves
+    #[storage(read, write)]
+    fn cover_bad_debt(asset: AssetId, borrowers: Vec<Identity>) {
+        only_owner();
+        let asset_contracts = storage.asset_contracts.get(asset).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "ProtocolManager: Asset is not registered",
+        );
+        let trove_manager = abi(TroveManager, asset_contracts.unwrap().trove_manager.bits());
+        trove_manager.cover_bad_debt(borrowers);
+    }
     #[storage(read, write)]
     fn transfer_owner(new_owner: Identity) {
         only_owner();
         transfer_ownership(new_owner);
     }
+    #[storage(read)]
+    fn get_registered_assets() -> Vec<RegisteredAsset> {
+        let mut registered_assets: Vec<RegisteredAsset> = Vec::new();
+        let mut ind = 0;
+        while (ind < storage.assets.len()) {
+            let asset = storage.assets.get(ind).unwrap().read();
+            let asset_contracts = storage.asset_contracts.get(asset).read();
+            registered_assets.push(RegisteredAsset {
+                asset,
+                trove_manager: asset_contracts.trove_manager,
+                oracle: asset_contracts.oracle,
+            });
+            ind += 1;
+        }
+        registered_assets
+    }
+    // Outstanding bad debt not covered by the reserves, summed over all registered assets
+    #[storage(read)]
+    fn get_total_bad_debt() -> u64 {
+        let mut total_bad_debt = 0;
+        let mut ind = 0;
+        while (ind < storage.assets.len()) {
+            let asset = storage.assets.get(ind).unwrap().read();
+            let trove_manager = abi(TroveManager, storage.asset_contracts.get(asset).read().trove_manager.bits());
+            total_bad_debt += trove_manager.get_bad_debt();
+            ind += 1;
+        }
+        total_bad_debt
+    }
 }
 
 impl SRC5 for Contract {
@@ -315,18 +471,19 @@ fn get_all_assets_info() -> AssetInfo {
         let trove_manager = abi(TroveManager, asset_contracts.get(i).unwrap().trove_manager.into());
         let asset = assets.get(i).unwrap();
         let price = oracle.get_price();
-        let mut current_borrower = sorted_troves.get_last(asset);
-        let mut current_cr = u64::max();
-        if (current_borrower != null_identity_address()) {
-            current_cr = trove_manager.get_current_icr(current_borrower, price);
-        }
+        let (current_borrower, current_cr) = get_first_redeemable_borrower(
+            sorted_troves
+                .get_last(asset),
+            asset,
+            asset_contracts
+                .get(i)
+                .unwrap()
+                .trove_manager,
+            price,
+        );
         prices.push(price);
         system_debt.push(trove_manager.get_entire_system_debt());
         redemption_totals.push(RedemptionTotals::default());
-        while (current_borrower != null_identity_address() && current_cr < MCR) {
-            current_borrower = sorted_troves.get_prev(current_borrower, asset);
-            current_cr = trove_manager.get_current_icr(current_borrower, price);
-        }
         current_borrowers.push(current_borrower);
         current_crs.push(current_cr);
         i += 1;
@@ -341,6 +498,43 @@ fn get_all_assets_info() -> AssetInfo {
         current_crs: current_crs,
     }
 }
+// Walks up the sorted list to the first trove at or above the MCR, troves below it are left to liquidations
+#[storage(read)]
+fn get_first_redeemable_borrower(
+    borrower: Identity,
+    asset: AssetId,
+    trove_manager_contract: ContractId,
+    price: u64,
+) -> (Identity, u64) {
+    let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().bits());
+    let trove_manager = abi(TroveManager, trove_manager_contract.bits());
+    let mut current_borrower = borrower;
+    while (current_borrower != null_identity_address()) {
+        let current_cr = trove_manager.get_current_icr(current_borrower, price);
+        if (current_cr >= MCR) {
+            return (current_borrower, current_cr);
+        }
+        current_borrower = sorted_troves.get_prev(current_borrower, asset);
+    }
+    (null_identity_address(), u64::max())
+}
+fn require_min_collateral_received(
+    received: u64,
+    asset: AssetId,
+    min_collateral_out: Vec<(AssetId, u64)>,
+) {
+    let mut i = 0;
+    while (i < min_collateral_out.len()) {
+        let (min_asset, min_amount) = min_collateral_out.get(i).unwrap();
+        if (min_asset == asset) {
+            require(
+                received >= min_amount,
+                "ProtocolManager: Received less collateral than the minimum",
+            );
+        }
+        i += 1;
+    }
+}
 // Find the borrower with the lowest collateral ratio
 fn find_min_borrower(current_borrowers: Vec<Identity>, current_crs: Vec<u64>) -> (Identity, u64) {
     let mut min_borrower = current_borrowers.get(0).unwrap();
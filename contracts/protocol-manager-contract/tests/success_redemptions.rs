@@ -2,15 +2,15 @@ use fuels::{prelude::*, types::Identity};
 use test_utils::data_structures::{ContractInstance, PRECISION};
 use test_utils::interfaces::oracle::oracle_abi;
 use test_utils::interfaces::protocol_manager::ProtocolManager;
-use test_utils::interfaces::pyth_oracle::PYTH_TIMESTAMP;
+use test_utils::interfaces::pyth_oracle::{PYTH_PRECISION, PYTH_TIMESTAMP};
 use test_utils::utils::print_response;
 use test_utils::{
     interfaces::{
         active_pool::active_pool_abi,
-        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
         coll_surplus_pool::coll_surplus_pool_abi,
         protocol_manager::protocol_manager_abi,
-        pyth_oracle::{pyth_oracle_abi, pyth_price_feed},
+        pyth_oracle::{pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time},
         token::token_abi,
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
     },
@@ -168,6 +168,8 @@ async fn proper_redemption_from_partially_closed() {
         0,
         None,
         None,
+        PRECISION,
+        vec![],
         &contracts.usdf,
         &contracts.fpt_staking,
         &contracts.coll_surplus_pool,
@@ -176,7 +178,8 @@ async fn proper_redemption_from_partially_closed() {
         &contracts.sorted_troves,
         &contracts.asset_contracts,
     )
-    .await;
+    .await
+    .unwrap();
 
     let logs = res.decode_logs();
     let redemption_event = logs
@@ -414,6 +417,8 @@ async fn proper_redemption_with_a_trove_closed_fully() {
         0,
         None,
         None,
+        PRECISION,
+        vec![],
         &contracts.usdf,
         &contracts.fpt_staking,
         &contracts.coll_surplus_pool,
@@ -422,7 +427,8 @@ async fn proper_redemption_with_a_trove_closed_fully() {
         &contracts.sorted_troves,
         &contracts.asset_contracts,
     )
-    .await;
+    .await
+    .unwrap();
 
     let active_pool_asset = active_pool_abi::get_asset(
         &contracts.active_pool,
@@ -518,3 +524,166 @@ async fn proper_redemption_with_a_trove_closed_fully() {
 
     assert_eq!(coll_surplus, coll3 - with_min_borrow_fee(debt3));
 }
+
+#[tokio::test]
+async fn proper_redemption_with_fee_and_min_collateral_protections() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, true, false).await;
+
+    let redeemer_wallet = wallets.pop().unwrap();
+    let healthy_wallet1 = wallets.pop().unwrap();
+    let healthy_wallet2 = wallets.pop().unwrap();
+    let risky_wallet = wallets.pop().unwrap();
+
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[1].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[1].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    for (wallet, coll) in [
+        (&redeemer_wallet, 1_000 * PRECISION),
+        (&healthy_wallet1, 900 * PRECISION),
+        (&healthy_wallet2, 800 * PRECISION),
+        (&risky_wallet, 700 * PRECISION),
+    ] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[0],
+            &contracts.borrow_operations,
+            &contracts.usdf,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll,
+            5_000 * PRECISION,
+        )
+        .await;
+    }
+
+    // The riskiest trove drops below the MCR and is exempt from redemptions
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(9, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    let protocol_manager_redeemer = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            redeemer_wallet.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+
+    let redemption_amount: u64 = 3_000 * PRECISION;
+    let asset_drawn =
+        (redemption_amount as u128 * PRECISION as u128 / (9 * PRECISION) as u128) as u64;
+    let asset_received = asset_drawn - trove_manager_abi::get_redemption_fee(asset_drawn);
+
+    // The 1% redemption fee is above a 0.5% maximum
+    let res = protocol_manager_abi::redeem_collateral(
+        &protocol_manager_redeemer,
+        redemption_amount,
+        10,
+        0,
+        None,
+        None,
+        5_000_000,
+        vec![],
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert!(res.is_err(), "Redemption above the max fee should fail");
+
+    let res = protocol_manager_abi::redeem_collateral(
+        &protocol_manager_redeemer,
+        redemption_amount,
+        10,
+        0,
+        None,
+        None,
+        PRECISION,
+        vec![(asset_id, asset_received + 1)],
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Redemption below the minimum collateral out should fail"
+    );
+
+    let provider = redeemer_wallet.provider().unwrap();
+    let asset_balance_before = provider
+        .get_asset_balance(redeemer_wallet.address(), asset_id)
+        .await
+        .unwrap();
+
+    protocol_manager_abi::redeem_collateral(
+        &protocol_manager_redeemer,
+        redemption_amount,
+        10,
+        0,
+        None,
+        None,
+        10_000_000,
+        vec![(asset_id, asset_received)],
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.coll_surplus_pool,
+        &contracts.default_pool,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+    )
+    .await
+    .unwrap();
+
+    let asset_balance_after = provider
+        .get_asset_balance(redeemer_wallet.address(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(asset_balance_after - asset_balance_before, asset_received);
+
+    trove_manager_utils::assert_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(risky_wallet.address().into()),
+        with_min_borrow_fee(5_000 * PRECISION),
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_debt(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(healthy_wallet2.address().into()),
+        with_min_borrow_fee(5_000 * PRECISION) - redemption_amount,
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(healthy_wallet2.address().into()),
+        800 * PRECISION - asset_drawn,
+    )
+    .await;
+}
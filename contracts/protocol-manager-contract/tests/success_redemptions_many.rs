@@ -137,6 +137,8 @@ async fn proper_multi_collateral_redemption_from_partially_closed() {
         0,
         None,
         None,
+        PRECISION,
+        vec![],
         &contracts.usdf,
         &contracts.fpt_staking,
         &contracts.coll_surplus_pool,
@@ -145,7 +147,8 @@ async fn proper_multi_collateral_redemption_from_partially_closed() {
         &contracts.sorted_troves,
         &contracts.asset_contracts,
     )
-    .await;
+    .await
+    .unwrap();
 
     let active_pool_asset = active_pool_abi::get_asset(
         &contracts.active_pool,
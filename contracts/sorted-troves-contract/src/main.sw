@@ -2,7 +2,7 @@ contract;
 
 use libraries::sorted_troves_interface::{Node, SortedTroves};
 use libraries::trove_manager_interface::TroveManager;
-use libraries::fluid_math::{null_contract, null_identity_address};
+use libraries::fluid_math::{fm_max, null_contract, null_identity_address};
 use std::{
     asset::transfer,
     block::{
@@ -29,6 +29,7 @@ storage {
     tail: StorageMap<AssetId, Identity> = StorageMap::<AssetId, Identity> {},
     nodes: StorageMap<(Identity, AssetId), Node> = StorageMap::<(Identity, AssetId), Node> {},
     size: StorageMap<AssetId, u64> = StorageMap::<AssetId, u64> {},
+    asset_max_size: StorageMap<AssetId, u64> = StorageMap::<AssetId, u64> {},
     asset_trove_manager: StorageMap<AssetId, ContractId> = StorageMap::<AssetId, ContractId> {},
     valid_trove_manager: StorageMap<Identity, bool> = StorageMap::<Identity, bool> {},
     is_initialized: bool = false,
@@ -125,6 +126,48 @@ impl SortedTroves for Contract {
     fn get_max_size() -> u64 {
         return internal_get_max_size();
     }
+    /*
+     * @dev Increase the default max size shared by every asset without an override
+     * @param size New max size, must be greater than the current one
+     */
+    #[storage(read, write)]
+    fn set_max_size(size: u64) {
+        require_is_protocol_manager();
+        require(
+            size > storage
+                .max_size
+                .read(),
+            "SortedTroves: max size can only increase",
+        );
+        storage.max_size.write(size);
+    }
+    /*
+     * @dev Set a max size for a single asset, overriding the default max size
+     * @param asset Asset of the list
+     * @param size New max size, must be greater than the current max size of the asset, the default one if no override is set
+     */
+    #[storage(read, write)]
+    fn set_asset_max_size(asset: AssetId, size: u64) {
+        require_is_protocol_manager();
+        require(
+            size > internal_get_asset_max_size(asset),
+            "SortedTroves: max size can only increase",
+        );
+        storage.asset_max_size.insert(asset, size);
+    }
+    #[storage(read)]
+    fn get_asset_max_size(asset: AssetId) -> u64 {
+        return internal_get_asset_max_size(asset);
+    }
+    #[storage(read)]
+    fn get_remaining_capacity(asset: AssetId) -> u64 {
+        let max_size = internal_get_asset_max_size(asset);
+        let size = internal_get_size(asset);
+        if size >= max_size {
+            return 0;
+        }
+        return max_size - size;
+    }
     #[storage(read)]
     fn get_first(asset: AssetId) -> Identity {
         return internal_get_first(asset);
@@ -188,13 +231,27 @@ fn internal_get_max_size() -> u64 {
     return storage.max_size.read();
 }
 #[storage(read)]
+fn internal_get_asset_max_size(asset: AssetId) -> u64 {
+    // An override never lowers the cap below the default, which may have been increased since
+    return fm_max(
+        storage
+            .asset_max_size
+            .get(asset)
+            .try_read()
+            .unwrap_or(0),
+        storage
+            .max_size
+            .read(),
+    );
+}
+#[storage(read)]
 fn internal_get_size(asset: AssetId) -> u64 {
     return storage.size.get(asset).try_read().unwrap_or(0);
 }
 #[storage(read)]
 fn internal_is_full(asset: AssetId) -> bool {
     match storage.size.get(asset).try_read() {
-        Some(size) => return size == storage.max_size.read(),
+        Some(size) => return size >= internal_get_asset_max_size(asset),
         None => return false,
     }
 }
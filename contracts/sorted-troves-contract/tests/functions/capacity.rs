@@ -0,0 +1,295 @@
+use crate::utils::setup::{
+    initialize_st_and_tm, remove, set_asset_max_size, set_max_size, set_nominal_icr_and_insert,
+    setup, try_set_nominal_icr_and_insert,
+};
+use fuels::types::AssetId;
+use fuels::{prelude::*, types::Identity};
+use test_utils::interfaces::sorted_troves::sorted_troves_abi;
+
+#[tokio::test]
+async fn fails_insert_when_full_and_proper_max_size_increase() {
+    let (sorted_troves, trove_manager, wallet, wallet2, wallets) = setup(Some(5)).await;
+    let max_size: u64 = 2;
+    let asset: AssetId = AssetId::zeroed();
+    initialize_st_and_tm(&sorted_troves, &trove_manager, max_size, asset).await;
+
+    let wallet3 = wallets[0].clone();
+    let placeholder = Identity::Address(Address::zeroed());
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet.address().into()),
+        100,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet2.address().into()),
+        200,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    let is_full = sorted_troves_abi::is_full(&sorted_troves, asset)
+        .await
+        .value;
+    assert!(is_full);
+    let remaining = sorted_troves_abi::get_remaining_capacity(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(remaining, 0);
+
+    let res = try_set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet3.address().into()),
+        300,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Should not be able to insert into a full list"
+    );
+
+    let res = set_max_size(&trove_manager, &sorted_troves, max_size).await;
+    assert!(res.is_err(), "Max size should only be able to increase");
+
+    set_max_size(&trove_manager, &sorted_troves, 3)
+        .await
+        .unwrap();
+
+    let result = sorted_troves_abi::get_max_size(&sorted_troves).await;
+    assert_eq!(result.value, 3);
+    let remaining = sorted_troves_abi::get_remaining_capacity(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(remaining, 1);
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet3.address().into()),
+        300,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    let size = sorted_troves_abi::get_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(size, 3);
+    let is_full = sorted_troves_abi::is_full(&sorted_troves, asset)
+        .await
+        .value;
+    assert!(is_full);
+}
+
+#[tokio::test]
+async fn proper_asset_max_size() {
+    let (sorted_troves, trove_manager, wallet, wallet2, wallets) = setup(Some(5)).await;
+    let max_size: u64 = 1;
+    let asset: AssetId = AssetId::zeroed();
+    let other_asset: AssetId = AssetId::new([1; 32]);
+    initialize_st_and_tm(&sorted_troves, &trove_manager, max_size, asset).await;
+
+    let wallet3 = wallets[0].clone();
+    let placeholder = Identity::Address(Address::zeroed());
+
+    let asset_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(asset_max_size, max_size);
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet.address().into()),
+        100,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    set_asset_max_size(&trove_manager, &sorted_troves, asset, 2)
+        .await
+        .unwrap();
+
+    let asset_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(asset_max_size, 2);
+
+    // Other assets keep using the default max size
+    let other_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, other_asset)
+        .await
+        .value;
+    assert_eq!(other_max_size, max_size);
+    let other_remaining = sorted_troves_abi::get_remaining_capacity(&sorted_troves, other_asset)
+        .await
+        .value;
+    assert_eq!(other_remaining, max_size);
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet2.address().into()),
+        200,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    let res = try_set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet3.address().into()),
+        300,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Should not be able to insert above the asset max size"
+    );
+
+    let res = set_asset_max_size(&trove_manager, &sorted_troves, asset, 2).await;
+    assert!(
+        res.is_err(),
+        "Asset max size should only be able to increase"
+    );
+
+    set_asset_max_size(&trove_manager, &sorted_troves, asset, 5)
+        .await
+        .unwrap();
+    let remaining = sorted_troves_abi::get_remaining_capacity(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(remaining, 3);
+
+    // Removing a node frees up capacity
+    remove(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet.address().into()),
+        asset,
+    )
+    .await;
+    let remaining = sorted_troves_abi::get_remaining_capacity(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(remaining, 4);
+
+    // Raising the default above the override raises the asset max size with it
+    set_max_size(&trove_manager, &sorted_troves, 10)
+        .await
+        .unwrap();
+    let asset_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(asset_max_size, 10);
+}
+
+#[tokio::test]
+async fn fails_first_asset_max_size_not_above_default() {
+    let (sorted_troves, trove_manager, _, _, _) = setup(Some(4)).await;
+    let max_size: u64 = 1000;
+    let asset: AssetId = AssetId::zeroed();
+    initialize_st_and_tm(&sorted_troves, &trove_manager, max_size, asset).await;
+
+    // An empty list could take any size, the first override must still exceed the default
+    let res = set_asset_max_size(&trove_manager, &sorted_troves, asset, 10).await;
+    assert!(
+        res.is_err(),
+        "First asset max size should not be below the default max size"
+    );
+
+    let res = set_asset_max_size(&trove_manager, &sorted_troves, asset, max_size).await;
+    assert!(
+        res.is_err(),
+        "First asset max size should be above the default max size"
+    );
+
+    let asset_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(asset_max_size, max_size);
+
+    set_asset_max_size(&trove_manager, &sorted_troves, asset, max_size + 1)
+        .await
+        .unwrap();
+    let asset_max_size = sorted_troves_abi::get_asset_max_size(&sorted_troves, asset)
+        .await
+        .value;
+    assert_eq!(asset_max_size, max_size + 1);
+}
+
+#[tokio::test]
+async fn fails_asset_max_size_below_current_size() {
+    let (sorted_troves, trove_manager, wallet, wallet2, _) = setup(Some(4)).await;
+    let max_size: u64 = 1000;
+    let asset: AssetId = AssetId::zeroed();
+    initialize_st_and_tm(&sorted_troves, &trove_manager, max_size, asset).await;
+
+    let placeholder = Identity::Address(Address::zeroed());
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet.address().into()),
+        100,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    set_nominal_icr_and_insert(
+        &trove_manager,
+        &sorted_troves,
+        Identity::Address(wallet2.address().into()),
+        200,
+        placeholder.clone(),
+        placeholder.clone(),
+        asset,
+    )
+    .await;
+
+    let res = set_asset_max_size(&trove_manager, &sorted_troves, asset, 1).await;
+    assert!(
+        res.is_err(),
+        "Asset max size should not be below the current size"
+    );
+
+    let res = set_asset_max_size(&trove_manager, &sorted_troves, asset, 0).await;
+    assert!(res.is_err(), "Asset max size should be greater than 0");
+
+    // Calls that don't come from the protocol manager are rejected
+    let res = sorted_troves
+        .contract
+        .methods()
+        .set_max_size(2000)
+        .with_contract_ids(&[sorted_troves.implementation_id.into()])
+        .call()
+        .await;
+    assert!(
+        res.is_err(),
+        "Only the protocol manager can set the max size"
+    );
+}
@@ -1 +1,2 @@
 pub mod sort;
+pub mod capacity;
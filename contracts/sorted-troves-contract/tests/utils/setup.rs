@@ -58,6 +58,71 @@ pub async fn set_nominal_icr_and_insert(
         .unwrap()
 }
 
+pub async fn try_set_nominal_icr_and_insert(
+    trove_manager: &MockTroveManagerContract<WalletUnlocked>,
+    sorted_troves: &ContractInstance<SortedTroves<WalletUnlocked>>,
+    new_id: Identity,
+    new_icr: u64,
+    prev_id: Identity,
+    next_id: Identity,
+    asset: AssetId,
+) -> Result<CallResponse<()>, Error> {
+    let tx_params = TxPolicies::default().with_tip(1);
+
+    trove_manager
+        .methods()
+        .set_nominal_icr_and_insert(new_id, new_icr, prev_id, next_id, asset.into())
+        .with_contracts(&[&sorted_troves.contract])
+        .with_contract_ids(&[
+            sorted_troves.implementation_id.into(),
+            sorted_troves.contract.contract_id().into(),
+        ])
+        .with_tx_policies(tx_params)
+        .call()
+        .await
+}
+
+pub async fn set_max_size(
+    trove_manager: &MockTroveManagerContract<WalletUnlocked>,
+    sorted_troves: &ContractInstance<SortedTroves<WalletUnlocked>>,
+    size: u64,
+) -> Result<CallResponse<()>, Error> {
+    let tx_params = TxPolicies::default().with_tip(1);
+
+    trove_manager
+        .methods()
+        .set_max_size(size)
+        .with_contracts(&[&sorted_troves.contract])
+        .with_contract_ids(&[
+            sorted_troves.implementation_id.into(),
+            sorted_troves.contract.contract_id().into(),
+        ])
+        .with_tx_policies(tx_params)
+        .call()
+        .await
+}
+
+pub async fn set_asset_max_size(
+    trove_manager: &MockTroveManagerContract<WalletUnlocked>,
+    sorted_troves: &ContractInstance<SortedTroves<WalletUnlocked>>,
+    asset: AssetId,
+    size: u64,
+) -> Result<CallResponse<()>, Error> {
+    let tx_params = TxPolicies::default().with_tip(1);
+
+    trove_manager
+        .methods()
+        .set_asset_max_size(asset.into(), size)
+        .with_contracts(&[&sorted_troves.contract])
+        .with_contract_ids(&[
+            sorted_troves.implementation_id.into(),
+            sorted_troves.contract.contract_id().into(),
+        ])
+        .with_tx_policies(tx_params)
+        .call()
+        .await
+}
+
 pub async fn get_nominal_icr(
     trove_manager: &MockTroveManagerContract<WalletUnlocked>,
     id: Identity,
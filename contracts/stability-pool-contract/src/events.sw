@@ -19,3 +19,68 @@ pub struct StabilityPoolLiquidationEvent {
     pub debt_to_offset: u64,
     pub collateral_to_offset: u64,
 }
+
+pub struct DepositsRestoredEvent {
+    pub asset_id: AssetId,
+    pub amount: u64,
+}
+
+pub struct FrontEndRegisteredEvent {
+    pub front_end: Identity,
+    pub kickback_rate: u64,
+}
+
+pub struct FrontEndTagSetEvent {
+    pub depositor: Identity,
+    pub front_end: Identity,
+}
+
+pub struct FrontEndStakeChangedEvent {
+    pub front_end: Identity,
+    pub new_stake: u64,
+}
+
+pub struct ClaimGainsEvent {
+    pub user: Identity,
+    pub initial_amount: u64,
+    pub compounded_amount: u64,
+}
+
+pub struct ClaimGainsToTroveEvent {
+    pub user: Identity,
+    pub asset_id: AssetId,
+    pub asset_gain: u64,
+    pub compounded_amount: u64,
+}
+
+pub struct SubPoolCreatedEvent {
+    pub pool_id: u64,
+    pub assets: Vec<AssetId>,
+}
+
+pub struct PoolOffsetEvent {
+    pub pool_id: u64,
+    pub asset_id: AssetId,
+    pub debt_to_offset: u64,
+    pub collateral_to_offset: u64,
+}
+
+pub struct WithdrawalCooldownUpdatedEvent {
+    pub delay: u64,
+    pub window: u64,
+}
+
+pub struct WithdrawalCooldownExemptionEvent {
+    pub depositor: Identity,
+    pub exempt: bool,
+}
+
+pub struct WithdrawalRequestedEvent {
+    pub user: Identity,
+    pub amount: u64,
+    pub unlock_time: u64,
+}
+
+pub struct WithdrawalRequestCancelledEvent {
+    pub user: Identity,
+}
@@ -12,20 +12,45 @@ contract;
 //
 // The contract uses a system of epochs, scales, and snapshots to accurately
 // track and distribute gains to depositors over time, even as the total deposits fluctuate.
+//
+// Deposits are open to any identity and can be tagged with a registered front end on the first deposit.
+// A front end keeps (1 - kickback rate) of the FPT earned by the deposits tagged with it, the depositors
+// keep the kickback rate share. Front end stakes compound with the same P/G/epoch/scale snapshots as deposits.
+//
+// Besides the shared pool (pool 0), the protocol manager can create isolated sub-pools covering a set of assets.
+// Each deposit lives in a single pool, chosen on the first deposit, and each pool keeps its own P/S/G/epoch/scale.
+// Liquidations of an asset are absorbed first by the sub-pools covering it, pro rata to their size, and the rest
+// by the shared pool. FPT issuance is split between the pools pro rata to their deposits.
+//
+// The protocol manager can enable a two-step withdrawal: depositors request a withdrawal, then execute it within a
+// window once the delay has passed. Requested USDF stays deposited and keeps absorbing liquidations until executed.
+// The cooldown uses the CommunityIssuance clock.
 // Solidity reference: https://github.com/liquity/dev/blob/main/packages/contracts/contracts/StabilityPool.sol
 
 mod data_structures;
 mod events;
 use ::data_structures::{AssetContracts, Snapshots};
 use ::events::{
+    ClaimGainsEvent,
+    ClaimGainsToTroveEvent,
+    DepositsRestoredEvent,
+    FrontEndRegisteredEvent,
+    FrontEndStakeChangedEvent,
+    FrontEndTagSetEvent,
+    PoolOffsetEvent,
     ProvideToStabilityPoolEvent,
     StabilityPoolLiquidationEvent,
+    SubPoolCreatedEvent,
+    WithdrawalCooldownExemptionEvent,
+    WithdrawalCooldownUpdatedEvent,
+    WithdrawalRequestCancelledEvent,
+    WithdrawalRequestedEvent,
     WithdrawFromStabilityPoolEvent,
 };
 
 use standards::src3::SRC3;
 use libraries::trove_manager_interface::data_structures::Status;
-use libraries::stability_pool_interface::StabilityPool;
+use libraries::stability_pool_interface::{FrontEnd, StabilityPool, WithdrawalRequest};
 use libraries::usdf_token_interface::USDFToken;
 use libraries::oracle_interface::Oracle;
 use libraries::active_pool_interface::ActivePool;
@@ -43,11 +68,16 @@ use std::{
     context::{
         msg_amount,
     },
-    hash::Hash,
+    hash::{
+        Hash,
+        sha256,
+    },
     storage::storage_vec::*,
     u128::U128,
 };
 const SCALE_FACTOR: u64 = 1_000_000_000;
+// Pool id of the shared pool, sub-pools are numbered from 1
+const SHARED_POOL_ID: u64 = 0;
 configurable {
     /// Initializer identity
     INITIALIZER: Identity = Identity::Address(Address::zero()),
@@ -61,6 +91,7 @@ storage {
     usdf_asset_id: AssetId = AssetId::zero(),
     community_issuance_contract: ContractId = ContractId::zero(),
     sorted_troves_contract: ContractId = ContractId::zero(),
+    borrow_operations_contract: ContractId = ContractId::zero(),
     // List of assets tracked by the Stability Pool
     valid_assets: StorageVec<AssetId> = StorageVec {},
     // Asset amounts held by the Stability Pool to be claimed
@@ -110,6 +141,47 @@ storage {
     lock_provide_to_stability_pool: bool = false,
     lock_withdraw_from_stability_pool: bool = false,
     lock_offset: bool = false,
+    lock_claim_gains: bool = false,
+    // Two-step withdrawal delay and execution window in seconds, disabled when the delay is 0
+    withdrawal_delay: u64 = 0,
+    withdrawal_window: u64 = 0,
+    // Pending withdrawal request of each depositor
+    withdrawal_requests: StorageMap<Identity, WithdrawalRequest> = StorageMap::<Identity, WithdrawalRequest> {},
+    // Depositors allowed to withdraw without a request
+    withdrawal_cooldown_exempt: StorageMap<Identity, bool> = StorageMap::<Identity, bool> {},
+    // Registered front ends and their kickback rate
+    front_ends: StorageMap<Identity, FrontEnd> = StorageMap::<Identity, FrontEnd> {},
+    // Front end tag chosen by each depositor on their first deposit
+    deposit_front_end_tags: StorageMap<Identity, Identity> = StorageMap::<Identity, Identity> {},
+    // Sum of the deposits tagged with each front end, as of the front end's last snapshot
+    front_end_stakes: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    // Snapshot of P, G, epoch and scale when each front end stake was last updated
+    front_end_snapshots: StorageMap<Identity, Snapshots> = StorageMap::<Identity, Snapshots> {},
+    /* --- SUB-POOLS ---
+    *
+    * The storage above holds the accounting of the shared pool (pool 0). Sub-pools keep the same accounting
+    * in the maps below, keyed by pool id. Front end stakes and snapshots are kept per pool as well.
+    */
+    sub_pool_count: u64 = 0,
+    // Sub-pool id for each asset set key (hash of the sorted asset set)
+    sub_pool_keys: StorageMap<b256, u64> = StorageMap::<b256, u64> {},
+    sub_pool_assets: StorageMap<u64, StorageVec<AssetId>> = StorageMap::<u64, StorageVec<AssetId>> {},
+    asset_sub_pools: StorageMap<AssetId, StorageVec<u64>> = StorageMap::<AssetId, StorageVec<u64>> {},
+    // Pool of each deposit, missing for deposits in the shared pool
+    deposit_pools: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    sub_pool_total_usdf_deposits: StorageMap<u64, u64> = StorageMap::<u64, u64> {},
+    sub_pool_p: StorageMap<u64, U128> = StorageMap::<u64, U128> {},
+    sub_pool_current_scale: StorageMap<u64, u64> = StorageMap::<u64, u64> {},
+    sub_pool_current_epoch: StorageMap<u64, u64> = StorageMap::<u64, u64> {},
+    sub_pool_epoch_to_scale_to_sum: StorageMap<(u64, u64, u64, AssetId), U128> = StorageMap::<(u64, u64, u64, AssetId), U128> {},
+    sub_pool_epoch_to_scale_to_gain: StorageMap<(u64, u64, u64), U128> = StorageMap::<(u64, u64, u64), U128> {},
+    sub_pool_last_fpt_error: StorageMap<u64, U128> = StorageMap::<u64, U128> {},
+    sub_pool_last_asset_error_offset: StorageMap<(u64, AssetId), U128> = StorageMap::<(u64, AssetId), U128> {},
+    sub_pool_last_usdf_error_offset: StorageMap<u64, U128> = StorageMap::<u64, U128> {},
+    sub_pool_front_end_stakes: StorageMap<(Identity, u64), u64> = StorageMap::<(Identity, u64), u64> {},
+    sub_pool_front_end_snapshots: StorageMap<(Identity, u64), Snapshots> = StorageMap::<(Identity, u64), Snapshots> {},
+    // Total collateral absorbed by each pool (shared pool included), per asset
+    pool_asset_gains: StorageMap<(u64, AssetId), u64> = StorageMap::<(u64, AssetId), u64> {},
 }
 impl StabilityPool for Contract {
     #[storage(read, write)]
@@ -119,6 +191,7 @@ impl StabilityPool for Contract {
         protocol_manager: ContractId,
         active_pool_contract: ContractId,
         sorted_troves_contract: ContractId,
+        borrow_operations_contract: ContractId,
     ) {
         require(
             msg_sender()
@@ -138,6 +211,9 @@ impl StabilityPool for Contract {
         storage.protocol_manager_address.write(protocol_manager);
         storage.active_pool_contract.write(active_pool_contract);
         storage.sorted_troves_contract.write(sorted_troves_contract);
+        storage
+            .borrow_operations_contract
+            .write(borrow_operations_contract);
         storage.is_initialized.write(true);
         storage
             .usdf_asset_id
@@ -165,45 +241,46 @@ impl StabilityPool for Contract {
             );
     }
     /*
-    * - Triggers a FPT issuance, based on time passed since the last issuance. The FPT issuance is shared between *all* depositors
+    * - Triggers a FPT issuance, based on time passed since the last issuance. The FPT issuance is shared between *all* depositors and front ends
+    * - Tags the deposit with the given front end if this is the depositor's first deposit, the tag is ignored otherwise
     * - Sends depositor's accumulated gains (FPT, Asset1, Asset2...) to depositor
     * - Sends the tagged front end's accumulated FPT gains to the tagged front end
-    * - Increases deposit stake, and takes new snapshots for each.
+    * - Increases deposit and tagged front end stake, and takes new snapshots for each.
+    *
+    * First deposits go to the shared pool, top ups go to the pool the deposit lives in.
+    */
+    #[storage(read, write), payable]
+    fn provide_to_stability_pool(front_end_tag: Option<Identity>) {
+        let depositor = msg_sender().unwrap();
+        internal_provide_to_pool(depositor, depositor_pool(depositor), front_end_tag);
+    }
+    /*
+    * Same as provide_to_stability_pool, with the first deposit going to the given sub-pool.
+    * Top ups must target the pool the deposit already lives in.
     */
     #[storage(read, write), payable]
-    fn provide_to_stability_pool() {
+    fn provide_to_sub_pool(pool_id: u64, front_end_tag: Option<Identity>) {
+        let depositor = msg_sender().unwrap();
+        require_pool_exists(pool_id);
         require(
             storage
-                .lock_provide_to_stability_pool
-                .read() == false,
-            "StabilityPool: Contract is locked",
+                .deposits
+                .get(depositor)
+                .try_read()
+                .unwrap_or(0) == 0 || depositor_pool(depositor) == pool_id,
+            "StabilityPool: Deposit is in another pool",
         );
-        storage.lock_provide_to_stability_pool.write(true);
-        require_usdf_is_valid_and_non_zero();
-        let initial_deposit = storage.deposits.get(msg_sender().unwrap()).try_read().unwrap_or(0);
-        internal_trigger_fpt_issuance();
-        let compounded_usdf_deposit = internal_get_compounded_usdf_deposit(msg_sender().unwrap());
-        internal_pay_out_asset_gains(msg_sender().unwrap()); // pay out asset gains
-        internal_pay_out_fpt_gains(msg_sender().unwrap());
-        let new_position = compounded_usdf_deposit + msg_amount();
-        internal_update_deposits_and_snapshots(msg_sender().unwrap(), new_position);
-        storage
-            .total_usdf_deposits
-            .write(storage.total_usdf_deposits.read() + msg_amount());
-        log(ProvideToStabilityPoolEvent {
-            user: msg_sender().unwrap(),
-            amount_to_deposit: msg_amount(),
-            initial_amount: initial_deposit,
-            compounded_amount: compounded_usdf_deposit,
-        });
-        storage.lock_provide_to_stability_pool.write(false);
+        internal_provide_to_pool(depositor, pool_id, front_end_tag);
     }
     /*
     * - Triggers a FPT issuance, based on time passed since the last issuance. The FPT issuance is shared between *all* depositors
     * - Sends all depositor's accumulated gains (FPT, Asset1, Asset2...) to depositor
-    * - Decreases deposit stake, and takes new snapshots for each.
+    * - Sends the tagged front end's accumulated FPT gains to the tagged front end
+    * - Decreases deposit and tagged front end stake, and takes new snapshots for each.
     *
     * If amount > userDeposit, the user withdraws all of their compounded deposit.
+    * When the withdrawal cooldown is enabled, the amount is capped by the depositor's request,
+    * which must be unlocked and not expired, and the request is consumed.
     */
     #[storage(read, write)]
     fn withdraw_from_stability_pool(amount: u64) {
@@ -217,14 +294,18 @@ impl StabilityPool for Contract {
         require_no_undercollateralized_troves();
         let initial_deposit = storage.deposits.get(msg_sender().unwrap()).try_read().unwrap_or(0);
         require_user_has_initial_deposit(initial_deposit);
+        let pool_id = depositor_pool(msg_sender().unwrap());
         internal_trigger_fpt_issuance();
         let compounded_usdf_deposit = internal_get_compounded_usdf_deposit(msg_sender().unwrap());
-        let usdf_to_withdraw = fm_min(amount, compounded_usdf_deposit);
+        let allowed_amount = internal_consume_withdrawal_request(msg_sender().unwrap(), amount);
+        let usdf_to_withdraw = fm_min(allowed_amount, compounded_usdf_deposit);
         let new_position = compounded_usdf_deposit - usdf_to_withdraw;
-        internal_pay_out_asset_gains(msg_sender().unwrap()); // pay out asset gains
-        internal_pay_out_fpt_gains(msg_sender().unwrap()); // pay out FPT
+        let front_end = storage.deposit_front_end_tags.get(msg_sender().unwrap()).try_read();
+        internal_pay_out_asset_gains(msg_sender().unwrap(), None); // pay out asset gains
+        internal_pay_out_fpt_gains(msg_sender().unwrap(), front_end); // pay out FPT
+        internal_update_front_end_stake(front_end, pool_id, usdf_to_withdraw, false);
         internal_update_deposits_and_snapshots(msg_sender().unwrap(), new_position);
-        send_usdf_to_depositor(msg_sender().unwrap(), usdf_to_withdraw);
+        send_usdf_to_depositor(msg_sender().unwrap(), pool_id, usdf_to_withdraw);
         log(WithdrawFromStabilityPoolEvent {
             user: msg_sender().unwrap(),
             amount_to_withdraw: usdf_to_withdraw,
@@ -234,6 +315,171 @@ impl StabilityPool for Contract {
         storage.lock_withdraw_from_stability_pool.write(false);
     }
     /*
+    * Requests a withdrawal of up to amount, executable with withdraw_from_stability_pool once the delay
+    * has passed and until the window ends. Replaces any previous request of the depositor.
+    */
+    #[storage(read, write)]
+    fn request_withdrawal(amount: u64) {
+        let depositor = msg_sender().unwrap();
+        let delay = storage.withdrawal_delay.read();
+        require(delay > 0, "StabilityPool: Withdrawal cooldown is disabled");
+        require(amount > 0, "StabilityPool: Amount must be greater than 0");
+        require_user_has_initial_deposit(storage.deposits.get(depositor).try_read().unwrap_or(0));
+        let unlock_time = internal_get_current_time() + delay;
+        storage
+            .withdrawal_requests
+            .insert(depositor, WithdrawalRequest {
+                amount,
+                unlock_time,
+            });
+        log(WithdrawalRequestedEvent {
+            user: depositor,
+            amount,
+            unlock_time,
+        });
+    }
+    #[storage(read, write)]
+    fn cancel_withdrawal_request() {
+        let depositor = msg_sender().unwrap();
+        require(
+            storage
+                .withdrawal_requests
+                .remove(depositor),
+            "StabilityPool: No withdrawal request",
+        );
+        log(WithdrawalRequestCancelledEvent {
+            user: depositor,
+        });
+    }
+    /*
+    * Sets the two-step withdrawal delay and execution window, a zero delay disables the cooldown.
+    * Only called by the ProtocolManager.
+    */
+    #[storage(read, write)]
+    fn set_withdrawal_cooldown(delay: u64, window: u64) {
+        require_is_protocol_manager();
+        require(
+            delay == 0 || window > 0,
+            "StabilityPool: Withdrawal window must be greater than 0",
+        );
+        storage.withdrawal_delay.write(delay);
+        storage.withdrawal_window.write(window);
+        log(WithdrawalCooldownUpdatedEvent { delay, window });
+    }
+    #[storage(read)]
+    fn get_withdrawal_cooldown() -> (u64, u64) {
+        return (storage.withdrawal_delay.read(), storage.withdrawal_window.read());
+    }
+    #[storage(read)]
+    fn get_withdrawal_request(depositor: Identity) -> Option<WithdrawalRequest> {
+        return storage.withdrawal_requests.get(depositor).try_read();
+    }
+    /*
+    * Exempts a depositor from the two-step withdrawal, for contracts such as the Stability Pool vault
+    * that withdraw on behalf of their own holders. Only called by the ProtocolManager.
+    */
+    #[storage(read, write)]
+    fn set_withdrawal_cooldown_exempt(depositor: Identity, exempt: bool) {
+        require_is_protocol_manager();
+        storage.withdrawal_cooldown_exempt.insert(depositor, exempt);
+        log(WithdrawalCooldownExemptionEvent {
+            depositor,
+            exempt,
+        });
+    }
+    #[storage(read)]
+    fn is_withdrawal_cooldown_exempt(depositor: Identity) -> bool {
+        return storage.withdrawal_cooldown_exempt.get(depositor).try_read().unwrap_or(false);
+    }
+    /*
+    * - Triggers a FPT issuance, based on time passed since the last issuance. The FPT issuance is shared between *all* depositors and front ends
+    * - Sends all depositor's accumulated gains (FPT, Asset1, Asset2...) to depositor
+    * - Sends the tagged front end's accumulated FPT gains to the tagged front end
+    * - Keeps the compounded deposit in the pool, and takes new snapshots for the deposit and tagged front end stake.
+    */
+    #[storage(read, write)]
+    fn claim_gains() {
+        require(
+            storage
+                .lock_claim_gains
+                .read() == false,
+            "StabilityPool: Claim is locked",
+        );
+        storage.lock_claim_gains.write(true);
+        let depositor = msg_sender().unwrap();
+        let initial_deposit = storage.deposits.get(depositor).try_read().unwrap_or(0);
+        require_user_has_initial_deposit(initial_deposit);
+        internal_trigger_fpt_issuance();
+        let compounded_usdf_deposit = internal_get_compounded_usdf_deposit(depositor);
+        let front_end = storage.deposit_front_end_tags.get(depositor).try_read();
+        internal_pay_out_asset_gains(depositor, None);
+        internal_pay_out_fpt_gains(depositor, front_end);
+        internal_update_front_end_stake(front_end, depositor_pool(depositor), 0, true);
+        internal_update_deposits_and_snapshots(depositor, compounded_usdf_deposit);
+        log(ClaimGainsEvent {
+            user: depositor,
+            initial_amount: initial_deposit,
+            compounded_amount: compounded_usdf_deposit,
+        });
+        storage.lock_claim_gains.write(false);
+    }
+    /*
+    * Same as claim_gains, except the gain of the given asset is added to the depositor's trove of that asset
+    * through BorrowOperations, re-inserting the trove in SortedTroves with the given hints.
+    * The depositor must have an active trove for the asset and a non zero gain of it.
+    */
+    #[storage(read, write)]
+    fn claim_gains_to_trove(asset_contract: AssetId, upper_hint: Identity, lower_hint: Identity) {
+        require(
+            storage
+                .lock_claim_gains
+                .read() == false,
+            "StabilityPool: Claim is locked",
+        );
+        storage.lock_claim_gains.write(true);
+        let depositor = msg_sender().unwrap();
+        let initial_deposit = storage.deposits.get(depositor).try_read().unwrap_or(0);
+        require_user_has_initial_deposit(initial_deposit);
+        let asset_contracts = storage.asset_contracts.get(asset_contract).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "StabilityPool: Asset is not supported",
+        );
+        require_user_has_trove(depositor, asset_contracts.unwrap().trove_manager);
+        internal_trigger_fpt_issuance();
+        let compounded_usdf_deposit = internal_get_compounded_usdf_deposit(depositor);
+        let asset_gain = internal_get_depositor_asset_gain(depositor, asset_contract);
+        require(asset_gain > 0, "StabilityPool: No asset gain to move to trove");
+        let front_end = storage.deposit_front_end_tags.get(depositor).try_read();
+        internal_pay_out_asset_gains(depositor, Some(asset_contract)); // pay out the other asset gains
+        internal_pay_out_fpt_gains(depositor, front_end);
+        internal_update_front_end_stake(front_end, depositor_pool(depositor), 0, true);
+        internal_update_deposits_and_snapshots(depositor, compounded_usdf_deposit);
+        storage
+            .asset
+            .insert(
+                asset_contract,
+                storage
+                    .asset
+                    .get(asset_contract)
+                    .read() - asset_gain,
+            );
+        let borrow_operations = abi(BorrowOperations, storage.borrow_operations_contract.read().bits());
+        borrow_operations
+            .move_asset_gain_to_trove {
+                asset_id: asset_contract.bits(),
+                coins: asset_gain,
+            }(depositor, upper_hint, lower_hint);
+        log(ClaimGainsToTroveEvent {
+            user: depositor,
+            asset_id: asset_contract,
+            asset_gain,
+            compounded_amount: compounded_usdf_deposit,
+        });
+        storage.lock_claim_gains.write(false);
+    }
+    /*
     * Cancels out the specified debt against the USDF contained in the Stability Pool (as far as possible)
     * and transfers the Trove's asset collateral from ActivePool to StabilityPool.
     * Only called by liquidation functions in the TroveManager.
@@ -252,18 +498,71 @@ impl StabilityPool for Contract {
         );
         storage.lock_offset.write(true);
         require_caller_is_trove_manager();
-        let total_usdf = storage.total_usdf_deposits.read();
+        let total_usdf = internal_get_total_usdf_deposits_for_asset(asset_contract);
         if total_usdf == 0 || debt_to_offset == 0 {
             storage.lock_offset.write(false);
             return;
         }
         internal_trigger_fpt_issuance();
-        let per_unit_staked_changes = compute_rewards_per_unit_staked(coll_to_offset, debt_to_offset, total_usdf, asset_contract);
-        update_reward_sum_and_product(
-            per_unit_staked_changes.0,
-            per_unit_staked_changes.1,
-            asset_contract,
-        );
+        // Sub-pools covering the asset absorb the debt first, pro rata to their size, the shared pool takes the rest
+        let mut pool_ids = storage.asset_sub_pools.get(asset_contract).load_vec();
+        let mut pool_totals: Vec<u64> = Vec::new();
+        let mut total_sub_pool_usdf = 0;
+        let mut i = 0;
+        while i < pool_ids.len() {
+            let pool_total = pool_total_usdf_deposits(pool_ids.get(i).unwrap());
+            pool_totals.push(pool_total);
+            total_sub_pool_usdf += pool_total;
+            i += 1;
+        }
+        let debt_to_sub_pools = fm_min(debt_to_offset, total_sub_pool_usdf);
+        let mut debt_shares: Vec<u64> = Vec::new();
+        let mut debt_left = debt_to_sub_pools;
+        i = 0;
+        while i < pool_ids.len() {
+            let debt_share = if total_sub_pool_usdf == 0 {
+                0
+            } else {
+                (U128::from(debt_to_sub_pools) * U128::from(pool_totals.get(i).unwrap()) / U128::from(total_sub_pool_usdf)).as_u64().unwrap()
+            };
+            debt_shares.push(debt_share);
+            debt_left -= debt_share;
+            i += 1;
+        }
+        // Rounding remainder tops up the sub-pools in order, as far as their deposits allow
+        i = 0;
+        while i < pool_ids.len() && debt_left > 0 {
+            let top_up = fm_min(pool_totals.get(i).unwrap() - debt_shares.get(i).unwrap(), debt_left);
+            debt_shares.set(i, debt_shares.get(i).unwrap() + top_up);
+            debt_left -= top_up;
+            i += 1;
+        }
+        pool_ids.push(SHARED_POOL_ID);
+        debt_shares.push(debt_to_offset - debt_to_sub_pools);
+        // Collateral follows the debt, the last pool absorbing debt takes the rounding remainder
+        let mut last_index = 0;
+        i = 0;
+        while i < pool_ids.len() {
+            if debt_shares.get(i).unwrap() > 0 {
+                last_index = i;
+            }
+            i += 1;
+        }
+        let mut coll_left = coll_to_offset;
+        i = 0;
+        while i <= last_index {
+            let debt_share = debt_shares.get(i).unwrap();
+            if debt_share > 0 {
+                let coll_share = if i == last_index {
+                    coll_left
+                } else {
+                    (U128::from(coll_to_offset) * U128::from(debt_share) / U128::from(debt_to_offset)).as_u64().unwrap()
+                };
+                internal_offset_pool(pool_ids.get(i).unwrap(), debt_share, coll_share, asset_contract);
+                coll_left -= coll_share;
+            }
+            i += 1;
+        }
         internal_move_offset_coll_and_debt(coll_to_offset, debt_to_offset, asset_contract);
         log(StabilityPoolLiquidationEvent {
             asset_id: asset_contract,
@@ -272,13 +571,85 @@ impl StabilityPool for Contract {
         });
         storage.lock_offset.write(false);
     }
+    /*
+    * Pays USDF back into the deposits that can offset the asset's debt, pro rata to each pool's size.
+    * Used by the TroveManager to return a liquidation shortfall the pools absorbed once the reserve can cover it.
+    */
+    #[storage(read, write), payable]
+    fn restore_deposits(asset_contract: AssetId) {
+        require_caller_is_trove_manager();
+        require_usdf_is_valid_and_non_zero();
+        let total_usdf = internal_get_total_usdf_deposits_for_asset(asset_contract);
+        require(
+            total_usdf > 0,
+            "StabilityPool: No deposits to restore",
+        );
+        internal_trigger_fpt_issuance();
+        let mut pool_ids = storage.asset_sub_pools.get(asset_contract).load_vec();
+        pool_ids.push(SHARED_POOL_ID);
+        // The last pool with deposits takes the rounding remainder
+        let mut last_index = 0;
+        let mut i = 0;
+        while i < pool_ids.len() {
+            if pool_total_usdf_deposits(pool_ids.get(i).unwrap()) > 0 {
+                last_index = i;
+            }
+            i += 1;
+        }
+        let amount = msg_amount();
+        let mut amount_left = amount;
+        i = 0;
+        while i <= last_index {
+            let pool_id = pool_ids.get(i).unwrap();
+            let pool_total = pool_total_usdf_deposits(pool_id);
+            if pool_total > 0 {
+                let pool_amount = if i == last_index {
+                    amount_left
+                } else {
+                    (U128::from(amount) * U128::from(pool_total) / U128::from(total_usdf)).as_u64().unwrap()
+                };
+                internal_restore_pool(pool_id, pool_amount);
+                amount_left -= pool_amount;
+            }
+            i += 1;
+        }
+        log(DepositsRestoredEvent {
+            asset_id: asset_contract,
+            amount,
+        });
+    }
     #[storage(read)]
     fn get_asset(asset_contract: AssetId) -> u64 {
         return storage.asset.get(asset_contract).try_read().unwrap_or(0);
     }
+    // Assets registered in the Stability Pool, in order of registration
+    #[storage(read)]
+    fn get_assets() -> Vec<AssetId> {
+        let mut assets: Vec<AssetId> = Vec::new();
+        let mut i = 0;
+        while i < storage.valid_assets.len() {
+            assets.push(storage.valid_assets.get(i).unwrap().read());
+            i += 1;
+        }
+        return assets;
+    }
+    #[storage(read)]
+    fn get_asset_oracle(asset_contract: AssetId) -> ContractId {
+        let asset_contracts = storage.asset_contracts.get(asset_contract).try_read();
+        require(
+            asset_contracts
+                .is_some(),
+            "StabilityPool: Asset is not supported",
+        );
+        return asset_contracts.unwrap().oracle;
+    }
     #[storage(read)]
     fn get_total_usdf_deposits() -> u64 {
-        return storage.total_usdf_deposits.try_read().unwrap_or(0);
+        return internal_get_total_usdf_deposits();
+    }
+    #[storage(read)]
+    fn get_total_usdf_deposits_for_asset(asset_contract: AssetId) -> u64 {
+        return internal_get_total_usdf_deposits_for_asset(asset_contract);
     }
     #[storage(read)]
     fn get_depositor_asset_gain(depositor: Identity, asset_contract: AssetId) -> u64 {
@@ -292,13 +663,179 @@ impl StabilityPool for Contract {
     fn get_depositor_fpt_gain(depositor: Identity) -> u64 {
         return internal_get_depositor_fpt_gain(depositor);
     }
+    /*
+    * Registers the caller as a front end with a kickback rate, the share of FPT gains passed on to tagged depositors.
+    * The kickback rate is fixed once registered and the caller cannot have a deposit.
+    */
+    #[storage(read, write)]
+    fn register_front_end(kickback_rate: u64) {
+        let front_end = msg_sender().unwrap();
+        require_front_end_not_registered(front_end);
+        require_user_has_no_deposit(front_end);
+        require(
+            kickback_rate <= DECIMAL_PRECISION,
+            "StabilityPool: Kickback rate must be in range [0,1]",
+        );
+        storage.front_ends.insert(
+            front_end,
+            FrontEnd {
+                kickback_rate,
+                is_registered: true,
+            },
+        );
+        log(FrontEndRegisteredEvent {
+            front_end,
+            kickback_rate,
+        });
+    }
+    #[storage(read)]
+    fn get_front_end(front_end: Identity) -> Option<FrontEnd> {
+        return storage.front_ends.get(front_end).try_read();
+    }
+    #[storage(read)]
+    fn get_depositor_front_end(depositor: Identity) -> Option<Identity> {
+        return storage.deposit_front_end_tags.get(depositor).try_read();
+    }
+    #[storage(read)]
+    fn get_compounded_front_end_stake(front_end: Identity) -> u64 {
+        let mut stake = 0;
+        let mut pool_id = SHARED_POOL_ID;
+        while pool_id <= storage.sub_pool_count.read() {
+            stake += internal_get_compounded_front_end_stake(front_end, pool_id);
+            pool_id += 1;
+        }
+        return stake;
+    }
+    #[storage(read)]
+    fn get_front_end_fpt_gain(front_end: Identity) -> u64 {
+        let mut gain = 0;
+        let mut pool_id = SHARED_POOL_ID;
+        while pool_id <= storage.sub_pool_count.read() {
+            gain += internal_get_front_end_fpt_gain(front_end, pool_id);
+            pool_id += 1;
+        }
+        return gain;
+    }
+    /*
+    * Creates an isolated sub-pool covering the given assets, which must be supported, sorted and unique.
+    * Only called by the ProtocolManager.
+    */
+    #[storage(read, write)]
+    fn create_sub_pool(assets: Vec<AssetId>) {
+        require_is_protocol_manager();
+        require(assets.len() > 0, "StabilityPool: Sub-pool must cover an asset");
+        let mut i = 0;
+        while i < assets.len() {
+            let asset = assets.get(i).unwrap();
+            require(
+                storage
+                    .asset_contracts
+                    .get(asset)
+                    .try_read()
+                    .is_some(),
+                "StabilityPool: Asset is not supported",
+            );
+            require(
+                i == 0 || assets.get(i - 1).unwrap().bits() < asset.bits(),
+                "StabilityPool: Assets must be sorted and unique",
+            );
+            i += 1;
+        }
+        let key = get_sub_pool_key(assets);
+        require(
+            storage
+                .sub_pool_keys
+                .get(key)
+                .try_read()
+                .is_none(),
+            "StabilityPool: Sub-pool already exists",
+        );
+        let pool_id = storage.sub_pool_count.read() + 1;
+        storage.sub_pool_count.write(pool_id);
+        storage.sub_pool_keys.insert(key, pool_id);
+        storage.sub_pool_p.insert(pool_id, U128::from(DECIMAL_PRECISION));
+        i = 0;
+        while i < assets.len() {
+            let asset = assets.get(i).unwrap();
+            storage.sub_pool_assets.get(pool_id).push(asset);
+            storage.asset_sub_pools.get(asset).push(pool_id);
+            i += 1;
+        }
+        log(SubPoolCreatedEvent { pool_id, assets });
+    }
+    #[storage(read)]
+    fn get_sub_pool_count() -> u64 {
+        return storage.sub_pool_count.read();
+    }
+    #[storage(read)]
+    fn get_sub_pool_id(assets: Vec<AssetId>) -> Option<u64> {
+        return storage.sub_pool_keys.get(get_sub_pool_key(assets)).try_read();
+    }
+    #[storage(read)]
+    fn get_sub_pool_assets(pool_id: u64) -> Vec<AssetId> {
+        return storage.sub_pool_assets.get(pool_id).load_vec();
+    }
+    #[storage(read)]
+    fn get_sub_pools_for_asset(asset_contract: AssetId) -> Vec<u64> {
+        return storage.asset_sub_pools.get(asset_contract).load_vec();
+    }
+    #[storage(read)]
+    fn get_depositor_pool(depositor: Identity) -> u64 {
+        return depositor_pool(depositor);
+    }
+    #[storage(read)]
+    fn get_pool_total_usdf_deposits(pool_id: u64) -> u64 {
+        return pool_total_usdf_deposits(pool_id);
+    }
+    #[storage(read)]
+    fn get_pool_asset_gain(pool_id: u64, asset_contract: AssetId) -> u64 {
+        return storage.pool_asset_gains.get((pool_id, asset_contract)).try_read().unwrap_or(0);
+    }
 }
 // --- Internal functions ---
+#[storage(read, write), payable]
+fn internal_provide_to_pool(depositor: Identity, pool_id: u64, front_end_tag: Option<Identity>) {
+    require(
+        storage
+            .lock_provide_to_stability_pool
+            .read() == false,
+        "StabilityPool: Contract is locked",
+    );
+    storage.lock_provide_to_stability_pool.write(true);
+    require_front_end_is_registered_or_none(front_end_tag);
+    require_front_end_not_registered(depositor);
+    require_usdf_is_valid_and_non_zero();
+    let initial_deposit = storage.deposits.get(depositor).try_read().unwrap_or(0);
+    internal_trigger_fpt_issuance();
+    if initial_deposit == 0 {
+        internal_set_front_end_tag(depositor, front_end_tag);
+        internal_set_deposit_pool(depositor, pool_id);
+    }
+    let compounded_usdf_deposit = internal_get_compounded_usdf_deposit(depositor);
+    let front_end = storage.deposit_front_end_tags.get(depositor).try_read();
+    internal_pay_out_asset_gains(depositor, None); // pay out asset gains
+    internal_pay_out_fpt_gains(depositor, front_end);
+    internal_update_front_end_stake(front_end, pool_id, msg_amount(), true);
+    let new_position = compounded_usdf_deposit + msg_amount();
+    internal_update_deposits_and_snapshots(depositor, new_position);
+    set_pool_total_usdf_deposits(pool_id, pool_total_usdf_deposits(pool_id) + msg_amount());
+    log(ProvideToStabilityPoolEvent {
+        user: depositor,
+        amount_to_deposit: msg_amount(),
+        initial_amount: initial_deposit,
+        compounded_amount: compounded_usdf_deposit,
+    });
+    storage.lock_provide_to_stability_pool.write(false);
+}
 #[storage(read, write)]
-fn internal_pay_out_asset_gains(depositor: Identity) {
+fn internal_pay_out_asset_gains(depositor: Identity, skipped_asset: Option<AssetId>) {
     let mut i = 0;
     while i < storage.valid_assets.len() {
         let asset_contract = storage.valid_assets.get(i).unwrap().read();
+        if skipped_asset == Some(asset_contract) {
+            i += 1;
+            continue;
+        }
         let asset_gain = internal_get_depositor_asset_gain(depositor, asset_contract);
         send_asset_gain_to_depositor(depositor, asset_gain, asset_contract);
         i += 1;
@@ -310,36 +847,94 @@ fn internal_trigger_fpt_issuance() {
     let fpt_issuance = community_issuance_contract.issue_fpt();
     internal_update_g(fpt_issuance);
 }
+#[storage(read)]
+fn internal_get_current_time() -> u64 {
+    let community_issuance_contract = abi(CommunityIssuance, storage.community_issuance_contract.read().bits());
+    community_issuance_contract.get_current_time()
+}
+// Caps the withdrawal by the depositor's unlocked request and consumes it, no-op when the cooldown is disabled
+// or the depositor is exempt
 #[storage(read, write)]
-fn internal_update_g(fpt_issuance: u64) {
-    if (storage.total_usdf_deposits.read() == 0
-        || fpt_issuance == 0)
+fn internal_consume_withdrawal_request(depositor: Identity, amount: u64) -> u64 {
+    if storage.withdrawal_delay.read() == 0
+        || storage.withdrawal_cooldown_exempt.get(depositor).try_read().unwrap_or(false)
     {
+        return amount;
+    }
+    let request = storage.withdrawal_requests.get(depositor).try_read();
+    require(request.is_some(), "StabilityPool: No withdrawal request");
+    let request = request.unwrap();
+    let current_time = internal_get_current_time();
+    require(
+        current_time >= request.unlock_time,
+        "StabilityPool: Withdrawal request is still in cooldown",
+    );
+    require(
+        current_time <= request.unlock_time + storage.withdrawal_window.read(),
+        "StabilityPool: Withdrawal request has expired",
+    );
+    let _ = storage.withdrawal_requests.remove(depositor);
+    fm_min(amount, request.amount)
+}
+// Splits the FPT issuance between the pools pro rata to their deposits
+#[storage(read, write)]
+fn internal_update_g(fpt_issuance: u64) {
+    let total_usdf_deposits = internal_get_total_usdf_deposits();
+    if (total_usdf_deposits == 0 || fpt_issuance == 0) {
         return;
     }
-    let fpt_per_unit_staked = internal_compute_fpt_per_unit_staked(fpt_issuance, storage.total_usdf_deposits.read());
-    let marginal_fpt_gain = U128::from(fpt_per_unit_staked) * storage.p.read();
-    let current_epoch = storage.current_epoch.read();
-    let current_scale = storage.current_scale.read();
-    let new_epoch_to_scale_to_gain = storage.epoch_to_scale_to_gain.get((current_epoch, current_scale)).try_read().unwrap_or(U128::zero()) + marginal_fpt_gain;
-    storage
-        .epoch_to_scale_to_gain
-        .insert((current_epoch, current_scale), new_epoch_to_scale_to_gain);
+    let mut pool_id = SHARED_POOL_ID;
+    while pool_id <= storage.sub_pool_count.read() {
+        let pool_total = pool_total_usdf_deposits(pool_id);
+        if pool_total > 0 {
+            let pool_issuance = (U128::from(fpt_issuance) * U128::from(pool_total) / U128::from(total_usdf_deposits)).as_u64().unwrap();
+            internal_update_pool_g(pool_id, pool_issuance, pool_total);
+        }
+        pool_id += 1;
+    }
 }
 #[storage(read, write)]
-fn internal_compute_fpt_per_unit_staked(fpt_issuance: u64, total_usdf_deposits: u64) -> u64 {
-    let fpt_numerator = U128::from(fpt_issuance) * U128::from(DECIMAL_PRECISION) + storage.last_fpt_error.read();
+fn internal_update_pool_g(pool_id: u64, fpt_issuance: u64, pool_total: u64) {
+    if fpt_issuance == 0 {
+        return;
+    }
+    let fpt_per_unit_staked = internal_compute_fpt_per_unit_staked(pool_id, fpt_issuance, pool_total);
+    let marginal_fpt_gain = U128::from(fpt_per_unit_staked) * pool_p(pool_id);
+    let current_epoch = pool_current_epoch(pool_id);
+    let current_scale = pool_current_scale(pool_id);
+    let new_epoch_to_scale_to_gain = pool_gain(pool_id, current_epoch, current_scale) + marginal_fpt_gain;
+    set_pool_gain(pool_id, current_epoch, current_scale, new_epoch_to_scale_to_gain);
+}
+#[storage(read, write)]
+fn internal_compute_fpt_per_unit_staked(
+    pool_id: u64,
+    fpt_issuance: u64,
+    total_usdf_deposits: u64,
+) -> u64 {
+    let fpt_numerator = U128::from(fpt_issuance) * U128::from(DECIMAL_PRECISION) + pool_last_fpt_error(pool_id);
     let fpt_per_unit_staked = fpt_numerator / U128::from(total_usdf_deposits);
-    storage
-        .last_fpt_error
-        .write(fpt_numerator - (fpt_per_unit_staked * U128::from(total_usdf_deposits)));
+    set_pool_last_fpt_error(
+        pool_id,
+        fpt_numerator - (fpt_per_unit_staked * U128::from(total_usdf_deposits)),
+    );
     fpt_per_unit_staked.as_u64().unwrap()
 }
 #[storage(read)]
-fn internal_pay_out_fpt_gains(depositor: Identity) {
+fn internal_pay_out_fpt_gains(depositor: Identity, front_end: Option<Identity>) {
+    let community_issuance_contract = abi(CommunityIssuance, storage.community_issuance_contract.read().bits());
+    // Pay out front end's FPT gain in the depositor's pool
+    match front_end {
+        Some(front_end) => {
+            let front_end_fpt_gain = internal_get_front_end_fpt_gain(front_end, depositor_pool(depositor));
+            if (front_end_fpt_gain > 0) {
+                community_issuance_contract.send_fpt(front_end, front_end_fpt_gain);
+            }
+        },
+        None => {},
+    }
+    // Pay out depositor's FPT gain
     let depositor_fpt_gain = internal_get_depositor_fpt_gain(depositor);
     if (depositor_fpt_gain > 0) {
-        let community_issuance_contract = abi(CommunityIssuance, storage.community_issuance_contract.read().bits());
         community_issuance_contract.send_fpt(depositor, depositor_fpt_gain);
     }
 }
@@ -349,18 +944,34 @@ fn internal_get_depositor_fpt_gain(depositor: Identity) -> u64 {
     if (initial_deposit == 0) {
         return 0;
     }
+    // Untagged deposits keep the whole gain
+    let kickback_rate = match storage.deposit_front_end_tags.get(depositor).try_read() {
+        Some(front_end) => storage.front_ends.get(front_end).read().kickback_rate,
+        None => DECIMAL_PRECISION,
+    };
     let snapshots = storage.deposit_snapshots.get(depositor).try_read().unwrap_or(Snapshots::default());
-    let fpt_gain = internal_get_fpt_gain_from_snapshots(initial_deposit, snapshots);
-    fpt_gain
+    let fpt_gain = internal_get_fpt_gain_from_snapshots(depositor_pool(depositor), initial_deposit, snapshots);
+    (U128::from(kickback_rate) * U128::from(fpt_gain) / U128::from(DECIMAL_PRECISION)).as_u64().unwrap()
+}
+#[storage(read)]
+fn internal_get_front_end_fpt_gain(front_end: Identity, pool_id: u64) -> u64 {
+    let front_end_stake = pool_front_end_stake(front_end, pool_id);
+    if (front_end_stake == 0) {
+        return 0;
+    }
+    let front_end_share = DECIMAL_PRECISION - storage.front_ends.get(front_end).read().kickback_rate;
+    let snapshots = pool_front_end_snapshots(front_end, pool_id).unwrap_or(Snapshots::default());
+    let fpt_gain = internal_get_fpt_gain_from_snapshots(pool_id, front_end_stake, snapshots);
+    (U128::from(front_end_share) * U128::from(fpt_gain) / U128::from(DECIMAL_PRECISION)).as_u64().unwrap()
 }
 #[storage(read)]
-fn internal_get_fpt_gain_from_snapshots(initial_stake: u64, snapshots: Snapshots) -> u64 {
+fn internal_get_fpt_gain_from_snapshots(pool_id: u64, initial_stake: u64, snapshots: Snapshots) -> u64 {
     let epoch_snapshot = snapshots.epoch;
     let scale_snapshot = snapshots.scale;
     let g_snapshot = snapshots.G;
     let p_snapshot = snapshots.P;
-    let first_portion = storage.epoch_to_scale_to_gain.get((epoch_snapshot, scale_snapshot)).try_read().unwrap_or(U128::zero()) - g_snapshot;
-    let second_portion = storage.epoch_to_scale_to_gain.get((epoch_snapshot, scale_snapshot + 1)).try_read().unwrap_or(U128::zero()) / U128::from(SCALE_FACTOR);
+    let first_portion = pool_gain(pool_id, epoch_snapshot, scale_snapshot) - g_snapshot;
+    let second_portion = pool_gain(pool_id, epoch_snapshot, scale_snapshot + 1) / U128::from(SCALE_FACTOR);
     let gain = (U128::from(initial_stake) * (first_portion + second_portion)) / p_snapshot / U128::from(DECIMAL_PRECISION);
     return gain.as_u64().unwrap();
 }
@@ -395,6 +1006,51 @@ fn require_user_has_trove(address: Identity, trove_manager_contract: ContractId)
         "StabilityPool: User does not have an active trove",
     );
 }
+#[storage(read)]
+fn require_front_end_is_registered_or_none(front_end: Option<Identity>) {
+    match front_end {
+        Some(front_end) => {
+            require(
+                storage
+                    .front_ends
+                    .get(front_end)
+                    .try_read()
+                    .is_some(),
+                "StabilityPool: Tag must be a registered front end, or none",
+            );
+        },
+        None => {},
+    }
+}
+#[storage(read)]
+fn require_front_end_not_registered(address: Identity) {
+    require(
+        storage
+            .front_ends
+            .get(address)
+            .try_read()
+            .is_none(),
+        "StabilityPool: Must not already be a registered front end",
+    );
+}
+#[storage(read)]
+fn require_user_has_no_deposit(address: Identity) {
+    require(
+        storage
+            .deposits
+            .get(address)
+            .try_read()
+            .unwrap_or(0) == 0,
+        "StabilityPool: User must have no deposit",
+    );
+}
+#[storage(read)]
+fn require_pool_exists(pool_id: u64) {
+    require(
+        pool_id > SHARED_POOL_ID && pool_id <= storage.sub_pool_count.read(),
+        "StabilityPool: Sub-pool does not exist",
+    );
+}
 // --- Reward calculator functions for depositor and front end ---
 #[storage(read)]
 fn internal_get_depositor_asset_gain(depositor: Identity, asset: AssetId) -> u64 {
@@ -404,10 +1060,17 @@ fn internal_get_depositor_asset_gain(depositor: Identity, asset: AssetId) -> u64
     }
     let s_snapshot = storage.deposit_snapshot_s_per_asset.get((depositor, asset)).try_read().unwrap_or(U128::zero());
     let mut snapshots = storage.deposit_snapshots.get(depositor).try_read().unwrap_or(Snapshots::default());
-    return internal_get_asset_gain_from_snapshots(initial_deposit, snapshots, s_snapshot, asset);
+    return internal_get_asset_gain_from_snapshots(
+        depositor_pool(depositor),
+        initial_deposit,
+        snapshots,
+        s_snapshot,
+        asset,
+    );
 }
 #[storage(read)]
 fn internal_get_asset_gain_from_snapshots(
+    pool_id: u64,
     initial_deposit: u64,
     snapshots: Snapshots,
     s_snapshot: U128,
@@ -416,8 +1079,8 @@ fn internal_get_asset_gain_from_snapshots(
     let epoch_snapshot = snapshots.epoch;
     let scale_snapshot = snapshots.scale;
     let p_snapshot = snapshots.P;
-    let first_portion = storage.epoch_to_scale_to_sum.get((epoch_snapshot, scale_snapshot, asset)).try_read().unwrap_or(U128::zero()) - s_snapshot;
-    let second_portion = storage.epoch_to_scale_to_sum.get((epoch_snapshot, scale_snapshot + 1, asset)).try_read().unwrap_or(U128::zero()) / U128::from(SCALE_FACTOR);
+    let first_portion = pool_sum(pool_id, epoch_snapshot, scale_snapshot, asset) - s_snapshot;
+    let second_portion = pool_sum(pool_id, epoch_snapshot, scale_snapshot + 1, asset) / U128::from(SCALE_FACTOR);
     let gain = (U128::from(initial_deposit) * (first_portion + second_portion)) / p_snapshot / U128::from(DECIMAL_PRECISION);
     return gain.as_u64().unwrap();
 }
@@ -428,22 +1091,31 @@ fn internal_get_compounded_usdf_deposit(depositor: Identity) -> u64 {
         return 0;
     }
     let mut snapshots = storage.deposit_snapshots.get(depositor).read();
-    return get_compounded_stake_from_snapshots(initial_deposit, snapshots)
+    return get_compounded_stake_from_snapshots(depositor_pool(depositor), initial_deposit, snapshots)
 }
 #[storage(read)]
-fn get_compounded_stake_from_snapshots(initial_stake: u64, snapshots: Snapshots) -> u64 {
+fn internal_get_compounded_front_end_stake(front_end: Identity, pool_id: u64) -> u64 {
+    let front_end_stake = pool_front_end_stake(front_end, pool_id);
+    if front_end_stake == 0 {
+        return 0;
+    }
+    let snapshots = pool_front_end_snapshots(front_end, pool_id).unwrap();
+    return get_compounded_stake_from_snapshots(pool_id, front_end_stake, snapshots)
+}
+#[storage(read)]
+fn get_compounded_stake_from_snapshots(pool_id: u64, initial_stake: u64, snapshots: Snapshots) -> u64 {
     let epoch_snapshot = snapshots.epoch;
     let scale_snapshot = snapshots.scale;
     let p_snapshot = snapshots.P;
-    if (epoch_snapshot < storage.current_epoch.read()) {
+    if (epoch_snapshot < pool_current_epoch(pool_id)) {
         return 0;
     }
     let mut compounded_stake: U128 = U128::zero();
-    let scale_diff = storage.current_scale.read() - scale_snapshot;
+    let scale_diff = pool_current_scale(pool_id) - scale_snapshot;
     if (scale_diff == 0) {
-        compounded_stake = U128::from(initial_stake) * storage.p.read() / p_snapshot;
+        compounded_stake = U128::from(initial_stake) * pool_p(pool_id) / p_snapshot;
     } else if (scale_diff == 1) {
-        compounded_stake = U128::from(initial_stake) * storage.p.read() / p_snapshot / U128::from(SCALE_FACTOR);
+        compounded_stake = U128::from(initial_stake) * pool_p(pool_id) / p_snapshot / U128::from(SCALE_FACTOR);
     } else {
         compounded_stake = U128::zero();
     }
@@ -453,11 +1125,34 @@ fn get_compounded_stake_from_snapshots(initial_stake: u64, snapshots: Snapshots)
     }
     return compounded_stake.as_u64().unwrap();
 }
+#[storage(read)]
+fn internal_get_total_usdf_deposits() -> u64 {
+    let mut total = 0;
+    let mut pool_id = SHARED_POOL_ID;
+    while pool_id <= storage.sub_pool_count.read() {
+        total += pool_total_usdf_deposits(pool_id);
+        pool_id += 1;
+    }
+    total
+}
+// USDF available to offset the debt of the asset: the shared pool and the sub-pools covering the asset
+#[storage(read)]
+fn internal_get_total_usdf_deposits_for_asset(asset_contract: AssetId) -> u64 {
+    let mut total = pool_total_usdf_deposits(SHARED_POOL_ID);
+    let sub_pools = storage.asset_sub_pools.get(asset_contract);
+    let mut i = 0;
+    while i < sub_pools.len() {
+        total += pool_total_usdf_deposits(sub_pools.get(i).unwrap().read());
+        i += 1;
+    }
+    total
+}
 #[storage(read, write)]
-fn internal_decrease_usdf(total_usdf_to_decrease: u64) {
-    storage
-        .total_usdf_deposits
-        .write(storage.total_usdf_deposits.read() - total_usdf_to_decrease);
+fn internal_decrease_usdf(pool_id: u64, total_usdf_to_decrease: u64) {
+    set_pool_total_usdf_deposits(
+        pool_id,
+        pool_total_usdf_deposits(pool_id) - total_usdf_to_decrease,
+    );
 }
 #[storage(read, write)]
 fn internal_increase_asset(total_asset_to_increase: u64, asset_contract: AssetId) {
@@ -467,14 +1162,17 @@ fn internal_increase_asset(total_asset_to_increase: u64, asset_contract: AssetId
 }
 #[storage(read, write)]
 fn internal_update_deposits_and_snapshots(depositor: Identity, amount: u64) {
+    let pool_id = depositor_pool(depositor);
     storage.deposits.insert(depositor, amount);
     if (amount == 0) {
         let _ = storage.deposit_snapshots.remove(depositor);
+        let _ = storage.deposit_front_end_tags.remove(depositor);
+        let _ = storage.deposit_pools.remove(depositor);
     }
-    let current_epoch = storage.current_epoch.read();
-    let current_scale = storage.current_scale.read();
-    let current_p = storage.p.read();
-    let current_g = storage.epoch_to_scale_to_gain.get((current_epoch, current_scale)).try_read().unwrap_or(U128::zero());
+    let current_epoch = pool_current_epoch(pool_id);
+    let current_scale = pool_current_scale(pool_id);
+    let current_p = pool_p(pool_id);
+    let current_g = pool_gain(pool_id, current_epoch, current_scale);
     let snapshots = Snapshots {
         epoch: current_epoch,
         scale: current_scale,
@@ -484,7 +1182,7 @@ fn internal_update_deposits_and_snapshots(depositor: Identity, amount: u64) {
     let mut i = 0;
     while i < storage.valid_assets.len() {
         let asset = storage.valid_assets.get(i).unwrap().read();
-        let current_s: U128 = storage.epoch_to_scale_to_sum.get((current_epoch, current_scale, asset)).try_read().unwrap_or(U128::zero());
+        let current_s: U128 = pool_sum(pool_id, current_epoch, current_scale, asset);
         storage
             .deposit_snapshot_s_per_asset
             .insert((depositor, asset), current_s);
@@ -492,6 +1190,69 @@ fn internal_update_deposits_and_snapshots(depositor: Identity, amount: u64) {
     }
     storage.deposit_snapshots.insert(depositor, snapshots);
 }
+#[storage(read, write)]
+fn internal_set_front_end_tag(depositor: Identity, front_end: Option<Identity>) {
+    match front_end {
+        Some(front_end) => {
+            storage.deposit_front_end_tags.insert(depositor, front_end);
+            log(FrontEndTagSetEvent {
+                depositor,
+                front_end,
+            });
+        },
+        None => {
+            let _ = storage.deposit_front_end_tags.remove(depositor);
+        },
+    }
+}
+#[storage(read, write)]
+fn internal_set_deposit_pool(depositor: Identity, pool_id: u64) {
+    if pool_id == SHARED_POOL_ID {
+        let _ = storage.deposit_pools.remove(depositor);
+    } else {
+        storage.deposit_pools.insert(depositor, pool_id);
+    }
+}
+// Compounds the front end stake and applies the deposit change, must be called after the front end's FPT gain is paid out
+#[storage(read, write)]
+fn internal_update_front_end_stake(
+    front_end: Option<Identity>,
+    pool_id: u64,
+    amount: u64,
+    is_increase: bool,
+) {
+    if front_end.is_none() {
+        return;
+    }
+    let front_end = front_end.unwrap();
+    let compounded_front_end_stake = internal_get_compounded_front_end_stake(front_end, pool_id);
+    let new_front_end_stake = if is_increase {
+        compounded_front_end_stake + amount
+    } else {
+        compounded_front_end_stake - fm_min(amount, compounded_front_end_stake)
+    };
+    set_pool_front_end_stake(front_end, pool_id, new_front_end_stake);
+    if (new_front_end_stake == 0) {
+        remove_pool_front_end_snapshots(front_end, pool_id);
+    } else {
+        let current_epoch = pool_current_epoch(pool_id);
+        let current_scale = pool_current_scale(pool_id);
+        set_pool_front_end_snapshots(
+            front_end,
+            pool_id,
+            Snapshots {
+                epoch: current_epoch,
+                scale: current_scale,
+                P: pool_p(pool_id),
+                G: pool_gain(pool_id, current_epoch, current_scale),
+            },
+        );
+    }
+    log(FrontEndStakeChangedEvent {
+        front_end,
+        new_stake: new_front_end_stake,
+    });
+}
 #[storage(read, write), payable]
 fn send_asset_gain_to_depositor(depositor: Identity, gain: u64, asset_contract: AssetId) {
     if (gain == 0) {
@@ -503,13 +1264,11 @@ fn send_asset_gain_to_depositor(depositor: Identity, gain: u64, asset_contract:
     transfer(depositor, asset_contract, gain);
 }
 #[storage(read, write)]
-fn send_usdf_to_depositor(depositor: Identity, amount: u64) {
+fn send_usdf_to_depositor(depositor: Identity, pool_id: u64, amount: u64) {
     if (amount == 0) {
         return;
     }
-    storage
-        .total_usdf_deposits
-        .write(storage.total_usdf_deposits.read() - amount);
+    internal_decrease_usdf(pool_id, amount);
     let usdf_asset_id = storage.usdf_asset_id.read();
     transfer(depositor, usdf_asset_id, amount);
 }
@@ -548,14 +1307,67 @@ fn require_no_undercollateralized_troves() {
 fn require_user_has_initial_deposit(deposit: u64) {
     require(deposit > 0, "StabilityPool: User has no initial deposit");
 }
+// Offsets the pool's share of a liquidation against its deposits and records its collateral gain
+#[storage(read, write)]
+fn internal_offset_pool(
+    pool_id: u64,
+    debt_to_offset: u64,
+    coll_to_offset: u64,
+    asset_contract: AssetId,
+) {
+    let per_unit_staked_changes = compute_rewards_per_unit_staked(
+        pool_id,
+        coll_to_offset,
+        debt_to_offset,
+        pool_total_usdf_deposits(pool_id),
+        asset_contract,
+    );
+    update_reward_sum_and_product(
+        pool_id,
+        per_unit_staked_changes.0,
+        per_unit_staked_changes.1,
+        asset_contract,
+    );
+    internal_decrease_usdf(pool_id, debt_to_offset);
+    storage
+        .pool_asset_gains
+        .insert(
+            (pool_id, asset_contract),
+            storage
+                .pool_asset_gains
+                .get((pool_id, asset_contract))
+                .try_read()
+                .unwrap_or(0) + coll_to_offset,
+        );
+    log(PoolOffsetEvent {
+        pool_id,
+        asset_id: asset_contract,
+        debt_to_offset,
+        collateral_to_offset: coll_to_offset,
+    });
+}
+// Grows every deposit of the pool by the same factor, the inverse of an offset loss
+#[storage(read, write)]
+fn internal_restore_pool(pool_id: u64, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    let pool_total = pool_total_usdf_deposits(pool_id);
+    set_pool_p(
+        pool_id,
+        pool_p(pool_id) * U128::from(pool_total + amount) / U128::from(pool_total),
+    );
+    set_pool_total_usdf_deposits(pool_id, pool_total + amount);
+}
 #[storage(read, write)]
 fn compute_rewards_per_unit_staked(
+    pool_id: u64,
     coll_to_add: u64,
     debt_to_offset: u64,
     total_usdf_deposits: u64,
     asset_contract: AssetId,
 ) -> (U128, U128) {
-    let asset_numerator: U128 = U128::from(coll_to_add) * U128::from(DECIMAL_PRECISION) + storage.last_asset_error_offset.get(asset_contract).try_read().unwrap_or(U128::zero());
+    let asset_numerator: U128 = U128::from(coll_to_add) * U128::from(DECIMAL_PRECISION) + pool_last_asset_error_offset(pool_id, asset_contract);
     require(
         debt_to_offset <= total_usdf_deposits,
         "StabilityPool: Debt offset exceeds total USDF deposits",
@@ -563,59 +1375,52 @@ fn compute_rewards_per_unit_staked(
     let mut usdf_loss_per_unit_staked: U128 = U128::zero();
     if (debt_to_offset == total_usdf_deposits) {
         usdf_loss_per_unit_staked = U128::from(DECIMAL_PRECISION);
-        storage.last_usdf_error_offset.write(U128::zero());
+        set_pool_last_usdf_error_offset(pool_id, U128::zero());
     } else {
-        let usdf_loss_per_unit_staked_numerator: U128 = U128::from(debt_to_offset) * U128::from(DECIMAL_PRECISION) - storage.last_usdf_error_offset.read();
+        let usdf_loss_per_unit_staked_numerator: U128 = U128::from(debt_to_offset) * U128::from(DECIMAL_PRECISION) - pool_last_usdf_error_offset(pool_id);
         usdf_loss_per_unit_staked = usdf_loss_per_unit_staked_numerator / U128::from(total_usdf_deposits) + U128::from(1u64);
-        storage
-            .last_usdf_error_offset
-            .write(
-                usdf_loss_per_unit_staked * U128::from(total_usdf_deposits) - usdf_loss_per_unit_staked_numerator,
-            );
+        set_pool_last_usdf_error_offset(
+            pool_id,
+            usdf_loss_per_unit_staked * U128::from(total_usdf_deposits) - usdf_loss_per_unit_staked_numerator,
+        );
     }
     let asset_gain_per_unit_staked = asset_numerator / U128::from(total_usdf_deposits);
-    storage
-        .last_asset_error_offset
-        .insert(
-            asset_contract,
-            asset_numerator - (asset_gain_per_unit_staked * U128::from(total_usdf_deposits)),
-        );
+    set_pool_last_asset_error_offset(
+        pool_id,
+        asset_contract,
+        asset_numerator - (asset_gain_per_unit_staked * U128::from(total_usdf_deposits)),
+    );
     return (asset_gain_per_unit_staked, usdf_loss_per_unit_staked);
 }
 #[storage(read, write)]
 fn update_reward_sum_and_product(
+    pool_id: u64,
     asset_gain_per_unit_staked: U128,
     usdf_loss_per_unit_staked: U128,
     asset: AssetId,
 ) {
-    let current_p = storage.p.read();
+    let current_p = pool_p(pool_id);
     let mut new_p: U128 = U128::zero();
     let new_product_factor = U128::from(DECIMAL_PRECISION) - usdf_loss_per_unit_staked;
-    let current_epoch = storage.current_epoch.read();
-    let current_scale = storage.current_scale.read();
-    let current_s = storage.epoch_to_scale_to_sum.get((current_epoch, current_scale, asset)).try_read().unwrap_or(U128::zero());
+    let current_epoch = pool_current_epoch(pool_id);
+    let current_scale = pool_current_scale(pool_id);
+    let current_s = pool_sum(pool_id, current_epoch, current_scale, asset);
     let marginal_asset_gain: U128 = asset_gain_per_unit_staked * current_p;
     let new_sum = current_s + marginal_asset_gain;
-    storage
-        .epoch_to_scale_to_sum
-        .insert((current_epoch, current_scale, asset), new_sum);
+    set_pool_sum(pool_id, current_epoch, current_scale, asset, new_sum);
     if (new_product_factor == U128::zero()) {
-        storage
-            .current_epoch
-            .write(storage.current_epoch.read() + 1);
-        storage.current_scale.write(0);
+        set_pool_current_epoch(pool_id, current_epoch + 1);
+        set_pool_current_scale(pool_id, 0);
         new_p = U128::from(DECIMAL_PRECISION);
     } else if (current_p * new_product_factor / U128::from(DECIMAL_PRECISION) < U128::from(SCALE_FACTOR))
     {
         new_p = current_p * new_product_factor * U128::from(SCALE_FACTOR) / U128::from(DECIMAL_PRECISION);
-        storage
-            .current_scale
-            .write(storage.current_scale.read() + 1);
+        set_pool_current_scale(pool_id, current_scale + 1);
     } else {
         new_p = current_p * new_product_factor / U128::from(DECIMAL_PRECISION);
     }
     require(new_p > U128::zero(), "StabilityPool: New p is 0");
-    storage.p.write(new_p);
+    set_pool_p(pool_id, new_p);
 }
 #[storage(read, write)]
 fn internal_move_offset_coll_and_debt(
@@ -625,7 +1430,6 @@ fn internal_move_offset_coll_and_debt(
 ) {
     let active_pool = abi(ActivePool, storage.active_pool_contract.read().bits());
     let usdf_contract = abi(SRC3, storage.usdf_contract.read().bits());
-    internal_decrease_usdf(debt_to_offset);
     internal_increase_asset(coll_to_add, asset_contract);
     active_pool.decrease_usdf_debt(debt_to_offset, asset_contract);
     usdf_contract
@@ -639,3 +1443,202 @@ fn internal_move_offset_coll_and_debt(
         asset_contract,
     );
 }
+// --- Sub-pool helpers ---
+// Key of an asset set, the assets are expected sorted so each set has a single key
+fn get_sub_pool_key(assets: Vec<AssetId>) -> b256 {
+    let mut key = b256::zero();
+    let mut i = 0;
+    while i < assets.len() {
+        key = sha256((key, assets.get(i).unwrap()));
+        i += 1;
+    }
+    key
+}
+#[storage(read)]
+fn depositor_pool(depositor: Identity) -> u64 {
+    storage.deposit_pools.get(depositor).try_read().unwrap_or(SHARED_POOL_ID)
+}
+// --- Per pool accounting accessors, the shared pool reads and writes the original storage ---
+#[storage(read)]
+fn pool_total_usdf_deposits(pool_id: u64) -> u64 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.total_usdf_deposits.read();
+    }
+    storage.sub_pool_total_usdf_deposits.get(pool_id).try_read().unwrap_or(0)
+}
+#[storage(read, write)]
+fn set_pool_total_usdf_deposits(pool_id: u64, amount: u64) {
+    if pool_id == SHARED_POOL_ID {
+        storage.total_usdf_deposits.write(amount);
+    } else {
+        storage.sub_pool_total_usdf_deposits.insert(pool_id, amount);
+    }
+}
+#[storage(read)]
+fn pool_p(pool_id: u64) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.p.read();
+    }
+    storage.sub_pool_p.get(pool_id).try_read().unwrap_or(U128::from(DECIMAL_PRECISION))
+}
+#[storage(read, write)]
+fn set_pool_p(pool_id: u64, p: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.p.write(p);
+    } else {
+        storage.sub_pool_p.insert(pool_id, p);
+    }
+}
+#[storage(read)]
+fn pool_current_scale(pool_id: u64) -> u64 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.current_scale.read();
+    }
+    storage.sub_pool_current_scale.get(pool_id).try_read().unwrap_or(0)
+}
+#[storage(read, write)]
+fn set_pool_current_scale(pool_id: u64, scale: u64) {
+    if pool_id == SHARED_POOL_ID {
+        storage.current_scale.write(scale);
+    } else {
+        storage.sub_pool_current_scale.insert(pool_id, scale);
+    }
+}
+#[storage(read)]
+fn pool_current_epoch(pool_id: u64) -> u64 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.current_epoch.read();
+    }
+    storage.sub_pool_current_epoch.get(pool_id).try_read().unwrap_or(0)
+}
+#[storage(read, write)]
+fn set_pool_current_epoch(pool_id: u64, epoch: u64) {
+    if pool_id == SHARED_POOL_ID {
+        storage.current_epoch.write(epoch);
+    } else {
+        storage.sub_pool_current_epoch.insert(pool_id, epoch);
+    }
+}
+#[storage(read)]
+fn pool_sum(pool_id: u64, epoch: u64, scale: u64, asset: AssetId) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.epoch_to_scale_to_sum.get((epoch, scale, asset)).try_read().unwrap_or(U128::zero());
+    }
+    storage.sub_pool_epoch_to_scale_to_sum.get((pool_id, epoch, scale, asset)).try_read().unwrap_or(U128::zero())
+}
+#[storage(read, write)]
+fn set_pool_sum(pool_id: u64, epoch: u64, scale: u64, asset: AssetId, sum: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.epoch_to_scale_to_sum.insert((epoch, scale, asset), sum);
+    } else {
+        storage
+            .sub_pool_epoch_to_scale_to_sum
+            .insert((pool_id, epoch, scale, asset), sum);
+    }
+}
+#[storage(read)]
+fn pool_gain(pool_id: u64, epoch: u64, scale: u64) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.epoch_to_scale_to_gain.get((epoch, scale)).try_read().unwrap_or(U128::zero());
+    }
+    storage.sub_pool_epoch_to_scale_to_gain.get((pool_id, epoch, scale)).try_read().unwrap_or(U128::zero())
+}
+#[storage(read, write)]
+fn set_pool_gain(pool_id: u64, epoch: u64, scale: u64, gain: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.epoch_to_scale_to_gain.insert((epoch, scale), gain);
+    } else {
+        storage
+            .sub_pool_epoch_to_scale_to_gain
+            .insert((pool_id, epoch, scale), gain);
+    }
+}
+#[storage(read)]
+fn pool_last_fpt_error(pool_id: u64) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.last_fpt_error.read();
+    }
+    storage.sub_pool_last_fpt_error.get(pool_id).try_read().unwrap_or(U128::zero())
+}
+#[storage(read, write)]
+fn set_pool_last_fpt_error(pool_id: u64, error: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.last_fpt_error.write(error);
+    } else {
+        storage.sub_pool_last_fpt_error.insert(pool_id, error);
+    }
+}
+#[storage(read)]
+fn pool_last_asset_error_offset(pool_id: u64, asset: AssetId) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.last_asset_error_offset.get(asset).try_read().unwrap_or(U128::zero());
+    }
+    storage.sub_pool_last_asset_error_offset.get((pool_id, asset)).try_read().unwrap_or(U128::zero())
+}
+#[storage(read, write)]
+fn set_pool_last_asset_error_offset(pool_id: u64, asset: AssetId, error: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.last_asset_error_offset.insert(asset, error);
+    } else {
+        storage
+            .sub_pool_last_asset_error_offset
+            .insert((pool_id, asset), error);
+    }
+}
+#[storage(read)]
+fn pool_last_usdf_error_offset(pool_id: u64) -> U128 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.last_usdf_error_offset.read();
+    }
+    storage.sub_pool_last_usdf_error_offset.get(pool_id).try_read().unwrap_or(U128::zero())
+}
+#[storage(read, write)]
+fn set_pool_last_usdf_error_offset(pool_id: u64, error: U128) {
+    if pool_id == SHARED_POOL_ID {
+        storage.last_usdf_error_offset.write(error);
+    } else {
+        storage.sub_pool_last_usdf_error_offset.insert(pool_id, error);
+    }
+}
+#[storage(read)]
+fn pool_front_end_stake(front_end: Identity, pool_id: u64) -> u64 {
+    if pool_id == SHARED_POOL_ID {
+        return storage.front_end_stakes.get(front_end).try_read().unwrap_or(0);
+    }
+    storage.sub_pool_front_end_stakes.get((front_end, pool_id)).try_read().unwrap_or(0)
+}
+#[storage(read, write)]
+fn set_pool_front_end_stake(front_end: Identity, pool_id: u64, stake: u64) {
+    if pool_id == SHARED_POOL_ID {
+        storage.front_end_stakes.insert(front_end, stake);
+    } else {
+        storage
+            .sub_pool_front_end_stakes
+            .insert((front_end, pool_id), stake);
+    }
+}
+#[storage(read)]
+fn pool_front_end_snapshots(front_end: Identity, pool_id: u64) -> Option<Snapshots> {
+    if pool_id == SHARED_POOL_ID {
+        return storage.front_end_snapshots.get(front_end).try_read();
+    }
+    storage.sub_pool_front_end_snapshots.get((front_end, pool_id)).try_read()
+}
+#[storage(read, write)]
+fn set_pool_front_end_snapshots(front_end: Identity, pool_id: u64, snapshots: Snapshots) {
+    if pool_id == SHARED_POOL_ID {
+        storage.front_end_snapshots.insert(front_end, snapshots);
+    } else {
+        storage
+            .sub_pool_front_end_snapshots
+            .insert((front_end, pool_id), snapshots);
+    }
+}
+#[storage(write)]
+fn remove_pool_front_end_snapshots(front_end: Identity, pool_id: u64) {
+    if pool_id == SHARED_POOL_ID {
+        let _ = storage.front_end_snapshots.remove(front_end);
+    } else {
+        let _ = storage.sub_pool_front_end_snapshots.remove((front_end, pool_id));
+    }
+}
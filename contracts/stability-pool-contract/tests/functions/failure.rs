@@ -3,7 +3,9 @@ use test_utils::{
     data_structures::{ContractInstance, PRECISION},
     interfaces::{
         borrow_operations::borrow_operations_utils,
+        community_issuance::community_issuance_abi,
         oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, ProtocolManager},
         pyth_oracle::{
             pyth_oracle_abi, pyth_price_feed, pyth_price_no_precision_with_time, PYTH_TIMESTAMP,
         },
@@ -68,6 +70,7 @@ async fn fails_unauthorized() {
         ContractId::zeroed(),
         ContractId::zeroed(),
         ContractId::zeroed(),
+        ContractId::zeroed(),
     )
     .await
     .expect_err("Able to initialize stability pool with unauthorized address");
@@ -206,3 +209,325 @@ async fn fails_withdraw_with_undercollateralized_trove() {
         "Withdrawal should succeed when there's no undercollateralized trove"
     );
 }
+
+#[tokio::test]
+async fn fails_front_end_tag_and_registration() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    let front_end = wallets.pop().unwrap();
+    let front_end_sp = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            front_end.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool_with_front_end(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+        Some(Identity::Address(front_end.address().into())),
+    )
+    .await
+    .expect_err("Able to tag a deposit with an unregistered front end");
+
+    stability_pool_abi::register_front_end(&front_end_sp, PRECISION + 1)
+        .await
+        .expect_err("Able to register a front end with a kickback rate above 100%");
+
+    stability_pool_abi::register_front_end(&front_end_sp, PRECISION / 2)
+        .await
+        .unwrap();
+
+    stability_pool_abi::register_front_end(&front_end_sp, PRECISION / 2)
+        .await
+        .expect_err("Able to register a front end twice");
+
+    // Depositors cannot register as front ends
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    stability_pool_abi::register_front_end(&contracts.stability_pool, PRECISION / 2)
+        .await
+        .expect_err("Able to register a front end with an active deposit");
+
+    // Front ends cannot deposit
+    admin
+        .transfer(
+            front_end.address().into(),
+            100 * PRECISION,
+            contracts.usdf_asset_id,
+            TxPolicies::default().with_tip(1),
+        )
+        .await
+        .unwrap();
+
+    stability_pool_abi::provide_to_stability_pool(
+        &front_end_sp,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+    )
+    .await
+    .expect_err("Registered front end able to deposit");
+}
+
+#[tokio::test]
+async fn fails_invalid_sub_pool() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, true, false).await;
+    let attacker = wallets.pop().unwrap();
+
+    let mut assets = vec![
+        contracts.asset_contracts[0].asset_id,
+        contracts.asset_contracts[1].asset_id,
+    ];
+    assets.sort();
+
+    let protocol_manager_attacker = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    protocol_manager_abi::create_stability_sub_pool(
+        &protocol_manager_attacker,
+        &contracts.stability_pool,
+        assets.clone(),
+    )
+    .await
+    .expect_err("Able to create a sub-pool with unauthorized address");
+
+    protocol_manager_abi::create_stability_sub_pool(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        vec![assets[1], assets[0]],
+    )
+    .await
+    .expect_err("Able to create a sub-pool with unsorted assets");
+
+    protocol_manager_abi::create_stability_sub_pool(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        vec![AssetId::zeroed()],
+    )
+    .await
+    .expect_err("Able to create a sub-pool with an unsupported asset");
+
+    protocol_manager_abi::create_stability_sub_pool(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        assets.clone(),
+    )
+    .await
+    .unwrap();
+
+    protocol_manager_abi::create_stability_sub_pool(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        assets.clone(),
+    )
+    .await
+    .expect_err("Able to create the same sub-pool twice");
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    stability_pool_abi::provide_to_sub_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+        2,
+        None,
+    )
+    .await
+    .expect_err("Able to deposit into a sub-pool that does not exist");
+
+    // Deposits live in a single pool
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    stability_pool_abi::provide_to_sub_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        100 * PRECISION,
+        1,
+        None,
+    )
+    .await
+    .expect_err("Able to top up a shared pool deposit into a sub-pool");
+}
+
+#[tokio::test]
+async fn fails_withdrawal_outside_cooldown_window() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let attacker = wallets.pop().unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        1_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    stability_pool_abi::request_withdrawal(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        100 * PRECISION,
+    )
+    .await
+    .expect_err("Able to request a withdrawal with the cooldown disabled");
+
+    let protocol_manager_attacker = ContractInstance::new(
+        ProtocolManager::new(
+            contracts.protocol_manager.contract.contract_id().clone(),
+            attacker.clone(),
+        ),
+        contracts.protocol_manager.implementation_id,
+    );
+    protocol_manager_abi::set_stability_pool_withdrawal_cooldown(
+        &protocol_manager_attacker,
+        &contracts.stability_pool,
+        100,
+        100,
+    )
+    .await
+    .expect_err("Able to set the withdrawal cooldown with unauthorized address");
+
+    protocol_manager_abi::set_stability_pool_withdrawal_cooldown(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        100,
+        100,
+    )
+    .await
+    .unwrap();
+
+    let withdraw = || {
+        stability_pool_abi::withdraw_from_stability_pool(
+            &contracts.stability_pool,
+            &contracts.community_issuance,
+            &contracts.usdf,
+            &contracts.asset_contracts[0].asset,
+            &contracts.sorted_troves,
+            &contracts.asset_contracts[0].oracle,
+            &contracts.asset_contracts[0].mock_pyth_oracle,
+            &contracts.asset_contracts[0].mock_redstone_oracle,
+            &contracts.asset_contracts[0].trove_manager,
+            100 * PRECISION,
+        )
+    };
+
+    withdraw()
+        .await
+        .expect_err("Able to withdraw without a withdrawal request");
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 1_000).await;
+    stability_pool_abi::request_withdrawal(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        100 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 1_099).await;
+    withdraw()
+        .await
+        .expect_err("Able to withdraw before the cooldown has passed");
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 1_201).await;
+    withdraw()
+        .await
+        .expect_err("Able to withdraw after the request has expired");
+
+    stability_pool_abi::cancel_withdrawal_request(&contracts.stability_pool)
+        .await
+        .unwrap();
+
+    stability_pool_abi::cancel_withdrawal_request(&contracts.stability_pool)
+        .await
+        .expect_err("Able to cancel a withdrawal request twice");
+}
@@ -1,10 +1,12 @@
 use crate::utils::setup::setup;
 use fuels::{prelude::*, types::Identity};
 use test_utils::{
-    data_structures::{ContractInstance, PRECISION},
+    data_structures::{ContractInstance, ProtocolContracts, PRECISION},
     interfaces::{
         borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        community_issuance::community_issuance_abi,
         oracle::oracle_abi,
+        protocol_manager::protocol_manager_abi,
         pyth_oracle::{
             pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
             PYTH_TIMESTAMP,
@@ -1345,3 +1347,765 @@ async fn proper_one_sp_depositor_position_new_asset_onboarded_midway() {
         "st_mock_balance not currect",
     );
 }
+
+#[tokio::test]
+async fn proper_stability_deposit_with_front_end_kickback() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 0).await;
+
+    let front_end = wallets.pop().unwrap();
+    let depositor = wallets.pop().unwrap();
+    let front_end_identity = Identity::Address(front_end.address().into());
+    let depositor_identity = Identity::Address(depositor.address().into());
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(1),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    // Depositor has no trove, only USDF
+    admin
+        .transfer(
+            depositor.address().into(),
+            1_000 * PRECISION,
+            contracts.usdf_asset_id,
+            TxPolicies::default().with_tip(1),
+        )
+        .await
+        .unwrap();
+
+    let front_end_sp = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            front_end.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+    let depositor_sp = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            depositor.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    // Front end passes 60% of the FPT gains on to its depositors
+    let kickback_rate = 600_000_000;
+    let res = stability_pool_abi::register_front_end(&front_end_sp, kickback_rate)
+        .await
+        .unwrap();
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("FrontEndRegisteredEvent")),
+        "FrontEndRegisteredEvent not found"
+    );
+
+    let registered =
+        stability_pool_abi::get_front_end(&contracts.stability_pool, front_end_identity)
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+    assert_eq!(registered.kickback_rate, kickback_rate);
+    assert!(registered.is_registered);
+
+    let deposit_amount = 1_000 * PRECISION;
+    let res = stability_pool_abi::provide_to_stability_pool_with_front_end(
+        &depositor_sp,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        deposit_amount,
+        Some(front_end_identity),
+    )
+    .await
+    .unwrap();
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("FrontEndTagSetEvent")),
+        "FrontEndTagSetEvent not found"
+    );
+
+    let tag =
+        stability_pool_abi::get_depositor_front_end(&contracts.stability_pool, depositor_identity)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(tag, Some(front_end_identity));
+
+    let front_end_stake = stability_pool_abi::get_compounded_front_end_stake(
+        &contracts.stability_pool,
+        front_end_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(front_end_stake, deposit_amount);
+
+    // One year of issuance, an untagged deposit from admin triggers the issuance
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 60 * 60 * 24 * 30 * 12)
+        .await;
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let depositor_fpt_gain =
+        stability_pool_abi::get_depositor_fpt_gain(&contracts.stability_pool, depositor_identity)
+            .await
+            .unwrap()
+            .value;
+    let front_end_fpt_gain =
+        stability_pool_abi::get_front_end_fpt_gain(&contracts.stability_pool, front_end_identity)
+            .await
+            .unwrap()
+            .value;
+
+    assert!(depositor_fpt_gain > 0, "Depositor should have FPT gains");
+    assert_within_threshold(
+        depositor_fpt_gain * 4 / 6,
+        front_end_fpt_gain,
+        "Front end should receive 40% of the FPT gains of its tagged deposits",
+    );
+
+    // Withdrawing the whole deposit pays out both and clears the tag and stake
+    stability_pool_abi::withdraw_from_stability_pool(
+        &depositor_sp,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].trove_manager,
+        deposit_amount,
+    )
+    .await
+    .unwrap();
+
+    let provider = admin.provider().unwrap();
+    let depositor_fpt_balance = provider
+        .get_asset_balance(depositor.address().into(), contracts.fpt_asset_id)
+        .await
+        .unwrap();
+    let front_end_fpt_balance = provider
+        .get_asset_balance(front_end.address().into(), contracts.fpt_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(depositor_fpt_balance, depositor_fpt_gain);
+    assert_eq!(front_end_fpt_balance, front_end_fpt_gain);
+
+    let tag =
+        stability_pool_abi::get_depositor_front_end(&contracts.stability_pool, depositor_identity)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(tag, None);
+
+    let front_end_stake = stability_pool_abi::get_compounded_front_end_stake(
+        &contracts.stability_pool,
+        front_end_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(front_end_stake, 0);
+}
+
+// Admin deposits 1_500 USDF in the Stability Pool which absorbs the liquidation of a 1_100 coll / 1_000 debt trove.
+// Returns the asset gain and compounded deposit of the admin after the liquidation.
+async fn setup_liquidated_sp_position(
+    contracts: &ProtocolContracts<WalletUnlocked>,
+    admin: &WalletUnlocked,
+    liquidated_wallet: &WalletUnlocked,
+) -> (u64, u64) {
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let init_stability_deposit = 1_500 * PRECISION;
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        init_stability_deposit,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    trove_manager_abi::liquidate(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let mut asset_gain = 1_100 * PRECISION;
+    asset_gain -= asset_gain / 200;
+    let compounded_deposit = init_stability_deposit - with_min_borrow_fee(1_000 * PRECISION);
+
+    (asset_gain, compounded_deposit)
+}
+
+#[tokio::test]
+async fn proper_claim_gains() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let liquidated_wallet = wallets.pop().unwrap();
+
+    let (asset_gain, compounded_deposit) =
+        setup_liquidated_sp_position(&contracts, &admin, &liquidated_wallet).await;
+
+    let provider = admin.provider().unwrap();
+    let mock_asset_id: AssetId = contracts.asset_contracts[0].asset_id;
+    let balance_before = provider
+        .get_asset_balance(admin.address(), mock_asset_id)
+        .await
+        .unwrap();
+
+    let res = stability_pool_abi::claim_gains(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.asset_contracts[0].asset,
+    )
+    .await
+    .unwrap();
+
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("ClaimGainsEvent")),
+        "ClaimGainsEvent not found"
+    );
+
+    let balance_after = provider
+        .get_asset_balance(admin.address(), mock_asset_id)
+        .await
+        .unwrap();
+    assert_within_threshold(
+        balance_after - balance_before,
+        asset_gain,
+        "Claimed asset gain is not correct",
+    );
+
+    // The deposit stays in the pool, only the gains are paid out
+    stability_pool_utils::assert_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+        compounded_deposit,
+    )
+    .await;
+
+    stability_pool_utils::assert_depositor_asset_gain(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+        0,
+        mock_asset_id,
+    )
+    .await;
+
+    stability_pool_utils::assert_total_usdf_deposits(&contracts.stability_pool, compounded_deposit)
+        .await;
+}
+
+#[tokio::test]
+async fn proper_claim_gains_to_trove() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let liquidated_wallet = wallets.pop().unwrap();
+
+    let (asset_gain, compounded_deposit) =
+        setup_liquidated_sp_position(&contracts, &admin, &liquidated_wallet).await;
+
+    let admin_identity = Identity::Address(admin.address().into());
+    let trove_coll_before = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        admin_identity,
+    )
+    .await
+    .value;
+
+    let res = stability_pool_abi::claim_gains_to_trove(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("ClaimGainsToTroveEvent")),
+        "ClaimGainsToTroveEvent not found"
+    );
+
+    let trove_coll_after = trove_manager_abi::get_trove_coll(
+        &contracts.asset_contracts[0].trove_manager,
+        admin_identity,
+    )
+    .await
+    .value;
+    assert_within_threshold(
+        trove_coll_after - trove_coll_before,
+        asset_gain,
+        "Asset gain was not added to the trove",
+    );
+
+    stability_pool_utils::assert_pool_asset(
+        &contracts.stability_pool,
+        0,
+        contracts.asset_contracts[0].asset_id,
+    )
+    .await;
+
+    stability_pool_utils::assert_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        admin_identity,
+        compounded_deposit,
+    )
+    .await;
+
+    // Nothing left to move
+    stability_pool_abi::claim_gains_to_trove(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.borrow_operations,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .expect_err("Able to move an empty asset gain to trove");
+}
+
+#[tokio::test]
+async fn proper_sub_pool_absorbs_liquidation_first() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let liquidated_wallet = wallets.pop().unwrap();
+    let sub_pool_depositor = wallets.pop().unwrap();
+    let mock_asset_id: AssetId = contracts.asset_contracts[0].asset_id;
+
+    protocol_manager_abi::create_stability_sub_pool(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        vec![mock_asset_id],
+    )
+    .await
+    .unwrap();
+
+    let pool_id =
+        stability_pool_abi::get_sub_pool_id(&contracts.stability_pool, vec![mock_asset_id])
+            .await
+            .unwrap()
+            .value
+            .unwrap();
+    assert_eq!(pool_id, 1);
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let shared_deposit = 1_000 * PRECISION;
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        shared_deposit,
+    )
+    .await
+    .unwrap();
+
+    let sub_pool_deposit = 2_000 * PRECISION;
+    admin
+        .transfer(
+            sub_pool_depositor.address().into(),
+            sub_pool_deposit,
+            contracts.usdf_asset_id,
+            TxPolicies::default().with_tip(1),
+        )
+        .await
+        .unwrap();
+
+    let sub_pool_depositor_sp = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            sub_pool_depositor.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+    stability_pool_abi::provide_to_sub_pool(
+        &sub_pool_depositor_sp,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        sub_pool_deposit,
+        pool_id,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let depositor_pool = stability_pool_abi::get_depositor_pool(
+        &contracts.stability_pool,
+        Identity::Address(sub_pool_depositor.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(depositor_pool, pool_id);
+
+    let total_for_asset = stability_pool_abi::get_total_usdf_deposits_for_asset(
+        &contracts.stability_pool,
+        mock_asset_id,
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(total_for_asset, shared_deposit + sub_pool_deposit);
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    let res = trove_manager_abi::liquidate(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("PoolOffsetEvent")),
+        "PoolOffsetEvent not found"
+    );
+
+    // The sub-pool is large enough to absorb the whole debt, the shared pool is untouched
+    let debt = with_min_borrow_fee(1_000 * PRECISION);
+    let mut asset_gain = 1_100 * PRECISION;
+    asset_gain -= asset_gain / 200;
+
+    let sub_pool_total =
+        stability_pool_abi::get_pool_total_usdf_deposits(&contracts.stability_pool, pool_id)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(sub_pool_total, sub_pool_deposit - debt);
+
+    let shared_pool_total =
+        stability_pool_abi::get_pool_total_usdf_deposits(&contracts.stability_pool, 0)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(shared_pool_total, shared_deposit);
+
+    stability_pool_utils::assert_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+        shared_deposit,
+    )
+    .await;
+
+    stability_pool_utils::assert_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        Identity::Address(sub_pool_depositor.address().into()),
+        sub_pool_deposit - debt,
+    )
+    .await;
+
+    let sub_pool_asset_gain =
+        stability_pool_abi::get_pool_asset_gain(&contracts.stability_pool, pool_id, mock_asset_id)
+            .await
+            .unwrap()
+            .value;
+    assert_within_threshold(
+        sub_pool_asset_gain,
+        asset_gain,
+        "Sub-pool asset gain is not correct",
+    );
+
+    let shared_pool_asset_gain =
+        stability_pool_abi::get_pool_asset_gain(&contracts.stability_pool, 0, mock_asset_id)
+            .await
+            .unwrap()
+            .value;
+    assert_eq!(shared_pool_asset_gain, 0);
+
+    stability_pool_utils::assert_depositor_asset_gain(
+        &contracts.stability_pool,
+        Identity::Address(sub_pool_depositor.address().into()),
+        asset_gain,
+        mock_asset_id,
+    )
+    .await;
+
+    stability_pool_utils::assert_depositor_asset_gain(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+        0,
+        mock_asset_id,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn proper_two_step_withdrawal() {
+    let (contracts, admin, _wallets) = setup_protocol(4, false, false).await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    let init_stability_deposit = 1_500 * PRECISION;
+    stability_pool_abi::provide_to_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        init_stability_deposit,
+    )
+    .await
+    .unwrap();
+
+    let delay = 60 * 60;
+    let window = 60 * 60 * 24;
+    protocol_manager_abi::set_stability_pool_withdrawal_cooldown(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        delay,
+        window,
+    )
+    .await
+    .unwrap();
+
+    let cooldown = stability_pool_abi::get_withdrawal_cooldown(&contracts.stability_pool)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(cooldown, (delay, window));
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 1_000).await;
+    let res = stability_pool_abi::request_withdrawal(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        500 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let logs = res.decode_logs();
+    assert!(
+        logs.results
+            .iter()
+            .any(|log| log.as_ref().unwrap().contains("WithdrawalRequestedEvent")),
+        "WithdrawalRequestedEvent not found"
+    );
+
+    let request = stability_pool_abi::get_withdrawal_request(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .unwrap()
+    .value
+    .unwrap();
+    assert_eq!(request.amount, 500 * PRECISION);
+    assert_eq!(request.unlock_time, 1_000 + delay);
+
+    // The requested USDF stays deposited until the withdrawal is executed
+    stability_pool_utils::assert_total_usdf_deposits(
+        &contracts.stability_pool,
+        init_stability_deposit,
+    )
+    .await;
+
+    community_issuance_abi::set_current_time(&contracts.community_issuance, 1_000 + delay).await;
+
+    // The withdrawal is capped by the requested amount
+    stability_pool_abi::withdraw_from_stability_pool(
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].trove_manager,
+        init_stability_deposit,
+    )
+    .await
+    .unwrap();
+
+    stability_pool_utils::assert_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+        init_stability_deposit - 500 * PRECISION,
+    )
+    .await;
+
+    let request = stability_pool_abi::get_withdrawal_request(
+        &contracts.stability_pool,
+        Identity::Address(admin.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert!(request.is_none(), "Withdrawal request was not consumed");
+}
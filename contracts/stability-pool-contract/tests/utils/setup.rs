@@ -200,6 +200,7 @@ pub async fn setup(
         mock_token.contract_id().into(),
         active_pool.contract.contract_id().into(),
         sorted_troves.contract_id().into(),
+        stability_pool.contract.contract_id().into(),
     )
     .await
     .unwrap();
@@ -0,0 +1,20 @@
+library;
+
+pub struct VaultDepositEvent {
+    pub sender: Identity,
+    pub receiver: Identity,
+    pub usdf_amount: u64,
+    pub shares: u64,
+}
+
+pub struct VaultWithdrawEvent {
+    pub sender: Identity,
+    pub receiver: Identity,
+    pub usdf_amount: u64,
+    pub shares: u64,
+}
+
+pub struct VaultHarvestEvent {
+    pub usdf_compounded: u64,
+    pub total_usdf: u64,
+}
@@ -0,0 +1,395 @@
+contract;
+// This contract, StabilityPoolVault, is an auto-compounding wrapper around the Stability Pool.
+//
+// Key functionalities include:
+// - Accepting USDF deposits, depositing them in the Stability Pool and minting SRC-20 vault shares
+// - Harvesting the collateral and FPT gains of the vault's Stability Pool position
+// - Selling the harvested gains for USDF through a pluggable SwapAdapter and re-depositing the proceeds
+// - Redeeming vault shares for their share of the vault's compounded USDF deposit and unharvested gains
+//
+// Collateral gains that have not been harvested yet belong to the current share holders. Every asset of the
+// Stability Pool is tracked, deposits are priced against the compounded deposit plus the unharvested collateral
+// gains valued at the protocol oracle price minus GAIN_HAIRCUT, and withdrawals pay out the withdrawn share of
+// the unharvested collateral gains in kind. The swap adapter is only used to harvest, never to price shares.
+// FPT has no protocol oracle, so FPT gains are left out of the share price until harvested into USDF.
+//
+// The vault withdraws on behalf of its holders in a single step, so it must be exempted from the
+// Stability Pool's withdrawal cooldown through the ProtocolManager whenever the cooldown is enabled.
+
+mod events;
+
+use ::events::{VaultDepositEvent, VaultHarvestEvent, VaultWithdrawEvent};
+use libraries::fluid_math::{DECIMAL_PRECISION, fm_multiply_ratio};
+use libraries::oracle_interface::Oracle;
+use libraries::stability_pool_interface::StabilityPool;
+use libraries::stability_pool_vault_interface::StabilityPoolVault;
+use libraries::swap_adapter_interface::SwapAdapter;
+use standards::{src20::{SetDecimalsEvent, SetNameEvent, SetSymbolEvent, SRC20, TotalSupplyEvent}, src5::*,};
+use sway_libs::ownership::*;
+use std::{
+    asset::{
+        burn,
+        mint_to,
+        transfer,
+    },
+    call_frames::msg_asset_id,
+    context::{
+        msg_amount,
+        this_balance,
+    },
+    hash::Hash,
+    string::String,
+    u128::U128,
+};
+
+pub const DECIMALS: u8 = 9;
+pub const SYMBOL: str[5] = __to_str_array("vUSDF");
+pub const NAME: str[25] = __to_str_array("Stability Pool USDF Vault");
+
+configurable {
+    /// Initializer identity
+    INITIALIZER: Identity = Identity::Address(Address::zero()),
+    STABILITY_POOL_CONTRACT: ContractId = ContractId::zero(),
+    USDF_ASSET_ID: AssetId = AssetId::zero(),
+    /// Share of the oracle value of unharvested collateral gains left out of the share price (5%)
+    GAIN_HAIRCUT: u64 = 50_000_000,
+}
+
+storage {
+    swap_adapter: ContractId = ContractId::zero(),
+    // Identity allowed to harvest besides the owner, harvests set the minimum USDF out of each swap
+    harvester: Identity = Identity::Address(Address::zero()),
+    total_supply: u64 = 0,
+    is_initialized: bool = false,
+    lock_vault: bool = false,
+}
+
+impl StabilityPoolVault for Contract {
+    #[storage(read, write)]
+    fn initialize(owner: Identity, swap_adapter: ContractId) {
+        require(
+            msg_sender()
+                .unwrap() == INITIALIZER,
+            "StabilityPoolVault: Caller is not initializer",
+        );
+        require(
+            storage
+                .is_initialized
+                .read() == false,
+            "StabilityPoolVault: Contract is already initialized",
+        );
+        initialize_ownership(owner);
+        storage.swap_adapter.write(swap_adapter);
+        storage.harvester.write(owner);
+        let sender = msg_sender().unwrap();
+        SetSymbolEvent::new(
+            AssetId::default(),
+            Some(String::from_ascii_str(from_str_array(SYMBOL))),
+            sender,
+        )
+            .log();
+        SetDecimalsEvent::new(AssetId::default(), DECIMALS, sender)
+            .log();
+        SetNameEvent::new(
+            AssetId::default(),
+            Some(String::from_ascii_str(from_str_array(NAME))),
+            sender,
+        )
+            .log();
+        TotalSupplyEvent::new(AssetId::default(), 0, sender).log();
+        storage.is_initialized.write(true);
+    }
+    /*
+    * Deposits the forwarded USDF in the Stability Pool and mints vault shares to the receiver.
+    * Shares are priced against the vault's compounded Stability Pool deposit.
+    */
+    #[storage(read, write), payable]
+    fn deposit(receiver: Identity) -> u64 {
+        require_not_locked();
+        storage.lock_vault.write(true);
+        require(
+            msg_asset_id() == USDF_ASSET_ID && msg_amount() > 0,
+            "StabilityPoolVault: USDF deposit must be non zero",
+        );
+        let usdf_amount = msg_amount();
+        let shares = internal_preview_deposit(usdf_amount);
+        require(shares > 0, "StabilityPoolVault: Deposit too small");
+        let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+        stability_pool
+            .provide_to_stability_pool {
+                asset_id: USDF_ASSET_ID.bits(),
+                coins: usdf_amount,
+            }(None);
+        internal_mint_shares(receiver, shares);
+        log(VaultDepositEvent {
+            sender: msg_sender().unwrap(),
+            receiver,
+            usdf_amount,
+            shares,
+        });
+        storage.lock_vault.write(false);
+        shares
+    }
+    /*
+    * Burns the forwarded vault shares and sends their share of the compounded deposit to the receiver in USDF,
+    * along with their share of the unharvested collateral gains in kind.
+    */
+    #[storage(read, write), payable]
+    fn withdraw(receiver: Identity) -> u64 {
+        require_not_locked();
+        storage.lock_vault.write(true);
+        require(
+            msg_asset_id() == AssetId::default() && msg_amount() > 0,
+            "StabilityPoolVault: Share amount must be non zero",
+        );
+        let shares = msg_amount();
+        let total_supply = storage.total_supply.read();
+        let usdf_amount = internal_preview_withdraw(shares);
+        internal_burn_shares(shares);
+        let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+        // Withdrawing from the Stability Pool also pays the vault's pending gains to the vault
+        if usdf_amount > 0 {
+            let (withdrawal_delay, _) = stability_pool.get_withdrawal_cooldown();
+            require(
+                withdrawal_delay == 0 || stability_pool
+                    .is_withdrawal_cooldown_exempt(Identity::ContractId(ContractId::this())),
+                "StabilityPoolVault: Vault is not exempt from the withdrawal cooldown",
+            );
+            stability_pool.withdraw_from_stability_pool(usdf_amount);
+            transfer(receiver, USDF_ASSET_ID, usdf_amount);
+        }
+        let assets = stability_pool.get_assets();
+        let mut i = 0;
+        while i < assets.len() {
+            let asset = assets.get(i).unwrap();
+            let gain_amount = (U128::from(this_balance(asset)) * U128::from(shares) / U128::from(total_supply)).as_u64().unwrap();
+            if gain_amount > 0 {
+                transfer(receiver, asset, gain_amount);
+            }
+            i += 1;
+        }
+        log(VaultWithdrawEvent {
+            sender: msg_sender().unwrap(),
+            receiver,
+            usdf_amount,
+            shares,
+        });
+        storage.lock_vault.write(false);
+        usdf_amount
+    }
+    /*
+    * Claims the vault's Stability Pool gains, sells each of the given assets for USDF through the swap adapter
+    * and deposits the proceeds back in the Stability Pool. FPT is harvested by including its asset id.
+    */
+    #[storage(read, write)]
+    fn harvest(assets: Vec<AssetId>, min_amounts_out: Vec<u64>) {
+        require_not_locked();
+        storage.lock_vault.write(true);
+        require_is_harvester();
+        require(
+            assets
+                .len() == min_amounts_out
+                .len(),
+            "StabilityPoolVault: Assets and minimum amounts length mismatch",
+        );
+        let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+        if storage.total_supply.read() > 0 {
+            stability_pool.claim_gains();
+        }
+        let swap_adapter = abi(SwapAdapter, storage.swap_adapter.read().bits());
+        let vault = Identity::ContractId(ContractId::this());
+        let mut i = 0;
+        while i < assets.len() {
+            let asset = assets.get(i).unwrap();
+            require(
+                asset != USDF_ASSET_ID && asset != AssetId::default(),
+                "StabilityPoolVault: Cannot harvest USDF or vault shares",
+            );
+            let amount_in = this_balance(asset);
+            if amount_in > 0 {
+                let _ = swap_adapter.swap {
+                    asset_id: asset.bits(),
+                    coins: amount_in,
+                }(USDF_ASSET_ID, min_amounts_out.get(i).unwrap(), vault);
+            }
+            i += 1;
+        }
+        let usdf_compounded = this_balance(USDF_ASSET_ID);
+        if usdf_compounded > 0 {
+            stability_pool
+                .provide_to_stability_pool {
+                    asset_id: USDF_ASSET_ID.bits(),
+                    coins: usdf_compounded,
+                }(None);
+        }
+        log(VaultHarvestEvent {
+            usdf_compounded,
+            total_usdf: internal_get_total_usdf(),
+        });
+        storage.lock_vault.write(false);
+    }
+    #[storage(read, write)]
+    fn set_swap_adapter(swap_adapter: ContractId) {
+        only_owner();
+        storage.swap_adapter.write(swap_adapter);
+    }
+    #[storage(read, write)]
+    fn set_harvester(harvester: Identity) {
+        only_owner();
+        storage.harvester.write(harvester);
+    }
+    #[storage(read, write)]
+    fn transfer_owner(new_owner: Identity) {
+        only_owner();
+        transfer_ownership(new_owner);
+    }
+    #[storage(read)]
+    fn get_total_usdf() -> u64 {
+        internal_get_total_usdf()
+    }
+    #[storage(read)]
+    fn get_share_asset_id() -> AssetId {
+        AssetId::default()
+    }
+    #[storage(read)]
+    fn get_swap_adapter() -> ContractId {
+        storage.swap_adapter.read()
+    }
+    #[storage(read)]
+    fn get_harvester() -> Identity {
+        storage.harvester.read()
+    }
+    #[storage(read)]
+    fn preview_deposit(usdf_amount: u64) -> u64 {
+        internal_preview_deposit(usdf_amount)
+    }
+    #[storage(read)]
+    fn preview_withdraw(shares: u64) -> u64 {
+        internal_preview_withdraw(shares)
+    }
+}
+
+impl SRC20 for Contract {
+    #[storage(read)]
+    fn total_assets() -> u64 {
+        return 1;
+    }
+    #[storage(read)]
+    fn total_supply(asset: AssetId) -> Option<u64> {
+        if asset == AssetId::default() {
+            return Some(storage.total_supply.read())
+        }
+        return None;
+    }
+    #[storage(read)]
+    fn name(asset: AssetId) -> Option<String> {
+        if asset == AssetId::default() {
+            return Some(String::from_ascii_str(from_str_array(NAME)));
+        }
+        return None;
+    }
+    #[storage(read)]
+    fn symbol(asset: AssetId) -> Option<String> {
+        if asset == AssetId::default() {
+            return Some(String::from_ascii_str(from_str_array(SYMBOL)));
+        }
+        return None;
+    }
+    #[storage(read)]
+    fn decimals(asset: AssetId) -> Option<u8> {
+        if asset == AssetId::default() {
+            return Some(DECIMALS);
+        }
+        return None;
+    }
+}
+
+impl SRC5 for Contract {
+    #[storage(read)]
+    fn owner() -> State {
+        _owner()
+    }
+}
+
+// Compounded deposit plus the pending and held collateral gains of every Stability Pool asset, valued at the
+// protocol oracle price minus GAIN_HAIRCUT
+#[storage(read)]
+fn internal_get_total_usdf() -> u64 {
+    let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+    let vault = Identity::ContractId(ContractId::this());
+    let mut total_usdf = stability_pool.get_compounded_usdf_deposit(vault);
+    let assets = stability_pool.get_assets();
+    let mut i = 0;
+    while i < assets.len() {
+        let asset = assets.get(i).unwrap();
+        let gain_amount = stability_pool.get_depositor_asset_gain(vault, asset) + this_balance(asset);
+        if gain_amount > 0 {
+            let oracle = abi(Oracle, stability_pool.get_asset_oracle(asset).bits());
+            let gain_value = fm_multiply_ratio(gain_amount, oracle.get_price(), DECIMAL_PRECISION);
+            total_usdf += fm_multiply_ratio(gain_value, DECIMAL_PRECISION - GAIN_HAIRCUT, DECIMAL_PRECISION);
+        }
+        i += 1;
+    }
+    total_usdf
+}
+#[storage(read)]
+fn internal_get_compounded_usdf() -> u64 {
+    let stability_pool = abi(StabilityPool, STABILITY_POOL_CONTRACT.bits());
+    stability_pool.get_compounded_usdf_deposit(Identity::ContractId(ContractId::this()))
+}
+#[storage(read)]
+fn internal_preview_deposit(usdf_amount: u64) -> u64 {
+    let total_supply = storage.total_supply.read();
+    if total_supply == 0 {
+        return usdf_amount;
+    }
+    let total_usdf = internal_get_total_usdf();
+    require(
+        total_usdf > 0,
+        "StabilityPoolVault: Vault deposit has been depleted",
+    );
+    (U128::from(usdf_amount) * U128::from(total_supply) / U128::from(total_usdf)).as_u64().unwrap()
+}
+#[storage(read)]
+fn internal_preview_withdraw(shares: u64) -> u64 {
+    let total_supply = storage.total_supply.read();
+    if total_supply == 0 {
+        return 0;
+    }
+    (U128::from(shares) * U128::from(internal_get_compounded_usdf()) / U128::from(total_supply)).as_u64().unwrap()
+}
+#[storage(read, write)]
+fn internal_mint_shares(receiver: Identity, shares: u64) {
+    let new_total_supply = storage.total_supply.read() + shares;
+    storage.total_supply.write(new_total_supply);
+    mint_to(receiver, SubId::zero(), shares);
+    TotalSupplyEvent::new(AssetId::default(), new_total_supply, msg_sender().unwrap())
+        .log();
+}
+#[storage(read, write)]
+fn internal_burn_shares(shares: u64) {
+    let new_total_supply = storage.total_supply.read() - shares;
+    storage.total_supply.write(new_total_supply);
+    burn(SubId::zero(), shares);
+    TotalSupplyEvent::new(AssetId::default(), new_total_supply, msg_sender().unwrap())
+        .log();
+}
+#[storage(read)]
+fn require_not_locked() {
+    require(
+        storage
+            .lock_vault
+            .read() == false,
+        "StabilityPoolVault: Contract is locked",
+    );
+}
+#[storage(read)]
+fn require_is_harvester() {
+    let sender = msg_sender().unwrap();
+    require(
+        sender == storage
+            .harvester
+            .read() || State::Initialized(sender) == _owner(),
+        "StabilityPoolVault: Caller is not the harvester",
+    );
+}
@@ -0,0 +1,604 @@
+use fuels::{prelude::*, types::Identity};
+use test_utils::{
+    data_structures::PRECISION,
+    interfaces::{
+        borrow_operations::borrow_operations_utils,
+        oracle::oracle_abi,
+        protocol_manager::protocol_manager_abi,
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        stability_pool_vault::{stability_pool_vault_abi, StabilityPoolVault},
+        swap_adapter::swap_adapter_abi,
+        trove_manager::trove_manager_abi,
+    },
+    setup::common::{
+        assert_within_threshold, deploy_mock_swap_adapter, deploy_stability_pool_vault,
+        setup_protocol,
+    },
+    utils::with_min_borrow_fee,
+};
+
+#[tokio::test]
+async fn proper_vault_deposit_harvest_and_withdraw() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let liquidated_wallet = wallets.pop().unwrap();
+    let admin_identity = Identity::Address(admin.address().into());
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let swap_adapter = deploy_mock_swap_adapter(&admin).await;
+    let vault = deploy_stability_pool_vault(
+        &admin,
+        &contracts.stability_pool.contract.contract_id().into(),
+        &contracts.usdf_asset_id,
+    )
+    .await;
+
+    stability_pool_vault_abi::initialize(&vault, admin_identity, swap_adapter.contract_id().into())
+        .await
+        .unwrap();
+
+    let deposit_amount = 1_500 * PRECISION;
+    let shares = stability_pool_vault_abi::deposit(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts,
+        deposit_amount,
+        admin_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    // First depositor gets shares 1:1
+    assert_eq!(shares, deposit_amount);
+    let total_supply = stability_pool_vault_abi::total_supply(&vault).await.value;
+    assert_eq!(total_supply, Some(deposit_amount));
+
+    // The vault's deposit absorbs the liquidation
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    trove_manager_abi::liquidate(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // The unharvested collateral gain is valued at the oracle price minus the 5% haircut
+    let compounded_deposit = deposit_amount - with_min_borrow_fee(1_000 * PRECISION);
+    let mut asset_gain = 1_100 * PRECISION;
+    asset_gain -= asset_gain / 200;
+    let total_usdf = stability_pool_vault_abi::get_total_usdf(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_within_threshold(
+        total_usdf.value,
+        compounded_deposit + asset_gain - asset_gain / 20,
+        "Vault deposit should absorb the liquidated debt",
+    );
+
+    // Collateral gain is sold at half its oracle price
+    let usdf_from_swap = asset_gain / 2;
+
+    swap_adapter_abi::set_rate(
+        &swap_adapter,
+        contracts.asset_contracts[0].asset_id,
+        contracts.usdf_asset_id,
+        PRECISION / 2,
+    )
+    .await;
+
+    admin
+        .force_transfer_to_contract(
+            swap_adapter.contract_id(),
+            usdf_from_swap + PRECISION,
+            contracts.usdf_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    stability_pool_vault_abi::harvest(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &swap_adapter,
+        vec![contracts.asset_contracts[0].asset_id],
+        vec![usdf_from_swap * 99 / 100],
+    )
+    .await
+    .unwrap();
+
+    let total_usdf = stability_pool_vault_abi::get_total_usdf(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_within_threshold(
+        total_usdf.value,
+        compounded_deposit + usdf_from_swap,
+        "Harvested gains should be compounded into the vault deposit",
+    );
+
+    // Redeeming every share returns the compounded position
+    let provider = admin.provider().unwrap();
+    let usdf_before = provider
+        .get_asset_balance(admin.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap();
+
+    let withdrawn = stability_pool_vault_abi::withdraw(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+        shares,
+        admin_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    let usdf_after = provider
+        .get_asset_balance(admin.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap();
+
+    assert_eq!(usdf_after - usdf_before, withdrawn);
+    assert_within_threshold(
+        withdrawn,
+        compounded_deposit + usdf_from_swap,
+        "Withdrawn USDF should match the compounded vault deposit",
+    );
+
+    let total_supply = stability_pool_vault_abi::total_supply(&vault).await.value;
+    assert_eq!(total_supply, Some(0));
+}
+
+#[tokio::test]
+async fn proper_vault_share_price_with_unharvested_gains() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let liquidated_wallet = wallets.pop().unwrap();
+    let second_depositor = wallets.pop().unwrap();
+    let admin_identity = Identity::Address(admin.address().into());
+    let second_identity = Identity::Address(second_depositor.address().into());
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    for (wallet, coll, debt) in [
+        (&admin, 6_000 * PRECISION, 3_000 * PRECISION),
+        (&second_depositor, 6_000 * PRECISION, 3_000 * PRECISION),
+        (&liquidated_wallet, 1_100 * PRECISION, 1_000 * PRECISION),
+    ] {
+        borrow_operations_utils::mint_token_and_open_trove(
+            wallet.clone(),
+            &contracts.asset_contracts[0],
+            &contracts.borrow_operations,
+            &contracts.usdf,
+            &contracts.fpt_staking,
+            &contracts.active_pool,
+            &contracts.sorted_troves,
+            coll,
+            debt,
+        )
+        .await;
+    }
+
+    let swap_adapter = deploy_mock_swap_adapter(&admin).await;
+    let vault = deploy_stability_pool_vault(
+        &admin,
+        &contracts.stability_pool.contract.contract_id().into(),
+        &contracts.usdf_asset_id,
+    )
+    .await;
+
+    stability_pool_vault_abi::initialize(&vault, admin_identity, swap_adapter.contract_id().into())
+        .await
+        .unwrap();
+    let first_deposit = 1_500 * PRECISION;
+    let first_shares = stability_pool_vault_abi::deposit(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts,
+        first_deposit,
+        admin_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    trove_manager_abi::liquidate(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // The unharvested collateral gain counts towards the vault's value at the oracle price minus the 5% haircut
+    let compounded_deposit = first_deposit - with_min_borrow_fee(1_000 * PRECISION);
+    let mut asset_gain = 1_100 * PRECISION;
+    asset_gain -= asset_gain / 200;
+    let first_value = compounded_deposit + asset_gain - asset_gain / 20;
+
+    let total_usdf = stability_pool_vault_abi::get_total_usdf(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_within_threshold(
+        total_usdf.value,
+        first_value,
+        "Vault value should include the unharvested gain",
+    );
+
+    // Moving the swap adapter's quote does not move the share price
+    swap_adapter_abi::set_rate(
+        &swap_adapter,
+        asset_id,
+        contracts.usdf_asset_id,
+        10 * PRECISION,
+    )
+    .await;
+
+    let total_usdf = stability_pool_vault_abi::get_total_usdf(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.asset_contracts,
+    )
+    .await;
+    assert_within_threshold(
+        total_usdf.value,
+        first_value,
+        "Vault value should not depend on the swap adapter's quote",
+    );
+
+    // A deposit after the liquidation is priced against the gain and does not dilute it
+    let second_deposit = 1_000 * PRECISION;
+    let second_vault =
+        StabilityPoolVault::new(vault.contract_id().clone(), second_depositor.clone());
+    let second_shares = stability_pool_vault_abi::deposit(
+        &second_vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts,
+        second_deposit,
+        second_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    let expected_shares =
+        (second_deposit as u128 * first_shares as u128 / first_value as u128) as u64;
+    assert_within_threshold(
+        second_shares,
+        expected_shares,
+        "Late depositor should buy shares at the price including the gain",
+    );
+
+    let provider = admin.provider().unwrap();
+    let second_usdf_before = provider
+        .get_asset_balance(second_depositor.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap();
+    let second_asset_before = provider
+        .get_asset_balance(second_depositor.address(), asset_id)
+        .await
+        .unwrap();
+
+    stability_pool_vault_abi::withdraw(
+        &second_vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+        second_shares,
+        second_identity,
+    )
+    .await
+    .unwrap();
+
+    let second_usdf = provider
+        .get_asset_balance(second_depositor.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap()
+        - second_usdf_before;
+    let second_asset = provider
+        .get_asset_balance(second_depositor.address(), asset_id)
+        .await
+        .unwrap()
+        - second_asset_before;
+    assert_within_threshold(
+        second_usdf + second_asset - second_asset / 20,
+        second_deposit,
+        "Late depositor should only get back what they put in",
+    );
+
+    // The first depositor withdraws before any harvest and keeps the whole gain
+    let admin_usdf_before = provider
+        .get_asset_balance(admin.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap();
+    let admin_asset_before = provider
+        .get_asset_balance(admin.address(), asset_id)
+        .await
+        .unwrap();
+
+    stability_pool_vault_abi::withdraw(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+        first_shares,
+        admin_identity,
+    )
+    .await
+    .unwrap();
+
+    let admin_usdf = provider
+        .get_asset_balance(admin.address(), contracts.usdf_asset_id)
+        .await
+        .unwrap()
+        - admin_usdf_before;
+    let admin_asset = provider
+        .get_asset_balance(admin.address(), asset_id)
+        .await
+        .unwrap()
+        - admin_asset_before;
+    assert_within_threshold(
+        admin_usdf + admin_asset - admin_asset / 20,
+        first_value,
+        "First depositor should keep the unharvested gain",
+    );
+
+    let total_supply = stability_pool_vault_abi::total_supply(&vault).await.value;
+    assert_eq!(total_supply, Some(0));
+}
+
+#[tokio::test]
+async fn proper_vault_withdrawal_with_cooldown_enabled() {
+    let (contracts, admin, _wallets) = setup_protocol(4, false, false).await;
+    let admin_identity = Identity::Address(admin.address().into());
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        admin.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        6_000 * PRECISION,
+        3_000 * PRECISION,
+    )
+    .await;
+
+    let swap_adapter = deploy_mock_swap_adapter(&admin).await;
+    let vault = deploy_stability_pool_vault(
+        &admin,
+        &contracts.stability_pool.contract.contract_id().into(),
+        &contracts.usdf_asset_id,
+    )
+    .await;
+
+    stability_pool_vault_abi::initialize(&vault, admin_identity, swap_adapter.contract_id().into())
+        .await
+        .unwrap();
+
+    let deposit_amount = 1_500 * PRECISION;
+    let shares = stability_pool_vault_abi::deposit(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts,
+        deposit_amount,
+        admin_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    protocol_manager_abi::set_stability_pool_withdrawal_cooldown(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        3_600,
+        3_600,
+    )
+    .await
+    .unwrap();
+
+    // The vault cannot request withdrawals for its holders
+    stability_pool_vault_abi::withdraw(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+        shares,
+        admin_identity,
+    )
+    .await
+    .expect_err("Vault withdrawal should fail while the vault is not exempt");
+
+    let vault_identity = Identity::ContractId(vault.contract_id().into());
+    protocol_manager_abi::set_stability_pool_withdrawal_cooldown_exempt(
+        &contracts.protocol_manager,
+        &contracts.stability_pool,
+        vault_identity,
+        true,
+    )
+    .await
+    .unwrap();
+
+    let withdrawn = stability_pool_vault_abi::withdraw(
+        &vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts,
+        shares,
+        admin_identity,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    assert_eq!(withdrawn, deposit_amount);
+}
+
+#[tokio::test]
+async fn fails_unauthorized_vault_management() {
+    let (contracts, admin, mut wallets) = setup_protocol(4, false, false).await;
+    let attacker = wallets.pop().unwrap();
+
+    let swap_adapter = deploy_mock_swap_adapter(&admin).await;
+    let vault = deploy_stability_pool_vault(
+        &admin,
+        &contracts.stability_pool.contract.contract_id().into(),
+        &contracts.usdf_asset_id,
+    )
+    .await;
+
+    let attacker_vault = StabilityPoolVault::new(vault.contract_id().clone(), attacker.clone());
+
+    stability_pool_vault_abi::initialize(
+        &attacker_vault,
+        Identity::Address(attacker.address().into()),
+        swap_adapter.contract_id().into(),
+    )
+    .await
+    .expect_err("Able to initialize vault with unauthorized address");
+
+    stability_pool_vault_abi::initialize(
+        &vault,
+        Identity::Address(admin.address().into()),
+        swap_adapter.contract_id().into(),
+    )
+    .await
+    .unwrap();
+
+    stability_pool_vault_abi::set_swap_adapter(&attacker_vault, ContractId::zeroed())
+        .await
+        .expect_err("Able to set swap adapter with unauthorized address");
+
+    stability_pool_vault_abi::set_harvester(
+        &attacker_vault,
+        Identity::Address(attacker.address().into()),
+    )
+    .await
+    .expect_err("Able to set harvester with unauthorized address");
+
+    stability_pool_vault_abi::harvest(
+        &attacker_vault,
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &swap_adapter,
+        vec![contracts.asset_contracts[0].asset_id],
+        vec![0],
+    )
+    .await
+    .expect_err("Able to harvest with unauthorized address");
+}
@@ -33,6 +33,10 @@ abi TroveManager {
     fn get_trove_status(id: Identity) -> Status;
     #[storage(read, write)]
     fn add_asset(asset: AssetId, trove_manager: ContractId);
+    #[storage(read, write)]
+    fn set_max_size(size: u64);
+    #[storage(read, write)]
+    fn set_asset_max_size(asset: AssetId, size: u64);
 }
 use std::{
     asset::transfer,
@@ -106,6 +110,16 @@ impl TroveManager for Contract {
         require_caller_is_borrow_operations_contract();
         internal_close_trove(id, Status::ClosedByOwner, asset);
     }
+    #[storage(read, write)]
+    fn set_max_size(size: u64) {
+        let sorted_troves_contract = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+        sorted_troves_contract.set_max_size(size);
+    }
+    #[storage(read, write)]
+    fn set_asset_max_size(asset: AssetId, size: u64) {
+        let sorted_troves_contract = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+        sorted_troves_contract.set_asset_max_size(asset, size);
+    }
 }
 #[storage(read, write)]
 fn internal_close_trove(id: Identity, close_status: Status, asset: AssetId) {
@@ -60,6 +60,7 @@ pub struct LiquidationValues {
     pub coll_to_redistribute: u64,
     pub coll_surplus: u64,
     pub coll_gas_compensation: u64,
+    pub coll_treasury_fee: u64,
     pub is_partial_liquidation: bool,
     pub remaining_trove_coll: u64,
     pub remaining_trove_debt: u64,
@@ -75,6 +76,7 @@ impl LiquidationValues {
             coll_to_redistribute: 0,
             coll_surplus: 0,
             coll_gas_compensation: 0,
+            coll_treasury_fee: 0,
             is_partial_liquidation: false,
             remaining_trove_coll: 0,
             remaining_trove_debt: 0,
@@ -87,9 +89,11 @@ pub struct LiquidationTotals {
     pub total_debt_to_redistribute: u64,
     pub total_coll_to_redistribute: u64,
     pub total_coll_gas_compensation: u64,
+    pub total_coll_treasury_fee: u64,
     pub total_coll_surplus: u64,
     pub total_debt_in_sequence: u64,
     pub total_coll_in_sequence: u64,
+    pub total_bad_debt_to_redistribute: u64,
 }
 impl LiquidationTotals {
     pub fn default() -> Self {
@@ -99,9 +103,11 @@ impl LiquidationTotals {
             total_debt_to_redistribute: 0,
             total_coll_to_redistribute: 0,
             total_coll_gas_compensation: 0,
+            total_coll_treasury_fee: 0,
             total_coll_surplus: 0,
             total_debt_in_sequence: 0,
             total_coll_in_sequence: 0,
+            total_bad_debt_to_redistribute: 0,
         }
     }
 }
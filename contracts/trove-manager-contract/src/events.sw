@@ -12,9 +12,73 @@ pub struct TrovePartialLiquidationEvent {
     pub remaining_collateral: u64,
 }
 
+pub struct BatchLiquidationEvent {
+    pub asset: AssetId,
+    pub troves_liquidated: u64,
+    pub liquidated_debt: u64,
+    pub liquidated_collateral: u64,
+    pub debt_offset: u64,
+    pub debt_redistributed: u64,
+    pub debt_auctioned: u64,
+    pub coll_gas_compensation: u64,
+}
+
+pub struct AuctionStartedEvent {
+    pub auction_id: u64,
+    pub debt: u64,
+    pub collateral: u64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub start_time: u64,
+}
+
+pub struct AuctionBidEvent {
+    pub auction_id: u64,
+    pub bidder: Identity,
+    pub usdf_amount: u64,
+    pub collateral_amount: u64,
+    pub price: u64,
+}
+
+pub struct AuctionSettledEvent {
+    pub auction_id: u64,
+    pub redistributed_debt: u64,
+    pub redistributed_collateral: u64,
+}
+
 pub struct RedemptionEvent {
     pub borrower: Identity,
     pub usdf_amount: u64,
     pub collateral_amount: u64,
     pub collateral_price: u64,
 }
+
+pub struct BadDebtEvent {
+    pub borrower: Identity,
+    pub asset: AssetId,
+    pub deficit: u64,
+    pub covered_by_reserve: u64,
+}
+
+pub struct BadDebtCoveredEvent {
+    pub asset: AssetId,
+    pub stability_pool_amount: u64,
+    pub trove_amount: u64,
+    pub remaining_bad_debt: u64,
+}
+
+pub struct ReserveFundedEvent {
+    pub funder: Identity,
+    pub amount: u64,
+}
+
+pub struct AutoDeleverageEvent {
+    pub borrower: Identity,
+    pub asset: AssetId,
+    pub keeper: Identity,
+    pub debt_repaid: u64,
+    pub collateral_sold: u64,
+    pub keeper_fee: u64,
+    pub used_swap_adapter: bool,
+    pub new_icr: u64,
+}
@@ -15,7 +15,19 @@ use ::data_structures::{
     RedemptionTotals,
     Trove,
 };
-use ::events::{RedemptionEvent, TroveFullLiquidationEvent, TrovePartialLiquidationEvent,};
+use ::events::{
+    AuctionBidEvent,
+    AuctionSettledEvent,
+    AuctionStartedEvent,
+    AutoDeleverageEvent,
+    BadDebtCoveredEvent,
+    BadDebtEvent,
+    BatchLiquidationEvent,
+    RedemptionEvent,
+    ReserveFundedEvent,
+    TroveFullLiquidationEvent,
+    TrovePartialLiquidationEvent,
+};
 use standards::src3::SRC3;
 use libraries::trove_manager_interface::TroveManager;
 use libraries::usdf_token_interface::USDFToken;
@@ -25,7 +37,14 @@ use libraries::default_pool_interface::DefaultPool;
 use libraries::active_pool_interface::ActivePool;
 use libraries::coll_surplus_pool_interface::CollSurplusPool;
 use libraries::oracle_interface::Oracle;
+use libraries::swap_adapter_interface::SwapAdapter;
 use libraries::trove_manager_interface::data_structures::{
+    Auction,
+    AuctionConfig,
+    AutoDeleverageParams,
+    AutoDeleverageSettings,
+    LiquidationParams,
+    LiquidationSummary,
     RewardSnapshot,
     SingleRedemptionValues,
     Status,
@@ -51,7 +70,13 @@ use std::{
 configurable {
     /// Initializer identity
     INITIALIZER: Identity = Identity::Address(Address::zero()),
+    /// Allows overriding the auction clock for testing
+    DEBUG: bool = false,
 }
+// 0.5% of the sold collateral goes to the keeper
+const DEFAULT_KEEPER_FEE: u64 = 5_000_000;
+// 1% maximum discount to the oracle price when selling through the swap adapter
+const DEFAULT_MAX_SWAP_SLIPPAGE: u64 = 10_000_000;
 storage {
     protocol_manager_contract: ContractId = ContractId::zero(),
     sorted_troves_contract: ContractId = ContractId::zero(),
@@ -77,6 +102,46 @@ storage {
     lock_internal_close_trove: bool = false,
     lock_internal_batch_liquidate_troves: bool = false,
     lock_internal_redeem_collateral_from_trove: bool = false,
+    auction_config: AuctionConfig = AuctionConfig {
+        enabled: false,
+        duration: 0,
+        start_price_ratio: 0,
+        floor_price_ratio: 0,
+    },
+    auctions: StorageMap<u64, Auction> = StorageMap::<u64, Auction> {},
+    auction_count: u64 = 0,
+    // Collateral held in the active pool for running auctions, not owned by any trove
+    total_auction_collateral: u64 = 0,
+    debug_timestamp: u64 = 0,
+    liquidation_params: LiquidationParams = LiquidationParams {
+        post_collateral_ratio: POST_COLLATERAL_RATIO,
+        stability_pool_fee: STABILITY_POOL_FEE,
+        liquidator_fee: LIQUIDATOR_EXECUTION_GAS_FEE,
+        treasury_fee: 0,
+        treasury: Identity::Address(Address::zero()),
+    },
+    auto_deleverage_settings: StorageMap<Identity, AutoDeleverageSettings> = StorageMap::<Identity, AutoDeleverageSettings> {},
+    auto_deleverage_params: AutoDeleverageParams = AutoDeleverageParams {
+        keeper_fee: DEFAULT_KEEPER_FEE,
+        swap_adapter: ContractId::zero(),
+        max_slippage: DEFAULT_MAX_SWAP_SLIPPAGE,
+    },
+    lock_internal_auto_deleverage: bool = false,
+    // Shortfall of liquidations where the collateral was worth less than the debt, not yet covered by the reserve
+    bad_debt: u64 = 0,
+    // USDF held by the trove manager to cover bad debt
+    reserve_balance: u64 = 0,
+    // Share of the borrowing fees sent to the reserve instead of FPT stakers
+    reserve_fee_share: u64 = 0,
+    // Part of the bad debt absorbed by the stability pool, owed back to its depositors
+    stability_pool_bad_debt: u64 = 0,
+    // Part of the bad debt carried by redistributions, per unit staked, owed back to the troves that absorbed it
+    l_bad_debt: u64 = 0,
+    last_bad_debt_error_redistribution: u64 = 0,
+    bad_debt_snapshots: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    trove_bad_debt: StorageMap<Identity, u64> = StorageMap::<Identity, u64> {},
+    // Bad debt of the troves sent to an auction, redistributed with the auction residual when it settles
+    auction_bad_debt: StorageMap<u64, u64> = StorageMap::<u64, u64> {},
 }
 impl TroveManager for Contract {
     #[storage(read, write)]
@@ -265,7 +330,38 @@ impl TroveManager for Contract {
         upper_partial_hint: Identity,
         lower_partial_hint: Identity,
     ) {
-        internal_batch_liquidate_troves(borrowers, upper_partial_hint, lower_partial_hint);
+        let _ = internal_batch_liquidate_troves(
+            borrowers,
+            internal_repeat_partial_hints(upper_partial_hint, lower_partial_hint, borrowers.len()),
+            false,
+        );
+    }
+    #[storage(read, write)]
+    fn liquidate_troves(n: u64) -> LiquidationSummary {
+        let borrowers = internal_get_riskiest_troves(n);
+        require(
+            borrowers
+                .len() > 0,
+            "TroveManager: Nothing to liquidate",
+        );
+        internal_batch_liquidate_troves(
+            borrowers,
+            internal_repeat_partial_hints(
+                Identity::Address(Address::zero()),
+                Identity::Address(Address::zero()),
+                borrowers
+                    .len(),
+            ),
+            false,
+        )
+    }
+    #[storage(read, write)]
+    fn batch_liquidate_troves_with_hints(
+        borrowers: Vec<Identity>,
+        partial_hints: Vec<(Identity, Identity)>,
+        skip_failures: bool,
+    ) -> LiquidationSummary {
+        internal_batch_liquidate_troves(borrowers, partial_hints, skip_failures)
     }
     #[storage(read, write)]
     fn liquidate(
@@ -276,7 +372,234 @@ impl TroveManager for Contract {
         require_trove_is_active(id);
         let mut borrowers: Vec<Identity> = Vec::new();
         borrowers.push(id);
-        internal_batch_liquidate_troves(borrowers, upper_partial_hint, lower_partial_hint);
+        let _ = internal_batch_liquidate_troves(
+            borrowers,
+            internal_repeat_partial_hints(upper_partial_hint, lower_partial_hint, 1),
+            false,
+        );
+    }
+    #[storage(read, write)]
+    fn set_liquidation_params(params: LiquidationParams) {
+        require_caller_is_protocol_manager_contract();
+        require(
+            params
+                .post_collateral_ratio > MCR && params
+                .post_collateral_ratio > ONE + params
+                .stability_pool_fee,
+            "TroveManager: Invalid post collateral ratio",
+        );
+        require(
+            params
+                .liquidator_fee + params
+                .treasury_fee <= params
+                .stability_pool_fee,
+            "TroveManager: Fees exceed the liquidation penalty",
+        );
+        require(
+            params
+                .treasury_fee == 0 || params
+                .treasury != Identity::Address(Address::zero()),
+            "TroveManager: Treasury is not set",
+        );
+        storage.liquidation_params.write(params);
+    }
+    #[storage(read)]
+    fn get_liquidation_params() -> LiquidationParams {
+        storage.liquidation_params.read()
+    }
+    #[storage(read, write)]
+    fn set_auto_deleverage(id: Identity, settings: AutoDeleverageSettings) {
+        require_caller_is_borrow_operations_contract();
+        storage.auto_deleverage_settings.insert(id, settings);
+    }
+    #[storage(read)]
+    fn get_auto_deleverage(id: Identity) -> AutoDeleverageSettings {
+        storage
+            .auto_deleverage_settings
+            .get(id)
+            .try_read()
+            .unwrap_or(AutoDeleverageSettings::default())
+    }
+    #[storage(read, write)]
+    fn set_auto_deleverage_params(params: AutoDeleverageParams) {
+        require_caller_is_protocol_manager_contract();
+        // Keeps the deleverage target reachable for any trigger above the MCR
+        require(
+            ONE + params.keeper_fee < MCR,
+            "TroveManager: Keeper fee is too high",
+        );
+        require(
+            params
+                .max_slippage < DECIMAL_PRECISION,
+            "TroveManager: Invalid max slippage",
+        );
+        storage.auto_deleverage_params.write(params);
+    }
+    #[storage(read)]
+    fn get_auto_deleverage_params() -> AutoDeleverageParams {
+        storage.auto_deleverage_params.read()
+    }
+    // Keeper entry point, repays part of an opted-in trove's debt with its collateral once its ICR falls below the trigger
+    #[storage(read, write)]
+    fn auto_deleverage(id: Identity, upper_hint: Identity, lower_hint: Identity) -> u64 {
+        internal_auto_deleverage(id, upper_hint, lower_hint)
+    }
+    #[storage(read, write)]
+    fn set_auction_config(config: AuctionConfig) {
+        require_caller_is_protocol_manager_contract();
+        if config.enabled {
+            require(
+                config.duration > 0 && config.floor_price_ratio > 0 && config.floor_price_ratio <= config.start_price_ratio,
+                "TroveManager: Invalid auction config",
+            );
+        }
+        storage.auction_config.write(config);
+    }
+    #[storage(read)]
+    fn get_auction_config() -> AuctionConfig {
+        storage.auction_config.read()
+    }
+    #[storage(read)]
+    fn get_auction_count() -> u64 {
+        storage.auction_count.read()
+    }
+    #[storage(read)]
+    fn get_auction(auction_id: u64) -> Auction {
+        internal_get_auction(auction_id)
+    }
+    #[storage(read)]
+    fn get_auction_price(auction_id: u64) -> u64 {
+        internal_get_auction_price(internal_get_auction(auction_id), internal_get_current_time())
+    }
+    // Buys auctioned collateral at the current auction price, the USDF used is burned against the auction debt
+    // and any excess is returned to the bidder
+    #[storage(read, write), payable]
+    fn bid_on_auction(auction_id: u64) {
+        let usdf_asset_id = AssetId::new(storage.usdf_contract.read(), SubId::zero());
+        require(
+            msg_asset_id() == usdf_asset_id,
+            "TroveManager: Invalid asset being transfered",
+        );
+        let mut auction = internal_get_auction(auction_id);
+        require(!auction.is_settled, "TroveManager: Auction is settled");
+        let current_time = internal_get_current_time();
+        require(
+            current_time < auction.start_time + auction.duration,
+            "TroveManager: Auction has ended",
+        );
+        let price = internal_get_auction_price(auction, current_time);
+        let mut usdf_amount = fm_min(msg_amount(), auction.debt);
+        let mut collateral_amount = fm_multiply_ratio(usdf_amount, DECIMAL_PRECISION, price);
+        if collateral_amount > auction.collateral {
+            collateral_amount = auction.collateral;
+            usdf_amount = fm_min(
+                fm_multiply_ratio(collateral_amount, price, DECIMAL_PRECISION),
+                auction.debt,
+            );
+        }
+        require(
+            usdf_amount > 0 && collateral_amount > 0,
+            "TroveManager: Bid is too small",
+        );
+        let asset_contract_cache = storage.asset_contract.read();
+        let bidder = msg_sender().unwrap();
+        let usdf_contract = abi(SRC3, storage.usdf_contract.read().into());
+        let active_pool = abi(ActivePool, storage.active_pool_contract.read().into());
+        usdf_contract
+            .burn {
+                coins: usdf_amount,
+                asset_id: usdf_asset_id.bits(),
+            }(SubId::zero(), usdf_amount);
+        active_pool.decrease_usdf_debt(usdf_amount, asset_contract_cache);
+        active_pool.send_asset(bidder, collateral_amount, asset_contract_cache);
+        if msg_amount() > usdf_amount {
+            transfer(bidder, usdf_asset_id, msg_amount() - usdf_amount);
+        }
+        auction.debt -= usdf_amount;
+        auction.collateral -= collateral_amount;
+        storage
+            .total_auction_collateral
+            .write(storage.total_auction_collateral.read() - collateral_amount);
+        storage.auctions.insert(auction_id, auction);
+        log(AuctionBidEvent {
+            auction_id,
+            bidder,
+            usdf_amount,
+            collateral_amount,
+            price,
+        });
+        // Once the debt is covered or the collateral is sold out the leftover is settled right away
+        if auction.debt == 0 || auction.collateral == 0 {
+            internal_settle_auction(auction_id);
+        }
+    }
+    // Redistributes the residual shortfall and unsold collateral of an ended auction to the active troves
+    #[storage(read, write)]
+    fn settle_auction(auction_id: u64) {
+        let auction = internal_get_auction(auction_id);
+        require(!auction.is_settled, "TroveManager: Auction is settled");
+        require(
+            internal_get_current_time() >= auction.start_time + auction.duration,
+            "TroveManager: Auction is still active",
+        );
+        internal_settle_auction(auction_id);
+    }
+    // Adds USDF to the protocol reserve, outstanding bad debt is only covered through `cover_bad_debt`
+    #[storage(read, write), payable]
+    fn fund_reserve() {
+        require(
+            msg_asset_id() == AssetId::new(storage.usdf_contract.read(), SubId::zero()),
+            "TroveManager: Invalid asset being transfered",
+        );
+        require(msg_amount() > 0, "TroveManager: Amount must be greater than 0");
+        storage
+            .reserve_balance
+            .write(storage.reserve_balance.read() + msg_amount());
+        log(ReserveFundedEvent {
+            funder: msg_sender().unwrap(),
+            amount: msg_amount(),
+        });
+    }
+    // Pays outstanding bad debt back from the reserve, the stability pool first, then the given troves in order
+    #[storage(read, write)]
+    fn cover_bad_debt(borrowers: Vec<Identity>) {
+        require_caller_is_protocol_manager_contract();
+        internal_cover_bad_debt(borrowers);
+    }
+    #[storage(read)]
+    fn get_reserve_balance() -> u64 {
+        storage.reserve_balance.read()
+    }
+    #[storage(read)]
+    fn get_bad_debt() -> u64 {
+        storage.bad_debt.read()
+    }
+    #[storage(read)]
+    fn get_stability_pool_bad_debt() -> u64 {
+        storage.stability_pool_bad_debt.read()
+    }
+    #[storage(read)]
+    fn get_trove_bad_debt(id: Identity) -> u64 {
+        storage.trove_bad_debt.get(id).try_read().unwrap_or(0) + internal_get_pending_bad_debt(id)
+    }
+    #[storage(read, write)]
+    fn set_reserve_fee_share(share: u64) {
+        require_caller_is_protocol_manager_contract();
+        require(
+            share <= DECIMAL_PRECISION,
+            "TroveManager: Reserve fee share must be at most 100%",
+        );
+        storage.reserve_fee_share.write(share);
+    }
+    #[storage(read)]
+    fn get_reserve_fee_share() -> u64 {
+        storage.reserve_fee_share.read()
+    }
+    #[storage(write)]
+    fn set_debug_timestamp(timestamp: u64) {
+        // Allow setting a custom timestamp for testing, but only in debug mode
+        require(DEBUG, "TroveManager: Debug is not enabled");
+        storage.debug_timestamp.write(timestamp);
     }
     #[storage(read, write)]
     fn update_trove_reward_snapshots(id: Identity) {
@@ -299,6 +622,7 @@ fn internal_update_trove_reward_snapshots(id: Identity) {
         usdf_debt: storage.l_usdf.read(),
     };
     storage.reward_snapshots.insert(id, reward_snapshot);
+    storage.bad_debt_snapshots.insert(id, storage.l_bad_debt.read());
 }
 #[storage(read, write)]
 fn internal_apply_pending_rewards(borrower: Identity) {
@@ -310,6 +634,7 @@ fn internal_apply_pending_rewards(borrower: Identity) {
         trove.coll += pending_asset;
         trove.debt += pending_usdf;
         storage.troves.insert(borrower, trove);
+        internal_apply_pending_bad_debt(borrower);
         internal_update_trove_reward_snapshots(borrower);
         internal_move_pending_trove_rewards_to_active_pool(pending_asset, pending_usdf);
     }
@@ -345,6 +670,14 @@ fn internal_close_trove(id: Identity, close_status: Status) {
     rewards_snapshot.asset = 0;
     rewards_snapshot.usdf_debt = 0;
     storage.reward_snapshots.insert(id, rewards_snapshot);
+    // The trove repaid or was liquidated with the bad debt it absorbed, so nothing is owed back to it anymore
+    let trove_bad_debt = storage.trove_bad_debt.get(id).try_read().unwrap_or(0);
+    storage
+        .bad_debt
+        .write(storage.bad_debt.read() - fm_min(trove_bad_debt, storage.bad_debt.read()));
+    let _ = storage.trove_bad_debt.remove(id);
+    let _ = storage.bad_debt_snapshots.remove(id);
+    let _ = storage.auto_deleverage_settings.remove(id);
     internal_remove_trove_owner(id, trove_owner_array_length);
     sorted_troves.remove(id, asset_contract_cache);
     storage.lock_internal_close_trove.write(false);
@@ -377,9 +710,9 @@ fn require_trove_is_active(id: Identity) {
 #[storage(read, write)]
 fn internal_batch_liquidate_troves(
     borrowers: Vec<Identity>,
-    upper_partial_hint: Identity,
-    lower_partial_hint: Identity,
-) {
+    partial_hints: Vec<(Identity, Identity)>,
+    skip_failures: bool,
+) -> LiquidationSummary {
     // Prevent reentrancy
     require(
         storage
@@ -394,9 +727,19 @@ fn internal_batch_liquidate_troves(
             .len() > 0,
         "TroveManager: No borrowers to liquidate",
     );
+    require(
+        partial_hints
+            .len() == borrowers
+            .len(),
+        "TroveManager: Partial hints length mismatch",
+    );
     require_all_troves_unique(borrowers);
-    require_all_troves_are_active(borrowers);
-    require_all_troves_sorted_by_nicr(borrowers);
+    // When skipping failures, inactive and healthy troves are skipped instead of ending the sequence,
+    // so the batch does not need to be sorted
+    if !skip_failures {
+        require_all_troves_are_active(borrowers);
+        require_all_troves_sorted_by_nicr(borrowers);
+    }
 
     // Initialize local variables and contracts
     let mut vars = LocalVariablesOuterLiquidationFunction::default();
@@ -404,15 +747,20 @@ fn internal_batch_liquidate_troves(
     let asset_contract_cache = storage.asset_contract.read();
     vars.price = oracle.get_price();
     let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().into());
-    let total_usdf_in_sp = stability_pool.get_total_usdf_deposits();
+    let total_usdf_in_sp = stability_pool.get_total_usdf_deposits_for_asset(asset_contract_cache);
     // Calculate totals for the batch liquidation
-    let totals = internal_get_totals_from_batch_liquidate(
+    let (totals, summary) = internal_get_totals_from_batch_liquidate(
         vars.price,
         total_usdf_in_sp,
         borrowers,
-        upper_partial_hint,
-        lower_partial_hint,
+        partial_hints,
+        skip_failures,
     );
+    // Nothing was liquidatable, there is nothing to offset or redistribute
+    if skip_failures && totals.total_debt_in_sequence == 0 {
+        storage.lock_internal_batch_liquidate_troves.write(false);
+        return summary;
+    }
     // Ensure there is debt to liquidate
     require(
         totals
@@ -439,7 +787,29 @@ fn internal_batch_liquidate_troves(
             asset_contract_cache,
         );
     }
-    internal_update_system_snapshots_exclude_coll_remainder(totals.total_coll_gas_compensation);
+    // When enabled, the debt and collateral the stability pool couldn't absorb are auctioned
+    // instead of being redistributed to the active troves
+    let auction_config = storage.auction_config.read();
+    let debt_auctioned = if auction_config.enabled {
+        totals.total_debt_to_redistribute
+    } else {
+        0
+    };
+    if debt_auctioned > 0 {
+        internal_start_auction(
+            totals
+                .total_debt_to_redistribute,
+            totals
+                .total_coll_to_redistribute,
+            totals
+                .total_bad_debt_to_redistribute,
+            vars.price,
+            auction_config,
+        );
+    }
+    internal_update_system_snapshots_exclude_coll_remainder(
+        totals.total_coll_gas_compensation + totals.total_coll_treasury_fee,
+    );
     // Send gas compensation to the caller (liquidator)
     if (totals.total_coll_gas_compensation > 0) {
         active_pool.send_asset(
@@ -450,15 +820,441 @@ fn internal_batch_liquidate_troves(
             asset_contract_cache,
         );
     }
+    // Send the treasury cut of the liquidation penalty
+    if (totals.total_coll_treasury_fee > 0) {
+        active_pool.send_asset(
+            storage
+                .liquidation_params
+                .read()
+                .treasury,
+            totals
+                .total_coll_treasury_fee,
+            asset_contract_cache,
+        );
+    }
     // Redistribute remaining debt and collateral
-    internal_redistribute_debt_and_coll(
-        totals
-            .total_debt_to_redistribute,
-        totals
-            .total_coll_to_redistribute,
-    );
+    if debt_auctioned == 0 {
+        internal_redistribute_debt_and_coll(
+            totals
+                .total_debt_to_redistribute,
+            totals
+                .total_coll_to_redistribute,
+        );
+        internal_redistribute_bad_debt(totals.total_bad_debt_to_redistribute);
+    }
+    log(BatchLiquidationEvent {
+        asset: asset_contract_cache,
+        troves_liquidated: summary.liquidated.len() + summary.partially_liquidated.len(),
+        liquidated_debt: totals.total_debt_in_sequence,
+        liquidated_collateral: totals.total_coll_in_sequence,
+        debt_offset: totals.total_debt_to_offset,
+        debt_redistributed: totals.total_debt_to_redistribute - debt_auctioned,
+        debt_auctioned,
+        coll_gas_compensation: totals.total_coll_gas_compensation,
+    });
     // Release the reentrancy lock
     storage.lock_internal_batch_liquidate_troves.write(false);
+    return summary;
+}
+// Walks the sorted troves from the tail (lowest NICR) and collects up to n troves below MCR,
+// stopping at the first healthy trove. The last trove in the system can never be liquidated.
+#[storage(read)]
+fn internal_get_riskiest_troves(n: u64) -> Vec<Identity> {
+    let asset_contract_cache = storage.asset_contract.read();
+    let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+    let oracle = abi(Oracle, storage.oracle_contract.read().into());
+    let price = oracle.get_price();
+    let size = sorted_troves.get_size(asset_contract_cache);
+    let mut borrowers: Vec<Identity> = Vec::new();
+    let mut current_borrower = sorted_troves.get_last(asset_contract_cache);
+    while borrowers.len() < n && borrowers.len() + 1 < size && current_borrower != Identity::Address(Address::zero()) {
+        if internal_get_current_icr(current_borrower, price) >= MCR {
+            break;
+        }
+        borrowers.push(current_borrower);
+        current_borrower = sorted_troves.get_prev(current_borrower, asset_contract_cache);
+    }
+    return borrowers;
+}
+#[storage(read, write)]
+fn internal_auto_deleverage(borrower: Identity, upper_hint: Identity, lower_hint: Identity) -> u64 {
+    // Prevent reentrancy
+    require(
+        storage
+            .lock_internal_auto_deleverage
+            .read() == false,
+        "TroveManager: Internal auto deleverage is locked",
+    );
+    storage.lock_internal_auto_deleverage.write(true);
+    require_trove_is_active(borrower);
+    let settings = storage.auto_deleverage_settings.get(borrower).try_read().unwrap_or(AutoDeleverageSettings::default());
+    require(
+        settings
+            .trigger_icr > 0,
+        "TroveManager: Auto deleverage is not enabled",
+    );
+    let asset_contract_cache = storage.asset_contract.read();
+    let usdf_asset_id = AssetId::new(storage.usdf_contract.read(), SubId::zero());
+    let oracle = abi(Oracle, storage.oracle_contract.read().into());
+    let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().into());
+    let active_pool = abi(ActivePool, storage.active_pool_contract.read().into());
+    let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+    let price = oracle.get_price();
+    internal_apply_pending_rewards(borrower);
+    let mut trove = storage.troves.get(borrower).read();
+    let icr = fm_compute_cr(trove.coll, trove.debt, price);
+    require(
+        icr < settings
+            .trigger_icr,
+        "TroveManager: Trove is above the deleverage trigger",
+    );
+    require(icr >= MCR, "TroveManager: Trove can be liquidated");
+    let params = storage.auto_deleverage_params.read();
+    // Debt to repay so that (coll - sold - keeper fee) * price / (debt - repaid) = target, the sold collateral being worth the repaid debt
+    let numerator: U128 = U128::from(trove.debt) * U128::from(settings.target_icr) - U128::from(trove.coll) * U128::from(price);
+    let denominator: U128 = U128::from(settings.target_icr - ONE - params.keeper_fee);
+    let mut debt_to_repay = fm_min(
+        (numerator / denominator)
+            .as_u64()
+            .unwrap(),
+        trove.debt - MIN_NET_DEBT,
+    );
+    require(debt_to_repay > 0, "TroveManager: Nothing to deleverage");
+    let coll_sold = fm_multiply_ratio(debt_to_repay, DECIMAL_PRECISION, price);
+    let keeper_fee = fm_multiply_ratio(coll_sold, params.keeper_fee, DECIMAL_PRECISION);
+    let used_swap_adapter = stability_pool.get_total_usdf_deposits_for_asset(asset_contract_cache) < debt_to_repay;
+    if !used_swap_adapter {
+        // The stability pool buys the collateral at the oracle price
+        stability_pool.offset(debt_to_repay, coll_sold, asset_contract_cache);
+    } else {
+        require(
+            params
+                .swap_adapter != ContractId::zero(),
+            "TroveManager: Not enough liquidity to deleverage",
+        );
+        let this_contract = Identity::ContractId(ContractId::this());
+        active_pool.send_asset(this_contract, coll_sold, asset_contract_cache);
+        let swap_adapter = abi(SwapAdapter, params.swap_adapter.bits());
+        let usdf_out = swap_adapter.swap {
+            asset_id: asset_contract_cache.bits(),
+            coins: coll_sold,
+        }(
+            usdf_asset_id,
+            fm_multiply_ratio(
+                debt_to_repay,
+                DECIMAL_PRECISION - params.max_slippage,
+                DECIMAL_PRECISION,
+            ),
+            this_contract,
+        );
+        // Proceeds above the planned repayment also pay down debt as far as the minimum debt allows, the rest goes to the borrower
+        debt_to_repay = fm_min(usdf_out, trove.debt - MIN_NET_DEBT);
+        let usdf_contract = abi(SRC3, storage.usdf_contract.read().into());
+        usdf_contract
+            .burn {
+                coins: debt_to_repay,
+                asset_id: usdf_asset_id.bits(),
+            }(SubId::zero(), debt_to_repay);
+        active_pool.decrease_usdf_debt(debt_to_repay, asset_contract_cache);
+        if usdf_out > debt_to_repay {
+            transfer(borrower, usdf_asset_id, usdf_out - debt_to_repay);
+        }
+    }
+    let keeper = msg_sender().unwrap();
+    active_pool.send_asset(keeper, keeper_fee, asset_contract_cache);
+    trove.coll -= coll_sold + keeper_fee;
+    trove.debt -= debt_to_repay;
+    storage.troves.insert(borrower, trove);
+    let new_icr = fm_compute_cr(trove.coll, trove.debt, price);
+    require(
+        new_icr > icr,
+        "TroveManager: Deleverage did not improve the trove",
+    );
+    let _ = internal_update_stake_and_total_stakes(borrower);
+    sorted_troves.re_insert(
+        borrower,
+        fm_compute_nominal_cr(trove.coll, trove.debt),
+        upper_hint,
+        lower_hint,
+        asset_contract_cache,
+    );
+    log(AutoDeleverageEvent {
+        borrower,
+        asset: asset_contract_cache,
+        keeper,
+        debt_repaid: debt_to_repay,
+        collateral_sold: coll_sold,
+        keeper_fee,
+        used_swap_adapter,
+        new_icr,
+    });
+    storage.lock_internal_auto_deleverage.write(false);
+    return debt_to_repay;
+}
+// Covers what it can of a liquidation shortfall from the reserve before the debt is offset or redistributed,
+// the rest is recorded as bad debt. Returns the amount covered
+#[storage(read, write)]
+fn internal_record_bad_debt(borrower: Identity, deficit: u64) -> u64 {
+    let covered_by_reserve = fm_min(deficit, storage.reserve_balance.read());
+    if covered_by_reserve > 0 {
+        internal_burn_reserve(covered_by_reserve);
+        let active_pool = abi(ActivePool, storage.active_pool_contract.read().into());
+        active_pool.decrease_usdf_debt(covered_by_reserve, storage.asset_contract.read());
+    }
+    storage
+        .bad_debt
+        .write(storage.bad_debt.read() + deficit - covered_by_reserve);
+    log(BadDebtEvent {
+        borrower,
+        asset: storage.asset_contract.read(),
+        deficit,
+        covered_by_reserve,
+    });
+    return covered_by_reserve;
+}
+// Pays outstanding bad debt back from the reserve to whoever absorbed it. The stability pool's part goes
+// back into the deposits, the troves' part is taken off their debt
+#[storage(read, write)]
+fn internal_cover_bad_debt(borrowers: Vec<Identity>) {
+    let asset_contract_cache = storage.asset_contract.read();
+    let stability_pool = abi(StabilityPool, storage.stability_pool_contract.read().into());
+    let mut stability_pool_amount = 0;
+    if stability_pool.get_total_usdf_deposits_for_asset(asset_contract_cache) > 0 {
+        stability_pool_amount = fm_min(
+            storage
+                .stability_pool_bad_debt
+                .read(),
+            storage
+                .reserve_balance
+                .read(),
+        );
+    }
+    if stability_pool_amount > 0 {
+        stability_pool
+            .restore_deposits {
+                coins: stability_pool_amount,
+                asset_id: AssetId::new(storage.usdf_contract.read(), SubId::zero()).bits(),
+            }(asset_contract_cache);
+        storage
+            .reserve_balance
+            .write(storage.reserve_balance.read() - stability_pool_amount);
+        storage
+            .stability_pool_bad_debt
+            .write(storage.stability_pool_bad_debt.read() - stability_pool_amount);
+        storage
+            .bad_debt
+            .write(storage.bad_debt.read() - stability_pool_amount);
+    }
+    let mut trove_amount = 0;
+    let mut i = 0;
+    while i < borrowers.len() && storage.reserve_balance.read() > 0 {
+        let borrower = borrowers.get(i).unwrap();
+        if storage.troves.get(borrower).try_read().unwrap_or(Trove::default()).status == Status::Active {
+            trove_amount += internal_cover_trove_bad_debt(borrower);
+        }
+        i += 1;
+    }
+    log(BadDebtCoveredEvent {
+        asset: asset_contract_cache,
+        stability_pool_amount,
+        trove_amount,
+        remaining_bad_debt: storage.bad_debt.read(),
+    });
+}
+// Takes the bad debt the trove absorbed through redistributions off its debt, returns the relief burned from the reserve
+#[storage(read, write)]
+fn internal_cover_trove_bad_debt(borrower: Identity) -> u64 {
+    internal_apply_pending_rewards(borrower);
+    let owed = storage.trove_bad_debt.get(borrower).try_read().unwrap_or(0);
+    if owed == 0 {
+        return 0;
+    }
+    let mut trove = storage.troves.get(borrower).read();
+    // The trove keeps at least the minimum debt, relief above that stays in the reserve
+    let max_relief = if trove.debt > MIN_NET_DEBT {
+        fm_min(owed, trove.debt - MIN_NET_DEBT)
+    } else {
+        0
+    };
+    let relief = fm_min(max_relief, storage.reserve_balance.read());
+    // Only a short reserve leaves the trove owed the rest
+    let settled = if relief < max_relief { relief } else { owed };
+    if relief > 0 {
+        let asset_contract_cache = storage.asset_contract.read();
+        internal_burn_reserve(relief);
+        let active_pool = abi(ActivePool, storage.active_pool_contract.read().into());
+        active_pool.decrease_usdf_debt(relief, asset_contract_cache);
+        trove.debt -= relief;
+        storage.troves.insert(borrower, trove);
+        let sorted_troves = abi(SortedTroves, storage.sorted_troves_contract.read().into());
+        sorted_troves.re_insert(
+            borrower,
+            fm_compute_nominal_cr(trove.coll, trove.debt),
+            Identity::Address(Address::zero()),
+            Identity::Address(Address::zero()),
+            asset_contract_cache,
+        );
+    }
+    storage.trove_bad_debt.insert(borrower, owed - settled);
+    storage
+        .bad_debt
+        .write(storage.bad_debt.read() - fm_min(settled, storage.bad_debt.read()));
+    return relief;
+}
+#[storage(read, write)]
+fn internal_burn_reserve(amount: u64) {
+    let usdf_contract = abi(SRC3, storage.usdf_contract.read().into());
+    usdf_contract
+        .burn {
+            coins: amount,
+            asset_id: AssetId::new(storage.usdf_contract.read(), SubId::zero()).bits(),
+        }(SubId::zero(), amount);
+    storage
+        .reserve_balance
+        .write(storage.reserve_balance.read() - amount);
+}
+// Spreads the uncovered shortfall carried by a redistribution over the troves that absorb it, like the debt itself
+#[storage(read, write)]
+fn internal_redistribute_bad_debt(amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    let total_stakes = storage.total_stakes.read();
+    let bad_debt_numerator: U128 = U128::from(amount) * U128::from(DECIMAL_PRECISION) + U128::from(storage.last_bad_debt_error_redistribution.read());
+    let bad_debt_per_unit_staked = bad_debt_numerator / U128::from(total_stakes);
+    storage
+        .last_bad_debt_error_redistribution
+        .write(
+            (bad_debt_numerator - (bad_debt_per_unit_staked * U128::from(total_stakes)))
+                .as_u64()
+                .unwrap(),
+        );
+    storage
+        .l_bad_debt
+        .write(storage.l_bad_debt.read() + bad_debt_per_unit_staked.as_u64().unwrap());
+}
+// Records the bad debt the trove absorbed since its last snapshot as owed back to it
+#[storage(read, write)]
+fn internal_apply_pending_bad_debt(borrower: Identity) {
+    let pending_bad_debt = internal_get_pending_bad_debt(borrower);
+    if pending_bad_debt > 0 {
+        storage
+            .trove_bad_debt
+            .insert(
+                borrower,
+                storage
+                    .trove_bad_debt
+                    .get(borrower)
+                    .try_read()
+                    .unwrap_or(0) + pending_bad_debt,
+            );
+    }
+    storage.bad_debt_snapshots.insert(borrower, storage.l_bad_debt.read());
+}
+#[storage(read, write)]
+fn internal_start_auction(
+    debt: u64,
+    collateral: u64,
+    bad_debt: u64,
+    price: u64,
+    config: AuctionConfig,
+) {
+    let auction_id = storage.auction_count.read();
+    let auction = Auction {
+        collateral,
+        debt,
+        start_price: fm_multiply_ratio(price, config.start_price_ratio, DECIMAL_PRECISION),
+        floor_price: fm_multiply_ratio(price, config.floor_price_ratio, DECIMAL_PRECISION),
+        start_time: internal_get_current_time(),
+        duration: config.duration,
+        is_settled: false,
+    };
+    storage.auctions.insert(auction_id, auction);
+    storage.auction_count.write(auction_id + 1);
+    if bad_debt > 0 {
+        storage.auction_bad_debt.insert(auction_id, bad_debt);
+    }
+    storage
+        .total_auction_collateral
+        .write(storage.total_auction_collateral.read() + collateral);
+    log(AuctionStartedEvent {
+        auction_id,
+        debt,
+        collateral,
+        start_price: auction.start_price,
+        floor_price: auction.floor_price,
+        start_time: auction.start_time,
+    });
+}
+#[storage(read, write)]
+fn internal_settle_auction(auction_id: u64) {
+    let mut auction = storage.auctions.get(auction_id).read();
+    storage
+        .total_auction_collateral
+        .write(storage.total_auction_collateral.read() - auction.collateral);
+    // Bids above the collateral's value at liquidation recovered part of the shortfall, the rest is redistributed
+    let auction_bad_debt = storage.auction_bad_debt.get(auction_id).try_read().unwrap_or(0);
+    let bad_debt_to_redistribute = fm_min(auction_bad_debt, auction.debt);
+    storage
+        .bad_debt
+        .write(storage.bad_debt.read() - fm_min(auction_bad_debt - bad_debt_to_redistribute, storage.bad_debt.read()));
+    let _ = storage.auction_bad_debt.remove(auction_id);
+    internal_redistribute_debt_and_coll(auction.debt, auction.collateral);
+    internal_redistribute_bad_debt(bad_debt_to_redistribute);
+    log(AuctionSettledEvent {
+        auction_id,
+        redistributed_debt: auction.debt,
+        redistributed_collateral: auction.collateral,
+    });
+    auction.debt = 0;
+    auction.collateral = 0;
+    auction.is_settled = true;
+    storage.auctions.insert(auction_id, auction);
+}
+#[storage(read)]
+fn internal_get_auction(auction_id: u64) -> Auction {
+    let auction = storage.auctions.get(auction_id).try_read();
+    require(auction.is_some(), "TroveManager: Auction does not exist");
+    auction.unwrap()
+}
+// The price decreases linearly from the start price to the floor price over the auction duration
+fn internal_get_auction_price(auction: Auction, current_time: u64) -> u64 {
+    if current_time <= auction.start_time {
+        return auction.start_price;
+    }
+    let elapsed = current_time - auction.start_time;
+    if elapsed >= auction.duration {
+        return auction.floor_price;
+    }
+    return auction.start_price - fm_multiply_ratio(
+        auction
+            .start_price - auction
+            .floor_price,
+        elapsed,
+        auction
+            .duration,
+    );
+}
+#[storage(read)]
+fn internal_get_current_time() -> u64 {
+    match DEBUG {
+        true => storage.debug_timestamp.read(),
+        false => timestamp(),
+    }
+}
+// Uses the same partial hints for every trove of a batch
+fn internal_repeat_partial_hints(
+    upper_partial_hint: Identity,
+    lower_partial_hint: Identity,
+    count: u64,
+) -> Vec<(Identity, Identity)> {
+    let mut partial_hints: Vec<(Identity, Identity)> = Vec::new();
+    let mut i = 0;
+    while i < count {
+        partial_hints.push((upper_partial_hint, lower_partial_hint));
+        i += 1;
+    }
+    return partial_hints;
 }
 #[storage(read)]
 fn require_caller_is_borrow_operations_contract() {
@@ -521,26 +1317,46 @@ fn internal_get_totals_from_batch_liquidate(
     price: u64,
     usdf_in_stability_pool: u64,
     borrowers: Vec<Identity>,
-    upper_partial_hint: Identity,
-    lower_partial_hint: Identity,
-) -> LiquidationTotals {
+    partial_hints: Vec<(Identity, Identity)>,
+    skip_failures: bool,
+) -> (LiquidationTotals, LiquidationSummary) {
     // Initialize variables for the liquidation sequence
     let mut vars = LocalVariablesLiquidationSequence::default();
     vars.remaining_usdf_in_stability_pool = usdf_in_stability_pool;
     let mut single_liquidation = LiquidationValues::default();
     let mut i = 0;
     let mut totals = LiquidationTotals::default();
+    let mut summary = LiquidationSummary::default();
+    let params = storage.liquidation_params.read();
     // Iterate through the list of borrowers
     while i < borrowers.len() {
         vars.borrower = borrowers.get(i).unwrap();
+        let (upper_partial_hint, lower_partial_hint) = partial_hints.get(i).unwrap();
+        // Inactive troves can only be part of the batch when skipping failures
+        let status = storage.troves.get(vars.borrower).try_read().unwrap_or(Trove::default()).status;
+        if status != Status::Active {
+            summary.skipped.push(vars.borrower);
+            i += 1;
+            continue;
+        }
         // Calculate the Individual Collateralization Ratio (ICR) for the current borrower
         vars.icr = internal_get_current_icr(vars.borrower, price);
         // If the trove is undercollateralized (ICR < Minimum Collateralization Ratio), liquidate it
         if vars.icr < MCR {
             // Get the entire debt and collateral for the trove
-            let position = internal_get_entire_debt_and_coll(vars.borrower);
+            let mut position = internal_get_entire_debt_and_coll(vars.borrower);
             // Move any pending rewards to the active pool before liquidation
             internal_move_pending_trove_rewards_to_active_pool(position.pending_coll_rewards, position.pending_debt_rewards);
+            internal_apply_pending_bad_debt(vars.borrower);
+            // Cover the shortfall from the reserve if the collateral is worth less than the debt
+            let collateral_value = fm_multiply_ratio(position.entire_trove_coll, price, DECIMAL_PRECISION);
+            let mut uncovered_bad_debt = 0;
+            if collateral_value < position.entire_trove_debt {
+                let deficit = position.entire_trove_debt - collateral_value;
+                let covered_by_reserve = internal_record_bad_debt(vars.borrower, deficit);
+                position.entire_trove_debt -= covered_by_reserve;
+                uncovered_bad_debt = deficit - covered_by_reserve;
+            }
             // Calculate the values for offsetting debt and redistributing collateral
             single_liquidation = get_offset_and_redistribution_vals(
                 position
@@ -549,6 +1365,7 @@ fn internal_get_totals_from_batch_liquidate(
                     .entire_trove_debt,
                 vars.remaining_usdf_in_stability_pool,
                 price,
+                params,
             );
             // Apply the liquidation to the trove
             internal_apply_liquidation(
@@ -561,14 +1378,40 @@ fn internal_get_totals_from_batch_liquidate(
             vars.remaining_usdf_in_stability_pool -= single_liquidation.debt_to_offset;
             // Add the results of this liquidation to the running totals
             totals = add_liquidation_vals_to_totals(totals, single_liquidation);
+            // The uncovered shortfall is owed to whoever absorbs the debt, split the same way as the debt
+            if uncovered_bad_debt > 0 {
+                let offset_bad_debt = fm_multiply_ratio(
+                    uncovered_bad_debt,
+                    single_liquidation
+                        .debt_to_offset,
+                    position
+                        .entire_trove_debt,
+                );
+                storage
+                    .stability_pool_bad_debt
+                    .write(storage.stability_pool_bad_debt.read() + offset_bad_debt);
+                totals.total_bad_debt_to_redistribute += uncovered_bad_debt - offset_bad_debt;
+            }
+            if single_liquidation.is_partial_liquidation {
+                summary.partially_liquidated.push(vars.borrower);
+            } else {
+                summary.liquidated.push(vars.borrower);
+            }
+        } else if skip_failures {
+            summary.skipped.push(vars.borrower);
         } else {
             // If we've reached a trove that's not undercollateralized, we can stop the liquidation process
+            // The remaining troves are healthier since the batch is sorted by nominal ICR
+            while i < borrowers.len() {
+                summary.skipped.push(borrowers.get(i).unwrap());
+                i += 1;
+            }
             break;
         }
         i += 1;
     }
     // Return the total results of all liquidations performed
-    return totals;
+    return (totals, summary);
 }
 #[storage(read)]
 fn require_more_than_one_trove_in_system(
@@ -677,7 +1520,7 @@ fn internal_apply_liquidation(
 #[storage(read, write)]
 fn internal_redistribute_debt_and_coll(debt: u64, coll: u64) {
     let asset_contract_cache = storage.asset_contract.read();
-    if (debt == 0) {
+    if (debt == 0 && coll == 0) {
         return;
     }
     let asset_numerator: U128 = U128::from(coll) * U128::from(DECIMAL_PRECISION) + U128::from(storage.last_asset_error_redistribution.read());
@@ -750,12 +1593,24 @@ fn internal_get_pending_usdf_reward(address: Identity) -> u64 {
     return pending_usdf_reward;
 }
 #[storage(read)]
+fn internal_get_pending_bad_debt(address: Identity) -> u64 {
+    let snapshot_bad_debt = storage.bad_debt_snapshots.get(address).try_read().unwrap_or(0);
+    let bad_debt_per_unit_staked = storage.l_bad_debt.read() - snapshot_bad_debt;
+    if (bad_debt_per_unit_staked == 0
+        || storage.troves.get(address).try_read().unwrap_or(Trove::default()).status != Status::Active)
+    {
+        return 0;
+    }
+    let stake = storage.troves.get(address).read().stake;
+    return fm_multiply_ratio(bad_debt_per_unit_staked, stake, DECIMAL_PRECISION);
+}
+#[storage(read)]
 fn internal_has_pending_rewards(address: Identity) -> bool {
     if (storage.troves.get(address).read().status != Status::Active)
     {
         return false;
     }
-    return (storage.reward_snapshots.get(address).read().asset < storage.l_asset.read());
+    return (storage.reward_snapshots.get(address).read().asset < storage.l_asset.read()) || (storage.bad_debt_snapshots.get(address).try_read().unwrap_or(0) < storage.l_bad_debt.read());
 }
 #[storage(read)]
 fn internal_move_pending_trove_rewards_to_active_pool(coll: u64, debt: u64) {
@@ -975,7 +1830,8 @@ fn internal_update_system_snapshots_exclude_coll_remainder(coll_remainder: u64)
     let active_pool_coll = active_pool.get_asset(storage.asset_contract.read());
     let liquidated_coll = default_pool.get_asset(storage.asset_contract.read());
 
+    // Collateral of running auctions sits in the active pool but doesn't belong to any trove
     storage
         .total_collateral_snapshot
-        .write(active_pool_coll - coll_remainder + liquidated_coll);
+        .write(active_pool_coll - coll_remainder - storage.total_auction_collateral.read() + liquidated_coll);
 }
@@ -1,4 +1,8 @@
 pub mod failure;
+pub mod success_auction_liquidations;
+pub mod success_auto_deleverage;
+pub mod success_bad_debt_liquidations;
 pub mod success_batch_liquidations;
 pub mod success_full_liquidations;
 pub mod success_partial_liquidations;
+pub mod success_price_path_liquidations;
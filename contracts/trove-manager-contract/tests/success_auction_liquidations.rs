@@ -0,0 +1,294 @@
+use fuels::prelude::*;
+use fuels::types::Identity;
+use test_utils::{
+    data_structures::{ContractInstance, PRECISION},
+    interfaces::{
+        borrow_operations::borrow_operations_utils,
+        default_pool::default_pool_abi,
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, AuctionConfig},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        trove_manager::{trove_manager_abi, trove_manager_utils, Status, TroveManagerContract},
+    },
+    setup::common::setup_protocol,
+    utils::with_min_borrow_fee,
+};
+
+const AUCTION_START_TIME: u64 = 1_000;
+const AUCTION_DURATION: u64 = 3_600;
+
+#[tokio::test]
+async fn proper_auction_pricing_and_settlement_with_empty_stability_pool() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    // Floor price above the start price is rejected
+    let res = protocol_manager_abi::set_liquidation_auction_config(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        AuctionConfig {
+            enabled: true,
+            duration: AUCTION_DURATION,
+            start_price_ratio: 800_000_000,
+            floor_price_ratio: 1_100_000_000,
+        },
+    )
+    .await;
+    assert!(res.is_err(), "Invalid auction config should be rejected");
+
+    protocol_manager_abi::set_liquidation_auction_config(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        AuctionConfig {
+            enabled: true,
+            duration: AUCTION_DURATION,
+            start_price_ratio: 1_100_000_000,
+            floor_price_ratio: 800_000_000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let config = trove_manager_abi::get_auction_config(trove_manager)
+        .await
+        .value;
+    assert!(config.enabled);
+    assert_eq!(config.duration, AUCTION_DURATION);
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+    let bidder_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        bidder_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+    trove_manager_abi::set_debug_timestamp(trove_manager, AUCTION_START_TIME).await;
+
+    // The stability pool is empty, so the whole trove goes to auction instead of being redistributed
+    trove_manager_abi::liquidate(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    trove_manager_utils::assert_trove_status(
+        trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+        Status::ClosedByLiquidation,
+    )
+    .await;
+
+    let auction_count = trove_manager_abi::get_auction_count(trove_manager)
+        .await
+        .value;
+    assert_eq!(auction_count, 1);
+
+    let auction_debt = with_min_borrow_fee(1_000 * PRECISION);
+    let auction_collateral = 1_100 * PRECISION - 1_100 * PRECISION / 200;
+
+    let auction = trove_manager_abi::get_auction(trove_manager, 0).await.value;
+    assert_eq!(auction.debt, auction_debt);
+    assert_eq!(auction.collateral, auction_collateral);
+    assert_eq!(auction.start_price, 1_100_000_000);
+    assert_eq!(auction.floor_price, 800_000_000);
+    assert_eq!(auction.start_time, AUCTION_START_TIME);
+    assert!(!auction.is_settled);
+
+    // Nothing is redistributed while the auction is running
+    let default_pool_debt = default_pool_abi::get_usdf_debt(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(default_pool_debt, 0);
+    trove_manager_utils::assert_pending_usdf_rewards(
+        trove_manager,
+        Identity::Address(healthy_wallet.address().into()),
+        0,
+    )
+    .await;
+
+    // Price decreases linearly from the start price to the floor price
+    let price = trove_manager_abi::get_auction_price(trove_manager, 0)
+        .await
+        .value;
+    assert_eq!(price, 1_100_000_000);
+
+    trove_manager_abi::set_debug_timestamp(
+        trove_manager,
+        AUCTION_START_TIME + AUCTION_DURATION / 2,
+    )
+    .await;
+    let price = trove_manager_abi::get_auction_price(trove_manager, 0)
+        .await
+        .value;
+    assert_eq!(price, 950_000_000);
+
+    let res = trove_manager_abi::settle_auction(
+        trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        0,
+    )
+    .await;
+    assert!(res.is_err(), "Auction should not settle before it ends");
+
+    let trove_manager_bidder = ContractInstance::new(
+        TroveManagerContract::new(
+            trove_manager.contract.contract_id().clone(),
+            bidder_wallet.clone(),
+        ),
+        trove_manager.implementation_id,
+    );
+
+    let provider = bidder_wallet.provider().unwrap();
+    let asset_balance_before = provider
+        .get_asset_balance(bidder_wallet.address().into(), asset_id)
+        .await
+        .unwrap();
+
+    let bid_amount = 500 * PRECISION;
+    trove_manager_abi::bid_on_auction(
+        &trove_manager_bidder,
+        &contracts.usdf,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        0,
+        bid_amount,
+    )
+    .await
+    .unwrap();
+
+    let asset_balance_after = provider
+        .get_asset_balance(bidder_wallet.address().into(), asset_id)
+        .await
+        .unwrap();
+    let collateral_bought = (bid_amount as u128 * PRECISION as u128 / 950_000_000) as u64;
+    assert_eq!(
+        asset_balance_after - asset_balance_before,
+        collateral_bought
+    );
+
+    let auction = trove_manager_abi::get_auction(trove_manager, 0).await.value;
+    assert_eq!(auction.debt, auction_debt - bid_amount);
+    assert_eq!(auction.collateral, auction_collateral - collateral_bought);
+
+    // Once the auction ends the residual shortfall is redistributed
+    trove_manager_abi::set_debug_timestamp(trove_manager, AUCTION_START_TIME + AUCTION_DURATION)
+        .await;
+    let price = trove_manager_abi::get_auction_price(trove_manager, 0)
+        .await
+        .value;
+    assert_eq!(price, 800_000_000);
+
+    let res = trove_manager_abi::bid_on_auction(
+        &trove_manager_bidder,
+        &contracts.usdf,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        0,
+        bid_amount,
+    )
+    .await;
+    assert!(res.is_err(), "Bids after the auction ended should fail");
+
+    trove_manager_abi::settle_auction(
+        trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        0,
+    )
+    .await
+    .unwrap();
+
+    let auction = trove_manager_abi::get_auction(trove_manager, 0).await.value;
+    assert!(auction.is_settled);
+    assert_eq!(auction.debt, 0);
+    assert_eq!(auction.collateral, 0);
+
+    let default_pool_debt = default_pool_abi::get_usdf_debt(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(default_pool_debt, auction_debt - bid_amount);
+
+    let default_pool_asset = default_pool_abi::get_asset(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(default_pool_asset, auction_collateral - collateral_bought);
+
+    let res = trove_manager_abi::settle_auction(
+        trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        0,
+    )
+    .await;
+    assert!(res.is_err(), "Auction can only be settled once");
+}
@@ -0,0 +1,476 @@
+use fuels::prelude::*;
+use fuels::types::Identity;
+use test_utils::{
+    data_structures::{ContractInstance, PRECISION},
+    interfaces::{
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, AutoDeleverageParams},
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        stability_pool::{stability_pool_abi, StabilityPool},
+        swap_adapter::swap_adapter_abi,
+        trove_manager::{trove_manager_abi, TroveManagerContract},
+    },
+    setup::common::{assert_within_threshold, deploy_mock_swap_adapter, setup_protocol},
+    utils::{calculate_icr, with_min_borrow_fee},
+};
+
+const TRIGGER_ICR: u64 = 1_600_000_000;
+const TARGET_ICR: u64 = 2_000_000_000;
+const KEEPER_FEE: u64 = 5_000_000;
+
+// Mirrors the trove manager, the repaid debt brings the trove back to the target ICR at the oracle price
+fn expected_debt_to_repay(coll: u64, debt: u64, price: u64) -> u64 {
+    let numerator = debt as u128 * TARGET_ICR as u128 - coll as u128 * price as u128;
+    let denominator = (TARGET_ICR - PRECISION - KEEPER_FEE) as u128;
+    (numerator / denominator) as u64
+}
+
+#[tokio::test]
+async fn proper_auto_deleverage_against_stability_pool() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let deleveraged_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+    let keeper_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        deleveraged_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        3_000 * PRECISION,
+        2_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let borrow_operations_deleveraged_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            deleveraged_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    // The trigger has to sit above the MCR
+    let res = borrow_operations_abi::set_auto_deleverage(
+        &borrow_operations_deleveraged_wallet,
+        trove_manager,
+        asset_id,
+        1_300_000_000,
+        TARGET_ICR,
+    )
+    .await;
+    assert!(res.is_err(), "Trigger below the MCR should be rejected");
+
+    borrow_operations_abi::set_auto_deleverage(
+        &borrow_operations_deleveraged_wallet,
+        trove_manager,
+        asset_id,
+        TRIGGER_ICR,
+        TARGET_ICR,
+    )
+    .await
+    .unwrap();
+
+    let settings = trove_manager_abi::get_auto_deleverage(
+        trove_manager,
+        Identity::Address(deleveraged_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(settings.trigger_icr, TRIGGER_ICR);
+    assert_eq!(settings.target_icr, TARGET_ICR);
+
+    let trove_manager_keeper = ContractInstance::new(
+        TroveManagerContract::new(
+            trove_manager.contract.contract_id().clone(),
+            keeper_wallet.clone(),
+        ),
+        trove_manager.implementation_id,
+    );
+
+    // ICR of 1500% is above the trigger
+    let res = trove_manager_abi::auto_deleverage(
+        &trove_manager_keeper,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        None,
+        Identity::Address(deleveraged_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Trove above the trigger should not be deleveraged"
+    );
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    // Troves that did not opt in can't be deleveraged
+    let res = trove_manager_abi::auto_deleverage(
+        &trove_manager_keeper,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        None,
+        Identity::Address(healthy_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Trove without auto deleverage should be rejected"
+    );
+
+    let provider = keeper_wallet.provider().unwrap();
+    let keeper_balance_before = provider
+        .get_asset_balance(keeper_wallet.address(), asset_id)
+        .await
+        .unwrap();
+
+    // ICR of ~149% is below the trigger but above the MCR
+    trove_manager_abi::auto_deleverage(
+        &trove_manager_keeper,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        None,
+        Identity::Address(deleveraged_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let debt = with_min_borrow_fee(2_000 * PRECISION);
+    let coll = 3_000 * PRECISION;
+    let debt_repaid = expected_debt_to_repay(coll, debt, PRECISION);
+    let coll_sold = debt_repaid;
+    let keeper_fee = (coll_sold as u128 * KEEPER_FEE as u128 / PRECISION as u128) as u64;
+
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(deleveraged_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_debt, debt - debt_repaid);
+
+    let trove_coll = trove_manager_abi::get_trove_coll(
+        trove_manager,
+        Identity::Address(deleveraged_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_coll, coll - coll_sold - keeper_fee);
+
+    assert_within_threshold(
+        calculate_icr(trove_coll, trove_debt),
+        TARGET_ICR,
+        "Deleveraged trove should be back at the target ICR",
+    );
+
+    let keeper_balance_after = provider
+        .get_asset_balance(keeper_wallet.address(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(keeper_balance_after - keeper_balance_before, keeper_fee);
+
+    // The stability pool bought the sold collateral at the oracle price
+    let stability_pool_asset = stability_pool_abi::get_asset(&contracts.stability_pool, asset_id)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(stability_pool_asset, coll_sold);
+
+    let deposits = stability_pool_abi::get_total_usdf_deposits(&contracts.stability_pool)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(deposits, 5_000 * PRECISION - debt_repaid);
+
+    // Back above the trigger, a second call is rejected
+    let res = trove_manager_abi::auto_deleverage(
+        &trove_manager_keeper,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        None,
+        Identity::Address(deleveraged_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert!(
+        res.is_err(),
+        "Deleveraged trove should be above the trigger"
+    );
+}
+
+#[tokio::test]
+async fn proper_auto_deleverage_through_swap_adapter() {
+    let (contracts, admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let deleveraged_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        deleveraged_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        3_000 * PRECISION,
+        2_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let borrow_operations_deleveraged_wallet = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            deleveraged_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    borrow_operations_abi::set_auto_deleverage(
+        &borrow_operations_deleveraged_wallet,
+        trove_manager,
+        asset_id,
+        TRIGGER_ICR,
+        TARGET_ICR,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    // The stability pool is empty and no swap adapter is set
+    let res = trove_manager_abi::auto_deleverage(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        None,
+        Identity::Address(deleveraged_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await;
+    assert!(res.is_err(), "Deleverage without liquidity should fail");
+
+    let swap_adapter = deploy_mock_swap_adapter(&admin).await;
+
+    protocol_manager_abi::set_auto_deleverage_params(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        AutoDeleverageParams {
+            keeper_fee: KEEPER_FEE,
+            swap_adapter: swap_adapter.contract_id().into(),
+            max_slippage: 10_000_000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let params = trove_manager_abi::get_auto_deleverage_params(trove_manager)
+        .await
+        .value;
+    assert_eq!(params.keeper_fee, KEEPER_FEE);
+
+    // The swap venue pays 1 USDF per unit of collateral, the oracle price
+    swap_adapter_abi::set_rate(&swap_adapter, asset_id, contracts.usdf_asset_id, PRECISION).await;
+
+    healthy_wallet
+        .force_transfer_to_contract(
+            swap_adapter.contract_id(),
+            2_000 * PRECISION,
+            contracts.usdf_asset_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let provider = admin.provider().unwrap();
+    let keeper_balance_before = provider
+        .get_asset_balance(admin.address(), asset_id)
+        .await
+        .unwrap();
+
+    trove_manager_abi::auto_deleverage(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        Some(&swap_adapter),
+        Identity::Address(deleveraged_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let debt = with_min_borrow_fee(2_000 * PRECISION);
+    let coll = 3_000 * PRECISION;
+    let debt_repaid = expected_debt_to_repay(coll, debt, PRECISION);
+    let coll_sold = debt_repaid;
+    let keeper_fee = (coll_sold as u128 * KEEPER_FEE as u128 / PRECISION as u128) as u64;
+
+    let trove_debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(deleveraged_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_debt, debt - debt_repaid);
+
+    let trove_coll = trove_manager_abi::get_trove_coll(
+        trove_manager,
+        Identity::Address(deleveraged_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_coll, coll - coll_sold - keeper_fee);
+
+    let keeper_balance_after = provider
+        .get_asset_balance(admin.address(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(keeper_balance_after - keeper_balance_before, keeper_fee);
+
+    // The swap venue received the sold collateral and the USDF it paid out was burned
+    let swap_adapter_asset = provider
+        .get_contract_asset_balance(swap_adapter.contract_id(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(swap_adapter_asset, coll_sold);
+
+    let swap_adapter_usdf = provider
+        .get_contract_asset_balance(swap_adapter.contract_id(), contracts.usdf_asset_id)
+        .await
+        .unwrap();
+    assert_eq!(swap_adapter_usdf, 2_000 * PRECISION - debt_repaid);
+}
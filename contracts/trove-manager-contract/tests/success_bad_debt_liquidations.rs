@@ -0,0 +1,545 @@
+use fuels::prelude::*;
+use fuels::types::Identity;
+use test_utils::{
+    data_structures::{ContractInstance, MIN_NET_DEBT, PRECISION},
+    interfaces::{
+        active_pool::active_pool_abi,
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
+        default_pool::default_pool_abi,
+        oracle::oracle_abi,
+        protocol_manager::protocol_manager_abi,
+        pyth_oracle::{
+            pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
+            PYTH_TIMESTAMP,
+        },
+        stability_pool::{stability_pool_abi, StabilityPool},
+        trove_manager::{
+            trove_manager_abi, BadDebtCoveredEvent, BadDebtEvent, TroveManagerContract,
+        },
+    },
+    setup::common::{assert_within_threshold, setup_protocol},
+    utils::with_min_borrow_fee,
+};
+
+#[tokio::test]
+async fn proper_bad_debt_tracking_and_reserve_coverage() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+    let small_wallet = wallets.pop().unwrap();
+    let late_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        200 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        small_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        MIN_NET_DEBT,
+    )
+    .await;
+
+    let trove_manager_funder = ContractInstance::new(
+        TroveManagerContract::new(
+            trove_manager.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        trove_manager.implementation_id,
+    );
+
+    trove_manager_abi::fund_reserve(&trove_manager_funder, &contracts.usdf, 300 * PRECISION)
+        .await
+        .unwrap();
+
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, 300 * PRECISION);
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    // 200 collateral worth 200 USDF against 1_005 USDF of debt, with an empty stability pool
+    let res = trove_manager_abi::liquidate(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    let liquidated_debt = with_min_borrow_fee(1_000 * PRECISION);
+    let deficit = liquidated_debt - 200 * PRECISION;
+    let uncovered = deficit - 300 * PRECISION;
+
+    let events = res.decode_logs_with_type::<BadDebtEvent>().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].borrower,
+        Identity::Address(liquidated_wallet.address().into())
+    );
+    assert_eq!(events[0].asset, contracts.asset_contracts[0].asset_id);
+    assert_eq!(events[0].deficit, deficit);
+    assert_eq!(events[0].covered_by_reserve, 300 * PRECISION);
+
+    let bad_debt = trove_manager_abi::get_bad_debt(trove_manager).await.value;
+    assert_eq!(bad_debt, uncovered);
+
+    let total_bad_debt = protocol_manager_abi::get_total_bad_debt(
+        &contracts.protocol_manager,
+        &contracts.asset_contracts,
+    )
+    .await
+    .value;
+    assert_eq!(total_bad_debt, uncovered);
+
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, 0);
+
+    // Nothing was offset, so the whole uncovered shortfall is owed to the troves that absorbed it
+    let stability_pool_bad_debt = trove_manager_abi::get_stability_pool_bad_debt(trove_manager)
+        .await
+        .value;
+    assert_eq!(stability_pool_bad_debt, 0);
+
+    // The covered part never reaches the other troves, only the rest is redistributed
+    let asset_id = contracts.asset_contracts[0].asset_id;
+    let healthy_debt = with_min_borrow_fee(5_000 * PRECISION);
+    let small_debt = with_min_borrow_fee(MIN_NET_DEBT);
+    let redistributed_debt = liquidated_debt - 300 * PRECISION;
+
+    let default_pool_debt = default_pool_abi::get_usdf_debt(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(default_pool_debt, redistributed_debt);
+
+    let active_pool_debt = active_pool_abi::get_usdf_debt(&contracts.active_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(active_pool_debt, healthy_debt + small_debt);
+
+    for wallet in [&healthy_wallet, &small_wallet] {
+        let trove_bad_debt = trove_manager_abi::get_trove_bad_debt(
+            trove_manager,
+            Identity::Address(wallet.address().into()),
+        )
+        .await
+        .value;
+        assert_eq!(trove_bad_debt, uncovered / 2);
+    }
+
+    // A trove opened after the redistribution did not absorb any of the shortfall
+    borrow_operations_utils::mint_token_and_open_trove(
+        late_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    let late_bad_debt = trove_manager_abi::get_trove_bad_debt(
+        trove_manager,
+        Identity::Address(late_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(late_bad_debt, 0);
+
+    // The small trove repays the debt it absorbed, keeping only the minimum debt
+    let borrow_operations_small = ContractInstance::new(
+        BorrowOperations::new(
+            contracts.borrow_operations.contract.contract_id().clone(),
+            small_wallet.clone(),
+        ),
+        contracts.borrow_operations.implementation_id,
+    );
+
+    borrow_operations_abi::repay_usdf(
+        &borrow_operations_small,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.asset_contracts[0].asset,
+        &contracts.usdf,
+        &contracts.sorted_troves,
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        redistributed_debt / 2,
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // Funding the reserve no longer covers anything by itself
+    trove_manager_abi::fund_reserve(&trove_manager_funder, &contracts.usdf, 600 * PRECISION)
+        .await
+        .unwrap();
+
+    let bad_debt = trove_manager_abi::get_bad_debt(trove_manager).await.value;
+    assert_eq!(bad_debt, uncovered);
+
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, 600 * PRECISION);
+
+    let res = protocol_manager_abi::cover_bad_debt(
+        &contracts.protocol_manager,
+        &contracts.asset_contracts[0],
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        vec![
+            Identity::Address(healthy_wallet.address().into()),
+            Identity::Address(small_wallet.address().into()),
+            Identity::Address(late_wallet.address().into()),
+        ],
+    )
+    .await
+    .unwrap();
+
+    // The small trove only gets relief down to the minimum debt, the excess stays in the reserve
+    let small_relief = small_debt - MIN_NET_DEBT;
+
+    let events = res.decode_logs_with_type::<BadDebtCoveredEvent>().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].stability_pool_amount, 0);
+    assert_eq!(events[0].trove_amount, uncovered / 2 + small_relief);
+    assert_eq!(events[0].remaining_bad_debt, 0);
+
+    let bad_debt = trove_manager_abi::get_bad_debt(trove_manager).await.value;
+    assert_eq!(bad_debt, 0);
+
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, 600 * PRECISION - uncovered / 2 - small_relief);
+
+    let healthy_trove_debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(healthy_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(
+        healthy_trove_debt,
+        healthy_debt + redistributed_debt / 2 - uncovered / 2
+    );
+
+    let small_trove_debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(small_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(small_trove_debt, MIN_NET_DEBT);
+
+    let late_trove_debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(late_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(late_trove_debt, with_min_borrow_fee(1_000 * PRECISION));
+
+    let default_pool_debt = default_pool_abi::get_usdf_debt(&contracts.default_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(default_pool_debt, 0);
+
+    let active_pool_debt = active_pool_abi::get_usdf_debt(&contracts.active_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(
+        active_pool_debt,
+        healthy_trove_debt + small_trove_debt + late_trove_debt
+    );
+}
+
+#[tokio::test]
+async fn proper_bad_debt_repaid_to_stability_pool() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        200 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    let deposit = 2_000 * PRECISION;
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        deposit,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    trove_manager_abi::liquidate(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    // The stability pool offset the whole debt, so it absorbed the whole shortfall
+    let liquidated_debt = with_min_borrow_fee(1_000 * PRECISION);
+    let deficit = liquidated_debt - 200 * PRECISION;
+
+    let stability_pool_bad_debt = trove_manager_abi::get_stability_pool_bad_debt(trove_manager)
+        .await
+        .value;
+    assert_eq!(stability_pool_bad_debt, deficit);
+
+    let trove_bad_debt = trove_manager_abi::get_trove_bad_debt(
+        trove_manager,
+        Identity::Address(healthy_wallet.address().into()),
+    )
+    .await
+    .value;
+    assert_eq!(trove_bad_debt, 0);
+
+    let trove_manager_funder = ContractInstance::new(
+        TroveManagerContract::new(
+            trove_manager.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        trove_manager.implementation_id,
+    );
+
+    trove_manager_abi::fund_reserve(&trove_manager_funder, &contracts.usdf, 1_000 * PRECISION)
+        .await
+        .unwrap();
+
+    protocol_manager_abi::cover_bad_debt(
+        &contracts.protocol_manager,
+        &contracts.asset_contracts[0],
+        &contracts.stability_pool,
+        &contracts.community_issuance,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.usdf,
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // The shortfall is paid back into the deposits that absorbed it
+    let stability_pool_bad_debt = trove_manager_abi::get_stability_pool_bad_debt(trove_manager)
+        .await
+        .value;
+    assert_eq!(stability_pool_bad_debt, 0);
+
+    let bad_debt = trove_manager_abi::get_bad_debt(trove_manager).await.value;
+    assert_eq!(bad_debt, 0);
+
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, 1_000 * PRECISION - deficit);
+
+    let total_deposits = stability_pool_abi::get_total_usdf_deposits(&contracts.stability_pool)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(total_deposits, deposit - liquidated_debt + deficit);
+
+    let compounded_deposit = stability_pool_abi::get_compounded_usdf_deposit(
+        &contracts.stability_pool,
+        Identity::Address(healthy_wallet.address().into()),
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_within_threshold(
+        compounded_deposit,
+        deposit - liquidated_debt + deficit,
+        "Deposit should be restored by the covered shortfall",
+    );
+}
+
+#[tokio::test]
+async fn proper_reserve_funding_from_borrowing_fees() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let res = protocol_manager_abi::set_reserve_fee_share(
+        &contracts.protocol_manager,
+        trove_manager,
+        contracts.asset_contracts[0].asset_id,
+        PRECISION + 1,
+    )
+    .await;
+    assert!(res.is_err(), "Reserve fee share above 100% should fail");
+
+    protocol_manager_abi::set_reserve_fee_share(
+        &contracts.protocol_manager,
+        trove_manager,
+        contracts.asset_contracts[0].asset_id,
+        PRECISION / 2,
+    )
+    .await
+    .unwrap();
+
+    let share = trove_manager_abi::get_reserve_fee_share(trove_manager)
+        .await
+        .value;
+    assert_eq!(share, PRECISION / 2);
+
+    let wallet = wallets.pop().unwrap();
+    borrow_operations_utils::mint_token_and_open_trove(
+        wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    // Half of the borrowing fee funds the reserve
+    let usdf_fee = with_min_borrow_fee(5_000 * PRECISION) - 5_000 * PRECISION;
+    let reserve = trove_manager_abi::get_reserve_balance(trove_manager)
+        .await
+        .value;
+    assert_eq!(reserve, usdf_fee / 2);
+}
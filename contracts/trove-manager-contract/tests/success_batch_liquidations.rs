@@ -13,7 +13,7 @@ use test_utils::{
             PYTH_TIMESTAMP,
         },
         stability_pool::{stability_pool_abi, StabilityPool},
-        trove_manager::{trove_manager_abi, trove_manager_utils, Status},
+        trove_manager::{trove_manager_abi, trove_manager_utils, BatchLiquidationEvent, Status},
     },
     setup::common::setup_protocol,
     utils::with_min_borrow_fee,
@@ -239,3 +239,383 @@ async fn proper_batch_liquidations_enough_usdf_in_sp() {
         "Liquidated wallet collateral surplus was not 0"
     );
 }
+
+#[tokio::test]
+async fn proper_batch_liquidations_with_hints_skipping_failures() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let full_liquidation_wallet = wallets.pop().unwrap();
+    let partial_liquidation_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+    let inactive_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        full_liquidation_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        partial_liquidation_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        12_000 * PRECISION,
+        10_100 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        30_000 * PRECISION,
+        10_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        10_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    let full_liquidation_id = Identity::Address(full_liquidation_wallet.address().into());
+    let partial_liquidation_id = Identity::Address(partial_liquidation_wallet.address().into());
+    let healthy_id = Identity::Address(healthy_wallet.address().into());
+    let inactive_id = Identity::Address(inactive_wallet.address().into());
+    let null_hints = (
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    );
+
+    // Without skipping failures the inactive trove reverts the whole batch
+    let res = trove_manager_abi::batch_liquidate_troves_with_hints(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        vec![full_liquidation_id.clone(), inactive_id.clone()],
+        vec![null_hints.clone(), null_hints.clone()],
+        false,
+    )
+    .await;
+    assert!(res.is_err(), "Inactive troves should revert the batch");
+
+    // Unsorted batch with a healthy and an inactive trove, the partially liquidated trove
+    // is reinserted between the healthy trove and the fully liquidated one
+    let summary = trove_manager_abi::batch_liquidate_troves_with_hints(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        vec![
+            healthy_id.clone(),
+            partial_liquidation_id.clone(),
+            inactive_id.clone(),
+            full_liquidation_id.clone(),
+        ],
+        vec![
+            null_hints.clone(),
+            (healthy_id.clone(), full_liquidation_id.clone()),
+            null_hints.clone(),
+            null_hints.clone(),
+        ],
+        true,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    assert_eq!(summary.liquidated, vec![full_liquidation_id.clone()]);
+    assert_eq!(
+        summary.partially_liquidated,
+        vec![partial_liquidation_id.clone()]
+    );
+    assert_eq!(
+        summary.skipped,
+        vec![healthy_id.clone(), inactive_id.clone()]
+    );
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        full_liquidation_id.clone(),
+        Status::ClosedByLiquidation,
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        partial_liquidation_id.clone(),
+        Status::Active,
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        healthy_id.clone(),
+        Status::Active,
+    )
+    .await;
+
+    // Nothing left to liquidate, the batch returns an empty summary instead of reverting
+    let summary = trove_manager_abi::batch_liquidate_troves_with_hints(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        vec![partial_liquidation_id.clone(), full_liquidation_id.clone()],
+        vec![null_hints.clone(), null_hints.clone()],
+        true,
+    )
+    .await
+    .unwrap()
+    .value;
+
+    assert!(summary.liquidated.is_empty());
+    assert!(summary.partially_liquidated.is_empty());
+    assert_eq!(
+        summary.skipped,
+        vec![partial_liquidation_id.clone(), full_liquidation_id.clone()]
+    );
+}
+
+#[tokio::test]
+async fn proper_liquidate_riskiest_troves() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    let riskiest_wallet = wallets.pop().unwrap();
+    let risky_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        riskiest_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        risky_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_150 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    let riskiest_id = Identity::Address(riskiest_wallet.address().into());
+    let risky_id = Identity::Address(risky_wallet.address().into());
+
+    // Only the trove at the tail of the list is liquidated
+    let res = trove_manager_abi::liquidate_troves(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        1,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.value.liquidated, vec![riskiest_id.clone()]);
+    assert!(res.value.partially_liquidated.is_empty());
+
+    let events = res
+        .decode_logs_with_type::<BatchLiquidationEvent>()
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].asset, contracts.asset_contracts[0].asset_id);
+    assert_eq!(events[0].troves_liquidated, 1);
+    assert_eq!(
+        events[0].liquidated_debt,
+        with_min_borrow_fee(1_000 * PRECISION)
+    );
+    assert_eq!(events[0].liquidated_collateral, 1_100 * PRECISION);
+    assert_eq!(
+        events[0].debt_offset,
+        with_min_borrow_fee(1_000 * PRECISION)
+    );
+    assert_eq!(events[0].debt_redistributed, 0);
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        risky_id.clone(),
+        Status::Active,
+    )
+    .await;
+
+    // Walks up the list and stops at the first healthy trove
+    let res = trove_manager_abi::liquidate_troves(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        10,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.value.liquidated, vec![risky_id.clone()]);
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        risky_id.clone(),
+        Status::ClosedByLiquidation,
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        Identity::Address(healthy_wallet.address().into()),
+        Status::Active,
+    )
+    .await;
+
+    let res = trove_manager_abi::liquidate_troves(
+        &contracts.asset_contracts[0].trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        10,
+    )
+    .await;
+    assert!(res.is_err(), "Only healthy troves are left");
+}
@@ -3,14 +3,17 @@ use std::cmp::min;
 use fuels::prelude::*;
 use fuels::types::Identity;
 use test_utils::{
-    data_structures::{ContractInstance, PRECISION},
+    data_structures::{
+        ContractInstance, LIQUIDATOR_EXECUTION_GAS_FEE, PRECISION, STABILITY_POOL_FEE,
+    },
     interfaces::{
         active_pool::active_pool_abi,
-        borrow_operations::{borrow_operations_abi, BorrowOperations},
+        borrow_operations::{borrow_operations_abi, borrow_operations_utils, BorrowOperations},
         coll_surplus_pool::coll_surplus_pool_abi,
         default_pool::default_pool_abi,
         multi_trove_getter::multi_trove_getter_utils,
         oracle::oracle_abi,
+        protocol_manager::{protocol_manager_abi, LiquidationParams},
         pyth_oracle::{
             pyth_oracle_abi, pyth_price_feed, pyth_price_feed_with_time, PYTH_PRECISION,
             PYTH_TIMESTAMP,
@@ -20,7 +23,10 @@ use test_utils::{
         trove_manager::{trove_manager_abi, trove_manager_utils, Status},
     },
     setup::common::{deploy_multi_trove_getter, setup_protocol},
-    utils::{assert_within_threshold, with_liquidation_penalty, with_min_borrow_fee},
+    utils::{
+        assert_within_threshold, calculate_icr, liquidation_fee_share, with_liquidation_penalty,
+        with_min_borrow_fee,
+    },
 };
 
 #[tokio::test]
@@ -806,7 +812,7 @@ async fn proper_full_liquidation_empty_sp() {
 
     // 1.10 * 500_000_000
     let mut expected_default_pool_asset =
-        with_liquidation_penalty(with_min_borrow_fee(1_000 * PRECISION));
+        with_liquidation_penalty(with_min_borrow_fee(1_000 * PRECISION), STABILITY_POOL_FEE);
     // max available to liquidate is 1_100 * PRECISION
     expected_default_pool_asset = min(expected_default_pool_asset, 1_100 * PRECISION);
     let expected_default_pool_debt = with_min_borrow_fee(1_000 * PRECISION);
@@ -1097,3 +1103,334 @@ async fn test_trove_sorting_after_liquidation_and_rewards() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn proper_full_liquidation_with_custom_liquidation_params() {
+    let (contracts, admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet1 = wallets.pop().unwrap();
+    let treasury_wallet = wallets.pop().unwrap();
+
+    let stability_pool_fee = 50_000_000;
+    let liquidator_fee = 10_000_000;
+    let treasury_fee = 20_000_000;
+
+    // Fees taken out of the penalty can't exceed the penalty itself
+    let res = protocol_manager_abi::set_liquidation_params(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        LiquidationParams {
+            post_collateral_ratio: 1_500_000_000,
+            stability_pool_fee,
+            liquidator_fee,
+            treasury_fee: 50_000_000,
+            treasury: Identity::Address(treasury_wallet.address().into()),
+        },
+    )
+    .await;
+    assert!(res.is_err(), "Fees above the penalty should be rejected");
+
+    // The partial liquidation target must stay above the MCR
+    let res = protocol_manager_abi::set_liquidation_params(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        LiquidationParams {
+            post_collateral_ratio: 1_300_000_000,
+            stability_pool_fee,
+            liquidator_fee,
+            treasury_fee,
+            treasury: Identity::Address(treasury_wallet.address().into()),
+        },
+    )
+    .await;
+    assert!(res.is_err(), "Target below the MCR should be rejected");
+
+    protocol_manager_abi::set_liquidation_params(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        LiquidationParams {
+            post_collateral_ratio: 1_500_000_000,
+            stability_pool_fee,
+            liquidator_fee,
+            treasury_fee,
+            treasury: Identity::Address(treasury_wallet.address().into()),
+        },
+    )
+    .await
+    .unwrap();
+
+    let params = trove_manager_abi::get_liquidation_params(trove_manager)
+        .await
+        .value;
+    assert_eq!(params.stability_pool_fee, stability_pool_fee);
+    assert_eq!(params.liquidator_fee, liquidator_fee);
+    assert_eq!(params.treasury_fee, treasury_fee);
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_100 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet1.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet1 = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet1.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet1,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    trove_manager_abi::liquidate(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    trove_manager_utils::assert_trove_status(
+        trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+        Status::ClosedByLiquidation,
+    )
+    .await;
+
+    // 5% penalty instead of the default 10%
+    let coll_liquidated =
+        with_liquidation_penalty(with_min_borrow_fee(1_000 * PRECISION), stability_pool_fee);
+    let gas_compensation = liquidation_fee_share(coll_liquidated, liquidator_fee);
+    let treasury_cut = liquidation_fee_share(coll_liquidated, treasury_fee);
+
+    let provider = admin.provider().unwrap();
+    let admin_balance = provider
+        .get_asset_balance(admin.address(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(admin_balance, gas_compensation);
+
+    let treasury_balance = provider
+        .get_asset_balance(treasury_wallet.address(), asset_id)
+        .await
+        .unwrap();
+    assert_eq!(treasury_balance, treasury_cut);
+
+    let stability_pool_asset = stability_pool_abi::get_asset(&contracts.stability_pool, asset_id)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(
+        stability_pool_asset,
+        coll_liquidated - gas_compensation - treasury_cut
+    );
+
+    let coll_surplus = coll_surplus_pool_abi::get_collateral(
+        &contracts.coll_surplus_pool,
+        Identity::Address(liquidated_wallet.address().into()),
+        asset_id,
+    )
+    .await
+    .unwrap()
+    .value;
+    assert_eq!(coll_surplus, 1_100 * PRECISION - coll_liquidated);
+
+    let active_pool_asset = active_pool_abi::get_asset(&contracts.active_pool, asset_id)
+        .await
+        .value;
+    assert_eq!(active_pool_asset, 10_000 * PRECISION);
+}
+
+#[tokio::test]
+async fn proper_partial_liquidation_with_custom_post_collateral_ratio() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let trove_manager = &contracts.asset_contracts[0].trove_manager;
+    let asset_id = contracts.asset_contracts[0].asset_id;
+
+    let liquidated_wallet = wallets.pop().unwrap();
+    let healthy_wallet1 = wallets.pop().unwrap();
+
+    let post_collateral_ratio = 1_800_000_000;
+
+    protocol_manager_abi::set_liquidation_params(
+        &contracts.protocol_manager,
+        trove_manager,
+        asset_id,
+        LiquidationParams {
+            post_collateral_ratio,
+            stability_pool_fee: STABILITY_POOL_FEE,
+            liquidator_fee: LIQUIDATOR_EXECUTION_GAS_FEE,
+            treasury_fee: 0,
+            treasury: Identity::Address(Address::zeroed()),
+        },
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed(10),
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        liquidated_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        12_000 * PRECISION,
+        10_100 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet1.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        20_000 * PRECISION,
+        15_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet1 = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet1.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet1,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        15_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    oracle_abi::set_debug_timestamp(&contracts.asset_contracts[0].oracle, PYTH_TIMESTAMP + 1).await;
+    pyth_oracle_abi::update_price_feeds(
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        pyth_price_feed_with_time(1, PYTH_TIMESTAMP + 1, PYTH_PRECISION.into()),
+    )
+    .await;
+
+    // ICR of ~118%, below the MCR but with enough collateral to be partially liquidated up to 180%
+    trove_manager_abi::liquidate(
+        trove_manager,
+        &contracts.community_issuance,
+        &contracts.stability_pool,
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &contracts.asset_contracts[0].mock_redstone_oracle,
+        &contracts.sorted_troves,
+        &contracts.active_pool,
+        &contracts.default_pool,
+        &contracts.coll_surplus_pool,
+        &contracts.usdf,
+        Identity::Address(liquidated_wallet.address().into()),
+        Identity::Address(Address::zeroed()),
+        Identity::Address(Address::zeroed()),
+    )
+    .await
+    .unwrap();
+
+    trove_manager_utils::assert_trove_status(
+        trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+        Status::Active,
+    )
+    .await;
+
+    let coll = trove_manager_abi::get_trove_coll(
+        trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+    )
+    .await
+    .value;
+
+    let debt = trove_manager_abi::get_trove_debt(
+        trove_manager,
+        Identity::Address(liquidated_wallet.address().into()),
+    )
+    .await
+    .value;
+
+    assert!(debt < with_min_borrow_fee(10_100 * PRECISION));
+    assert_within_threshold(
+        calculate_icr(coll, debt),
+        post_collateral_ratio,
+        "Trove ICR after partial liquidation is not the configured target",
+    );
+}
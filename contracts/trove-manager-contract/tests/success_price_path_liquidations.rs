@@ -0,0 +1,164 @@
+use fuels::prelude::*;
+use fuels::types::Identity;
+use test_utils::{
+    data_structures::{ContractInstance, PRECISION},
+    interfaces::{
+        borrow_operations::borrow_operations_utils,
+        oracle::oracle_abi,
+        pyth_oracle::PYTH_TIMESTAMP,
+        stability_pool::{stability_pool_abi, StabilityPool},
+        trove_manager::{trove_manager_abi, trove_manager_utils, Status},
+    },
+    price_path::{price_paths_from_csv_reader, OracleFeeds, PricePathReplay, PriceSource},
+    setup::common::setup_protocol,
+    utils::calculate_cr,
+};
+
+const MCR: u64 = 1_350_000_000;
+
+// Hourly crash shaped path, timestamps are rebased onto the mock oracle clock
+const CRASH_PATH_CSV: &str = "asset,timestamp,price,confidence
+MCK,0,1.50,0.001
+MCK,3600,1.30,0.001
+MCK,7200,1.20,0.002
+MCK,10800,1.05,0.002
+MCK,14400,0.95,0.003
+MCK,18000,1.10,0.002";
+
+#[tokio::test]
+async fn proper_liquidation_during_replayed_crash() {
+    let (contracts, _admin, mut wallets) = setup_protocol(5, false, false).await;
+
+    let paths = price_paths_from_csv_reader(CRASH_PATH_CSV.as_bytes());
+    let path = paths.get("MCK").unwrap().rebased_to(PYTH_TIMESTAMP);
+    let feeds = OracleFeeds::from_asset_contracts(&contracts.asset_contracts[0]);
+    let mut replay = PricePathReplay::new(&path, PriceSource::Pyth);
+
+    // Open troves at the first price of the path
+    let first_point = replay.step(&feeds).await.unwrap();
+    let price = oracle_abi::get_price(
+        &contracts.asset_contracts[0].oracle,
+        &contracts.asset_contracts[0].mock_pyth_oracle,
+        &None,
+    )
+    .await
+    .value;
+    assert_eq!(price, (first_point.price * PRECISION as f64) as u64);
+
+    let risky_wallet = wallets.pop().unwrap();
+    let healthy_wallet = wallets.pop().unwrap();
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        risky_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        1_300 * PRECISION,
+        1_000 * PRECISION,
+    )
+    .await;
+
+    borrow_operations_utils::mint_token_and_open_trove(
+        healthy_wallet.clone(),
+        &contracts.asset_contracts[0],
+        &contracts.borrow_operations,
+        &contracts.usdf,
+        &contracts.fpt_staking,
+        &contracts.active_pool,
+        &contracts.sorted_troves,
+        10_000 * PRECISION,
+        5_000 * PRECISION,
+    )
+    .await;
+
+    let stability_pool_healthy_wallet = ContractInstance::new(
+        StabilityPool::new(
+            contracts.stability_pool.contract.contract_id().clone(),
+            healthy_wallet.clone(),
+        ),
+        contracts.stability_pool.implementation_id,
+    );
+
+    stability_pool_abi::provide_to_stability_pool(
+        &stability_pool_healthy_wallet,
+        &contracts.community_issuance,
+        &contracts.usdf,
+        &contracts.asset_contracts[0].asset,
+        5_000 * PRECISION,
+    )
+    .await
+    .unwrap();
+
+    let risky_id = Identity::Address(risky_wallet.address().into());
+    let healthy_id = Identity::Address(healthy_wallet.address().into());
+    let mut liquidated_at = None;
+
+    while let Some(point) = replay.step(&feeds).await {
+        let price = oracle_abi::get_price(
+            &contracts.asset_contracts[0].oracle,
+            &contracts.asset_contracts[0].mock_pyth_oracle,
+            &None,
+        )
+        .await
+        .value;
+        assert_eq!(price, (point.price * PRECISION as f64).round() as u64);
+
+        if liquidated_at.is_some() {
+            continue;
+        }
+
+        let (debt, coll, _, _) = trove_manager_abi::get_entire_debt_and_coll(
+            &contracts.asset_contracts[0].trove_manager,
+            risky_id.clone(),
+        )
+        .await
+        .value;
+
+        let result = trove_manager_abi::liquidate(
+            &contracts.asset_contracts[0].trove_manager,
+            &contracts.community_issuance,
+            &contracts.stability_pool,
+            &contracts.asset_contracts[0].oracle,
+            &contracts.asset_contracts[0].mock_pyth_oracle,
+            &contracts.asset_contracts[0].mock_redstone_oracle,
+            &contracts.sorted_troves,
+            &contracts.active_pool,
+            &contracts.default_pool,
+            &contracts.coll_surplus_pool,
+            &contracts.usdf,
+            risky_id.clone(),
+            Identity::Address(Address::zeroed()),
+            Identity::Address(Address::zeroed()),
+        )
+        .await;
+
+        // The trove can only be liquidated once the replayed price pushes it below MCR
+        if calculate_cr(price, coll, debt) < MCR {
+            result.unwrap();
+            liquidated_at = Some(point.timestamp);
+        } else {
+            assert!(result.is_err(), "Healthy trove should not be liquidated");
+        }
+    }
+
+    assert!(replay.is_finished());
+    // 1_300 coll against ~1_005 debt drops below 135% on the 0.95 candle
+    assert_eq!(liquidated_at, Some(PYTH_TIMESTAMP + 14400));
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        risky_id,
+        Status::ClosedByLiquidation,
+    )
+    .await;
+
+    trove_manager_utils::assert_trove_status(
+        &contracts.asset_contracts[0].trove_manager,
+        healthy_id,
+        Status::Active,
+    )
+    .await;
+}
@@ -1,5 +1,14 @@
 library;
 
+pub enum VestingCurve {
+    // Cliff amount at the cliff, then the rest released linearly until the end
+    Linear: (),
+    // Cliff amount at the cliff, then the rest released in equal steps, one per period of the given length in seconds
+    Periodic: u64,
+    // Released by the tranches of the recipient, the cliff amount is unused
+    Tranches: (),
+}
+
 pub struct VestingSchedule {
     pub cliff_timestamp: u64,
     pub end_timestamp: u64,
@@ -7,4 +16,11 @@ pub struct VestingSchedule {
     pub total_amount: u64,
     pub claimed_amount: u64,
     pub recipient: Identity,
+    pub curve: VestingCurve,
+}
+
+pub struct Tranche {
+    pub recipient: Identity,
+    pub timestamp: u64,
+    pub amount: u64,
 }
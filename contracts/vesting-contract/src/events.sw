@@ -0,0 +1,29 @@
+library;
+
+pub struct ScheduleAddedEvent {
+    pub recipient: Identity,
+    pub total_amount: u64,
+}
+
+pub struct ScheduleRevokedEvent {
+    pub recipient: Identity,
+    pub vested_amount: u64,
+    pub unvested_amount: u64,
+    pub treasury: Identity,
+}
+
+pub struct BeneficiaryChangeProposedEvent {
+    pub recipient: Identity,
+    pub new_recipient: Identity,
+}
+
+pub struct BeneficiaryChangedEvent {
+    pub previous_recipient: Identity,
+    pub new_recipient: Identity,
+}
+
+pub struct TokensClaimedEvent {
+    pub recipient: Identity,
+    pub destination: Identity,
+    pub amount: u64,
+}
@@ -1,20 +1,45 @@
 library;
 
-use ::data_structures::VestingSchedule;
+use ::data_structures::{Tranche, VestingSchedule};
 abi VestingContract {
     #[storage(write, read)]
     fn constructor(
         asset: AssetId,
         schedules: Vec<VestingSchedule>,
+        tranches: Vec<Tranche>,
+        admin: Identity,
+        treasury: Identity,
         debugging: bool,
     );
     #[storage(read, write)]
     fn claim_vested_tokens();
+    #[storage(read, write)]
+    fn claim_for(recipient: Identity);
+    #[storage(read, write)]
+    fn claim_to(destination: Identity);
+    #[storage(read, write)]
+    fn claim_for_many(recipients: Vec<Identity>);
+    #[storage(read, write), payable]
+    fn add_schedule(schedule: VestingSchedule, tranches: Vec<Tranche>);
+    #[storage(read, write)]
+    fn revoke(recipient: Identity);
+    #[storage(read, write)]
+    fn propose_beneficiary(new_recipient: Identity);
+    #[storage(read, write)]
+    fn accept_beneficiary(previous_recipient: Identity);
     #[storage(read)]
     fn get_vesting_schedule(address: Identity) -> VestingSchedule;
     #[storage(read)]
     fn get_redeemable_amount(timestamp: u64, address: Identity) -> u64;
     #[storage(read)]
+    fn get_tranches(address: Identity) -> Vec<Tranche>;
+    #[storage(read)]
+    fn get_pending_beneficiary(address: Identity) -> Option<Identity>;
+    #[storage(read)]
+    fn is_revoked(address: Identity) -> bool;
+    #[storage(read)]
+    fn get_treasury() -> Identity;
+    #[storage(read)]
     fn get_current_time() -> u64;
     #[storage(write, read)]
     fn set_current_time(time: u64);
@@ -1,10 +1,18 @@
 contract;
 
 mod data_structures;
+mod events;
 mod interface;
 mod utils;
 
-use ::data_structures::VestingSchedule;
+use ::data_structures::{Tranche, VestingSchedule};
+use ::events::{
+    BeneficiaryChangedEvent,
+    BeneficiaryChangeProposedEvent,
+    ScheduleAddedEvent,
+    ScheduleRevokedEvent,
+    TokensClaimedEvent,
+};
 use ::interface::VestingContract;
 use ::utils::{calculate_redeemable_amount, is_valid_vesting_schedule};
 use std::{
@@ -22,6 +30,8 @@ use std::{
     hash::Hash,
     storage::storage_vec::*,
 };
+use sway_libs::ownership::*;
+use standards::{src5::*,};
 configurable {
     /// Initializer identity
     INITIALIZER: Identity = Identity::Address(Address::zero()),
@@ -32,6 +42,10 @@ storage {
     vesting_addresses: StorageVec<Identity> = StorageVec {},
     asset: AssetId = AssetId::zero(),
     is_initialized: bool = false,
+    treasury: Identity = Identity::Address(Address::zero()),
+    tranches: StorageMap<Identity, StorageVec<Tranche>> = StorageMap::<Identity, StorageVec<Tranche>> {},
+    revoked: StorageMap<Identity, bool> = StorageMap::<Identity, bool> {},
+    pending_beneficiaries: StorageMap<Identity, Identity> = StorageMap::<Identity, Identity> {},
     // timestamp is used for testing purposes only, as Fuel does not support timestamp currently in integration tests
     debug: bool = false,
     debug_timestamp: u64 = 0,
@@ -42,6 +56,9 @@ impl VestingContract for Contract {
     fn constructor(
         asset: AssetId,
         schedules: Vec<VestingSchedule>,
+        tranches: Vec<Tranche>,
+        admin: Identity,
+        treasury: Identity,
         debugging: bool,
     ) {
         require(
@@ -56,46 +73,216 @@ impl VestingContract for Contract {
             "VestingContract: Contract is already initialized",
         );
         storage.asset.write(asset);
+        storage.treasury.write(treasury);
         storage.debug.write(debugging);
         let mut total_vested_amount = 0;
+        let mut total_tranches = 0;
         let mut i = 0;
         while i < schedules.len() {
             let schedule = schedules.get(i).unwrap();
-            require(
-                is_valid_vesting_schedule(schedule),
-                "VestingContract: Invalid vesting schedule",
-            );
-            match storage.vesting_schedules.get(schedule.recipient).try_read() {
-                Some(_) => require(false, "VestingContract: Schedule already exists"),
-                None => {}
-            }
-            storage
-                .vesting_schedules
-                .insert(schedule.recipient, schedule);
-            storage.vesting_addresses.push(schedule.recipient);
+            let schedule_tranches = internal_get_tranches_of(tranches, schedule.recipient);
+            internal_insert_schedule(schedule, schedule_tranches);
             total_vested_amount += schedule.total_amount;
+            total_tranches += schedule_tranches.len();
             i += 1;
         }
+        require(
+            total_tranches == tranches.len(),
+            "VestingContract: Tranche without a schedule",
+        );
         require(
             total_vested_amount == TOTAL_AMOUNT,
             "VestingContract: Total amount does not match",
         );
         storage.is_initialized.write(true);
+
+        initialize_ownership(admin);
     }
 
     #[storage(read, write)]
     fn claim_vested_tokens() {
         let address = msg_sender().unwrap();
-        let mut schedule = storage.vesting_schedules.get(address).read();
-        let now = internal_get_current_time();
-        let currently_unclaimed = calculate_redeemable_amount(now, schedule);
+        let claimed = internal_claim(address, address);
+        require(claimed > 0, "VestingContract: Nothing to redeem");
+    }
+    // Anyone can push the vested tokens of a recipient, they are still sent to the recipient
+    #[storage(read, write)]
+    fn claim_for(recipient: Identity) {
+        let claimed = internal_claim(recipient, recipient);
+        require(claimed > 0, "VestingContract: Nothing to redeem");
+    }
+    // Claims the vested tokens of the caller and sends them to another destination
+    #[storage(read, write)]
+    fn claim_to(destination: Identity) {
+        let claimed = internal_claim(msg_sender().unwrap(), destination);
+        require(claimed > 0, "VestingContract: Nothing to redeem");
+    }
+    // Pushes the vested tokens of many recipients, skipping the ones with nothing to redeem
+    #[storage(read, write)]
+    fn claim_for_many(recipients: Vec<Identity>) {
+        let mut i = 0;
+        while i < recipients.len() {
+            let recipient = recipients.get(i).unwrap();
+            if storage.vesting_schedules.get(recipient).try_read().is_some() {
+                let _ = internal_claim(recipient, recipient);
+            }
+            i += 1;
+        }
+    }
+    // Adds the schedule of a new recipient, funded by the FPT attached to the call
+    #[storage(read, write), payable]
+    fn add_schedule(schedule: VestingSchedule, tranches: Vec<Tranche>) {
+        only_owner();
+        require(
+            storage
+                .is_initialized
+                .read(),
+            "VestingContract: Contract is not initialized",
+        );
+        require(
+            msg_asset_id() == storage
+                .asset
+                .read(),
+            "VestingContract: Wrong asset sent",
+        );
+        require(
+            msg_amount() == schedule
+                .total_amount,
+            "VestingContract: Amount sent does not match the schedule",
+        );
+        let schedule_tranches = internal_get_tranches_of(tranches, schedule.recipient);
+        require(
+            schedule_tranches
+                .len() == tranches
+                .len(),
+            "VestingContract: Tranche without a schedule",
+        );
+        internal_insert_schedule(schedule, schedule_tranches);
+
+        log(ScheduleAddedEvent {
+            recipient: schedule.recipient,
+            total_amount: schedule.total_amount,
+        });
+    }
+    // Pays out the vested tokens of a recipient and returns the unvested ones to the treasury
+    #[storage(read, write)]
+    fn revoke(recipient: Identity) {
+        only_owner();
+        let schedule = storage.vesting_schedules.get(recipient).try_read();
+        require(
+            schedule
+                .is_some(),
+            "VestingContract: Schedule does not exist",
+        );
+        require(
+            !internal_is_revoked(recipient),
+            "VestingContract: Schedule already revoked",
+        );
+        let mut schedule = schedule.unwrap();
+        let vested_amount = calculate_redeemable_amount(
+            internal_get_current_time(),
+            schedule,
+            storage
+                .tranches
+                .get(recipient)
+                .load_vec(),
+        );
+        let unvested_amount = schedule.total_amount - schedule.claimed_amount - vested_amount;
+
+        schedule.claimed_amount += vested_amount;
+        schedule.total_amount = schedule.claimed_amount;
+        storage.vesting_schedules.insert(recipient, schedule);
+        storage.revoked.insert(recipient, true);
+        let _ = storage.pending_beneficiaries.remove(recipient);
+
+        let asset = storage.asset.read();
+        let treasury = storage.treasury.read();
+        if vested_amount > 0 {
+            transfer(recipient, asset, vested_amount);
+        }
+        if unvested_amount > 0 {
+            transfer(treasury, asset, unvested_amount);
+        }
+
+        log(ScheduleRevokedEvent {
+            recipient,
+            vested_amount,
+            unvested_amount,
+            treasury,
+        });
+    }
+    // First step of moving a schedule to a new wallet, called by the current recipient
+    #[storage(read, write)]
+    fn propose_beneficiary(new_recipient: Identity) {
+        let recipient = msg_sender().unwrap();
+        require(
+            storage
+                .vesting_schedules
+                .get(recipient)
+                .try_read()
+                .is_some(),
+            "VestingContract: Schedule does not exist",
+        );
         require(
-            currently_unclaimed > 0,
-            "VestingContract: Nothing to redeem",
+            !internal_is_revoked(recipient),
+            "VestingContract: Schedule is revoked",
         );
-        schedule.claimed_amount += currently_unclaimed;
-        storage.vesting_schedules.insert(address, schedule);
-        transfer(address, storage.asset.read(), currently_unclaimed);
+        require(
+            new_recipient != recipient,
+            "VestingContract: New recipient is the current recipient",
+        );
+        storage.pending_beneficiaries.insert(recipient, new_recipient);
+
+        log(BeneficiaryChangeProposedEvent {
+            recipient,
+            new_recipient,
+        });
+    }
+    // Second step of moving a schedule to a new wallet, called by the proposed recipient
+    #[storage(read, write)]
+    fn accept_beneficiary(previous_recipient: Identity) {
+        let new_recipient = msg_sender().unwrap();
+        match storage.pending_beneficiaries.get(previous_recipient).try_read() {
+            Some(pending) => require(
+                pending == new_recipient,
+                "VestingContract: Caller is not the proposed recipient",
+            ),
+            None => require(false, "VestingContract: No beneficiary change proposed"),
+        }
+        match storage.vesting_schedules.get(new_recipient).try_read() {
+            Some(_) => require(false, "VestingContract: Schedule already exists"),
+            None => {}
+        }
+
+        let mut schedule = storage.vesting_schedules.get(previous_recipient).read();
+        schedule.recipient = new_recipient;
+        storage.vesting_schedules.insert(new_recipient, schedule);
+        let _ = storage.vesting_schedules.remove(previous_recipient);
+        let _ = storage.pending_beneficiaries.remove(previous_recipient);
+
+        let tranches = storage.tranches.get(previous_recipient).load_vec();
+        let mut i = 0;
+        while i < tranches.len() {
+            let mut tranche = tranches.get(i).unwrap();
+            tranche.recipient = new_recipient;
+            storage.tranches.get(new_recipient).push(tranche);
+            i += 1;
+        }
+        storage.tranches.get(previous_recipient).clear();
+
+        let mut i = 0;
+        while i < storage.vesting_addresses.len() {
+            if storage.vesting_addresses.get(i).unwrap().read() == previous_recipient {
+                storage.vesting_addresses.set(i, new_recipient);
+                break;
+            }
+            i += 1;
+        }
+
+        log(BeneficiaryChangedEvent {
+            previous_recipient,
+            new_recipient,
+        });
     }
     #[storage(read)]
     fn get_vesting_schedule(address: Identity) -> VestingSchedule {
@@ -103,8 +290,26 @@ impl VestingContract for Contract {
     }
     #[storage(read)]
     fn get_redeemable_amount(at_timestamp: u64, address: Identity) -> u64 {
-        let schedule = storage.vesting_schedules.get(address).read();
-        return calculate_redeemable_amount(at_timestamp, schedule);
+        match storage.vesting_schedules.get(address).try_read() {
+            Some(schedule) => internal_get_redeemable_amount(at_timestamp, address, schedule),
+            None => 0,
+        }
+    }
+    #[storage(read)]
+    fn get_tranches(address: Identity) -> Vec<Tranche> {
+        return storage.tranches.get(address).load_vec();
+    }
+    #[storage(read)]
+    fn get_pending_beneficiary(address: Identity) -> Option<Identity> {
+        return storage.pending_beneficiaries.get(address).try_read();
+    }
+    #[storage(read)]
+    fn is_revoked(address: Identity) -> bool {
+        return internal_is_revoked(address);
+    }
+    #[storage(read)]
+    fn get_treasury() -> Identity {
+        return storage.treasury.read();
     }
     #[storage(read)]
     fn get_current_time() -> u64 {
@@ -121,6 +326,87 @@ impl VestingContract for Contract {
         storage.debug_timestamp.write(time);
     }
 }
+impl SRC5 for Contract {
+    #[storage(read)]
+    fn owner() -> State {
+        _owner()
+    }
+}
+#[storage(read, write)]
+fn internal_claim(recipient: Identity, destination: Identity) -> u64 {
+    let mut schedule = storage.vesting_schedules.get(recipient).read();
+    let now = internal_get_current_time();
+    let currently_unclaimed = internal_get_redeemable_amount(now, recipient, schedule);
+    if currently_unclaimed == 0 {
+        return 0;
+    }
+    schedule.claimed_amount += currently_unclaimed;
+    storage.vesting_schedules.insert(recipient, schedule);
+    transfer(destination, storage.asset.read(), currently_unclaimed);
+
+    log(TokensClaimedEvent {
+        recipient,
+        destination,
+        amount: currently_unclaimed,
+    });
+    return currently_unclaimed;
+}
+#[storage(read)]
+fn internal_is_revoked(address: Identity) -> bool {
+    return storage.revoked.get(address).try_read().unwrap_or(false);
+}
+// A revoked schedule has been settled, nothing more can be redeemed from it
+#[storage(read)]
+fn internal_get_redeemable_amount(
+    current_time: u64,
+    address: Identity,
+    schedule: VestingSchedule,
+) -> u64 {
+    if internal_is_revoked(address) {
+        return 0;
+    }
+    return calculate_redeemable_amount(
+        current_time,
+        schedule,
+        storage
+            .tranches
+            .get(address)
+            .load_vec(),
+    );
+}
+// Tranches of a recipient out of a list of tranches of any recipient
+fn internal_get_tranches_of(tranches: Vec<Tranche>, recipient: Identity) -> Vec<Tranche> {
+    let mut recipient_tranches: Vec<Tranche> = Vec::new();
+    let mut i = 0;
+    while i < tranches.len() {
+        let tranche = tranches.get(i).unwrap();
+        if tranche.recipient == recipient {
+            recipient_tranches.push(tranche);
+        }
+        i += 1;
+    }
+    return recipient_tranches;
+}
+#[storage(read, write)]
+fn internal_insert_schedule(schedule: VestingSchedule, tranches: Vec<Tranche>) {
+    require(
+        is_valid_vesting_schedule(schedule, tranches),
+        "VestingContract: Invalid vesting schedule",
+    );
+    match storage.vesting_schedules.get(schedule.recipient).try_read() {
+        Some(_) => require(false, "VestingContract: Schedule already exists"),
+        None => {}
+    }
+    storage
+        .vesting_schedules
+        .insert(schedule.recipient, schedule);
+    storage.vesting_addresses.push(schedule.recipient);
+    let mut i = 0;
+    while i < tranches.len() {
+        storage.tranches.get(schedule.recipient).push(tranches.get(i).unwrap());
+        i += 1;
+    }
+}
 #[storage(read)]
 fn internal_get_current_time() -> u64 {
     if storage.debug.read() {
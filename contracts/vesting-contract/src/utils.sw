@@ -1,24 +1,59 @@
 library;
 
-use ::data_structures::VestingSchedule;
+use ::data_structures::{Tranche, VestingCurve, VestingSchedule};
 use std::u128::U128;
 use libraries::fluid_math::fm_multiply_ratio;
-pub fn calculate_redeemable_amount(current_time: u64, vesting_schedule: VestingSchedule) -> u64 {
+pub fn calculate_redeemable_amount(
+    current_time: u64,
+    vesting_schedule: VestingSchedule,
+    tranches: Vec<Tranche>,
+) -> u64 {
+    return calculate_vested_amount(current_time, vesting_schedule, tranches) - vesting_schedule.claimed_amount;
+}
+pub fn calculate_vested_amount(
+    current_time: u64,
+    vesting_schedule: VestingSchedule,
+    tranches: Vec<Tranche>,
+) -> u64 {
     if current_time < vesting_schedule.cliff_timestamp {
         return 0;
     }
     if current_time >= vesting_schedule.end_timestamp {
-        return vesting_schedule.total_amount - vesting_schedule.claimed_amount;
+        return vesting_schedule.total_amount;
     }
 
     let total_minus_cliff = vesting_schedule.total_amount - vesting_schedule.cliff_amount;
     let total_vesting_duration = vesting_schedule.end_timestamp - vesting_schedule.cliff_timestamp;
     let time_elapsed = current_time - vesting_schedule.cliff_timestamp;
-    // Linear vesting
-    let fraction_amount_claimable = fm_multiply_ratio(total_minus_cliff, time_elapsed, total_vesting_duration);
-    return vesting_schedule.cliff_amount + fraction_amount_claimable - vesting_schedule.claimed_amount;
+    match vesting_schedule.curve {
+        VestingCurve::Linear => {
+            let fraction_amount_claimable = fm_multiply_ratio(total_minus_cliff, time_elapsed, total_vesting_duration);
+            return vesting_schedule.cliff_amount + fraction_amount_claimable;
+        },
+        VestingCurve::Periodic(period) => {
+            // Only whole periods are released
+            let fraction_amount_claimable = fm_multiply_ratio(
+                total_minus_cliff,
+                time_elapsed / period,
+                total_vesting_duration / period,
+            );
+            return vesting_schedule.cliff_amount + fraction_amount_claimable;
+        },
+        VestingCurve::Tranches => {
+            let mut vested_amount = 0;
+            let mut i = 0;
+            while i < tranches.len() {
+                let tranche = tranches.get(i).unwrap();
+                if tranche.timestamp <= current_time {
+                    vested_amount += tranche.amount;
+                }
+                i += 1;
+            }
+            return vested_amount;
+        },
+    }
 }
-pub fn is_valid_vesting_schedule(vesting_schedule: VestingSchedule) -> bool {
+pub fn is_valid_vesting_schedule(vesting_schedule: VestingSchedule, tranches: Vec<Tranche>) -> bool {
     if vesting_schedule.cliff_timestamp >= vesting_schedule.end_timestamp
     {
         return false;
@@ -30,7 +65,44 @@ pub fn is_valid_vesting_schedule(vesting_schedule: VestingSchedule) -> bool {
     if vesting_schedule.claimed_amount != 0 {
         return false;
     }
-    return true;
+    match vesting_schedule.curve {
+        VestingCurve::Linear => {
+            return tranches.len() == 0;
+        },
+        VestingCurve::Periodic(period) => {
+            // The vesting duration must be a whole number of periods
+            let total_vesting_duration = vesting_schedule.end_timestamp - vesting_schedule.cliff_timestamp;
+            return period > 0 && total_vesting_duration % period == 0 && tranches.len() == 0;
+        },
+        VestingCurve::Tranches => {
+            return is_valid_tranche_list(vesting_schedule, tranches);
+        },
+    }
+}
+// Tranches must be in strictly increasing order, from the cliff to the end, and add up to the total amount
+fn is_valid_tranche_list(vesting_schedule: VestingSchedule, tranches: Vec<Tranche>) -> bool {
+    if tranches.len() == 0 || vesting_schedule.cliff_amount != 0 {
+        return false;
+    }
+    if tranches.get(0).unwrap().timestamp != vesting_schedule.cliff_timestamp
+        || tranches.get(tranches.len() - 1).unwrap().timestamp != vesting_schedule.end_timestamp
+    {
+        return false;
+    }
+    let mut total_amount = 0;
+    let mut i = 0;
+    while i < tranches.len() {
+        let tranche = tranches.get(i).unwrap();
+        if tranche.recipient != vesting_schedule.recipient || tranche.amount == 0 {
+            return false;
+        }
+        if i > 0 && tranche.timestamp <= tranches.get(i - 1).unwrap().timestamp {
+            return false;
+        }
+        total_amount += tranche.amount;
+        i += 1;
+    }
+    return total_amount == vesting_schedule.total_amount;
 }
 #[test]
 fn test_redeemable_calculations() {
@@ -41,11 +113,12 @@ fn test_redeemable_calculations() {
         total_amount: 1000,
         claimed_amount: 0,
         recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Linear,
     };
     // Before cliff
-    assert(calculate_redeemable_amount(0, vesting_schedule) == 0);
+    assert(calculate_redeemable_amount(0, vesting_schedule, Vec::new()) == 0);
     // At cliff
-    assert(calculate_redeemable_amount(100, vesting_schedule) == 100);
+    assert(calculate_redeemable_amount(100, vesting_schedule, Vec::new()) == 100);
     // In the middle of the vesting period with cliff amount claimed
     vesting_schedule = VestingSchedule {
         cliff_timestamp: 100,
@@ -54,8 +127,9 @@ fn test_redeemable_calculations() {
         total_amount: 1000,
         claimed_amount: 100,
         recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Linear,
     };
-    assert(calculate_redeemable_amount(150, vesting_schedule) == 450);
+    assert(calculate_redeemable_amount(150, vesting_schedule, Vec::new()) == 450);
     // At the end of the vesting period with 650 claimed
     vesting_schedule = VestingSchedule {
         cliff_timestamp: 100,
@@ -64,8 +138,9 @@ fn test_redeemable_calculations() {
         total_amount: 1000,
         claimed_amount: 650,
         recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Linear,
     };
-    assert(calculate_redeemable_amount(200, vesting_schedule) == 350);
+    assert(calculate_redeemable_amount(200, vesting_schedule, Vec::new()) == 350);
     // After the end of the vesting period with 1000 claimed
     vesting_schedule = VestingSchedule {
         cliff_timestamp: 100,
@@ -74,6 +149,81 @@ fn test_redeemable_calculations() {
         total_amount: 1000,
         claimed_amount: 1000,
         recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Linear,
+    };
+    assert(calculate_redeemable_amount(300, vesting_schedule, Vec::new()) == 0);
+}
+#[test]
+fn test_periodic_redeemable_calculations() {
+    let vesting_schedule = VestingSchedule {
+        cliff_timestamp: 100,
+        cliff_amount: 100,
+        end_timestamp: 500,
+        total_amount: 1000,
+        claimed_amount: 0,
+        recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Periodic(100),
+    };
+    assert(is_valid_vesting_schedule(vesting_schedule, Vec::new()));
+    // Before cliff
+    assert(calculate_redeemable_amount(99, vesting_schedule, Vec::new()) == 0);
+    // At cliff, and before the first period has passed
+    assert(calculate_redeemable_amount(100, vesting_schedule, Vec::new()) == 100);
+    assert(calculate_redeemable_amount(199, vesting_schedule, Vec::new()) == 100);
+    // One of four periods
+    assert(calculate_redeemable_amount(200, vesting_schedule, Vec::new()) == 325);
+    // Three of four periods
+    assert(calculate_redeemable_amount(450, vesting_schedule, Vec::new()) == 775);
+    // End
+    assert(calculate_redeemable_amount(500, vesting_schedule, Vec::new()) == 1000);
+    // Duration that is not a whole number of periods
+    let invalid_schedule = VestingSchedule {
+        cliff_timestamp: 100,
+        cliff_amount: 100,
+        end_timestamp: 550,
+        total_amount: 1000,
+        claimed_amount: 0,
+        recipient: Identity::Address(Address::zero()),
+        curve: VestingCurve::Periodic(100),
+    };
+    assert(!is_valid_vesting_schedule(invalid_schedule, Vec::new()));
+}
+#[test]
+fn test_tranche_redeemable_calculations() {
+    let recipient = Identity::Address(Address::zero());
+    let mut vesting_schedule = VestingSchedule {
+        cliff_timestamp: 100,
+        cliff_amount: 0,
+        end_timestamp: 300,
+        total_amount: 1000,
+        claimed_amount: 0,
+        recipient,
+        curve: VestingCurve::Tranches,
     };
-    assert(calculate_redeemable_amount(300, vesting_schedule) == 0);
+    let mut tranches: Vec<Tranche> = Vec::new();
+    tranches.push(Tranche {
+        recipient,
+        timestamp: 100,
+        amount: 200,
+    });
+    tranches.push(Tranche {
+        recipient,
+        timestamp: 250,
+        amount: 300,
+    });
+    tranches.push(Tranche {
+        recipient,
+        timestamp: 300,
+        amount: 500,
+    });
+    assert(is_valid_vesting_schedule(vesting_schedule, tranches));
+    assert(calculate_redeemable_amount(99, vesting_schedule, tranches) == 0);
+    assert(calculate_redeemable_amount(100, vesting_schedule, tranches) == 200);
+    assert(calculate_redeemable_amount(260, vesting_schedule, tranches) == 500);
+    vesting_schedule.claimed_amount = 500;
+    assert(calculate_redeemable_amount(300, vesting_schedule, tranches) == 500);
+    // Tranches not adding up to the total amount
+    vesting_schedule.claimed_amount = 0;
+    vesting_schedule.total_amount = 900;
+    assert(!is_valid_vesting_schedule(vesting_schedule, tranches));
 }
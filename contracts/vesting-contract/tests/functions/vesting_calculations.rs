@@ -6,13 +6,19 @@ mod success {
 
     use test_utils::{
         data_structures::ContractInstance,
-        interfaces::vesting::{
-            get_vesting_schedule, load_vesting_schedules_from_json_file,
-            vesting_abi::{
-                claim_vested_tokens, get_redeemable_amount, get_vesting_schedule_call,
-                instantiate_vesting_contract, set_timestamp,
+        interfaces::{
+            token::{token_abi::mint_to_id, Token},
+            vesting::{
+                calculate_redeemable_amount, get_vesting_schedule,
+                load_vesting_schedules_from_json_file,
+                vesting_abi::{
+                    accept_beneficiary, add_schedule, claim_for, claim_for_many, claim_to,
+                    claim_vested_tokens, get_redeemable_amount, get_tranches,
+                    get_vesting_schedule_call, instantiate_vesting_contract, is_revoked,
+                    propose_beneficiary, revoke, set_timestamp,
+                },
+                Tranche, VestingContract, VestingCurve, VestingSchedule,
             },
-            VestingContract,
         },
     };
 
@@ -22,7 +28,7 @@ mod success {
 
     #[tokio::test]
     async fn create_vesting_contract() {
-        let (vest, _admin, recipient, asset) = setup(10000).await;
+        let (vest, admin, recipient, asset) = setup(10000).await;
 
         let recipient_identity = Identity::Address(recipient.address().into());
         let vesting_schedule = [get_vesting_schedule(
@@ -41,6 +47,9 @@ mod success {
                 .asset_id(&AssetId::zeroed().into())
                 .into(),
             vesting_schedule.to_vec(),
+            vec![],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
             true,
         )
         .await
@@ -58,6 +67,9 @@ mod success {
                 .asset_id(&AssetId::zeroed().into())
                 .into(),
             vesting_schedule.to_vec(),
+            vec![],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
             true,
         )
         .await;
@@ -90,6 +102,9 @@ mod success {
                 .asset_id(&AssetId::zeroed().into())
                 .into(),
             vesting_schedule.to_vec(),
+            vec![],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
             true,
         )
         .await;
@@ -165,6 +180,9 @@ mod success {
                 .asset_id(&AssetId::zeroed().into())
                 .into(),
             vesting_schedule.to_vec(),
+            vec![],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
             true,
         )
         .await;
@@ -260,6 +278,364 @@ mod success {
         assert_eq!(total_amount, rec_balance);
     }
 
+    #[tokio::test]
+    async fn proper_revoke_returns_unvested_to_treasury() {
+        let (vest, admin, recipient, asset) = setup(10000).await;
+        let cliff_timestamp = 100;
+        let end_timestamp = 200;
+        let total_amount = 10000;
+        let cliff_amount = 2000;
+        let recipient_identity = Identity::Address(recipient.address().into());
+        // the admin wallet doubles as the treasury
+        let treasury_identity = Identity::Address(admin.address().into());
+
+        let vesting_schedule = [get_vesting_schedule(
+            cliff_amount,
+            cliff_timestamp,
+            end_timestamp,
+            0,
+            total_amount,
+            recipient_identity,
+        )];
+
+        instantiate_vesting_contract(
+            &vest,
+            &asset
+                .contract_id()
+                .asset_id(&AssetId::zeroed().into())
+                .into(),
+            vesting_schedule.to_vec(),
+            vec![],
+            treasury_identity,
+            treasury_identity,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let _ = init_and_mint_to_vesting(&asset, &vest.contract, total_amount, &admin).await;
+
+        let asset_id = asset
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+        let provider = admin.provider().unwrap();
+
+        let recpient_vesting = ContractInstance::new(
+            VestingContract::new(vest.contract.contract_id().clone(), recipient.clone()),
+            vest.implementation_id,
+        );
+
+        // only the owner can revoke
+        let res = revoke(&recpient_vesting, recipient_identity).await;
+        assert!(res.is_err());
+
+        let _ = set_timestamp(
+            &vest,
+            cliff_timestamp + (end_timestamp - cliff_timestamp) / 2,
+        )
+        .await
+        .unwrap();
+
+        revoke(&vest, recipient_identity).await.unwrap();
+
+        let vested_amount = cliff_amount + (total_amount - cliff_amount) / 2;
+        let rec_balance = provider
+            .get_asset_balance(&recipient.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(rec_balance, vested_amount);
+
+        let treasury_balance = provider
+            .get_asset_balance(&admin.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(treasury_balance, total_amount - vested_amount);
+
+        let revoked = is_revoked(&vest, recipient_identity).await.unwrap().value;
+        assert!(revoked);
+
+        // nothing is left to claim, even after the original end
+        let _ = set_timestamp(&vest, end_timestamp + 1).await.unwrap();
+        let res = claim_vested_tokens(&recpient_vesting).await;
+        assert!(res.is_err());
+
+        let res = revoke(&vest, recipient_identity).await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn proper_add_schedule_and_change_beneficiary() {
+        let (vest, admin, recipient, asset) = setup(6000).await;
+        let admin_identity = Identity::Address(admin.address().into());
+        let recipient_identity = Identity::Address(recipient.address().into());
+
+        instantiate_vesting_contract(
+            &vest,
+            &asset
+                .contract_id()
+                .asset_id(&AssetId::zeroed().into())
+                .into(),
+            vec![get_vesting_schedule(
+                1000,
+                100,
+                200,
+                0,
+                6000,
+                admin_identity,
+            )],
+            vec![],
+            admin_identity,
+            admin_identity,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let _ = init_and_mint_to_vesting(&asset, &vest.contract, 6000, &admin).await;
+
+        let asset_id = asset
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+        let recpient_vesting = ContractInstance::new(
+            VestingContract::new(vest.contract.contract_id().clone(), recipient.clone()),
+            vest.implementation_id,
+        );
+
+        // the admin moves its schedule to the recipient wallet
+        propose_beneficiary(&vest, recipient_identity)
+            .await
+            .unwrap();
+
+        // only the proposed recipient can accept
+        let res = accept_beneficiary(&vest, admin_identity).await;
+        assert!(res.is_err());
+
+        accept_beneficiary(&recpient_vesting, admin_identity)
+            .await
+            .unwrap();
+
+        let res = get_vesting_schedule_call(&vest, recipient_identity)
+            .await
+            .unwrap();
+        assert_eq!(
+            res.value,
+            get_vesting_schedule(1000, 100, 200, 0, 6000, recipient_identity)
+        );
+        let res = get_vesting_schedule_call(&vest, admin_identity).await;
+        assert!(res.is_err());
+
+        // a new schedule for the admin wallet, funded by the attached tokens
+        let token = Token::new(asset.contract_id().clone(), admin.clone());
+        let _ = mint_to_id(&token, 4000, admin_identity).await;
+
+        let new_schedule = get_vesting_schedule(0, 100, 300, 0, 4000, admin_identity);
+
+        // the attached amount must match the schedule
+        let mut overfunded_schedule = new_schedule.clone();
+        overfunded_schedule.total_amount = 5000;
+        let res = add_schedule(&vest, asset_id, overfunded_schedule, vec![]).await;
+        assert!(res.is_err());
+
+        add_schedule(&vest, asset_id, new_schedule.clone(), vec![])
+            .await
+            .unwrap();
+
+        let res = get_vesting_schedule_call(&vest, admin_identity)
+            .await
+            .unwrap();
+        assert_eq!(res.value, new_schedule);
+
+        let _ = set_timestamp(&vest, 300).await.unwrap();
+        let res = get_redeemable_amount(&vest, 300, admin_identity)
+            .await
+            .unwrap();
+        assert_eq!(res.value, 4000);
+    }
+
+    #[tokio::test]
+    async fn proper_periodic_and_tranche_vesting() {
+        let (vest, admin, recipient, asset) = setup(10000).await;
+        let admin_identity = Identity::Address(admin.address().into());
+        let recipient_identity = Identity::Address(recipient.address().into());
+
+        // quarterly style steps for the admin, tranches for the recipient
+        let periodic_schedule = VestingSchedule {
+            cliff_timestamp: 100,
+            end_timestamp: 500,
+            cliff_amount: 1000,
+            total_amount: 5000,
+            claimed_amount: 0,
+            recipient: admin_identity,
+            curve: VestingCurve::Periodic(100),
+        };
+        let tranche_schedule = VestingSchedule {
+            cliff_timestamp: 200,
+            end_timestamp: 400,
+            cliff_amount: 0,
+            total_amount: 5000,
+            claimed_amount: 0,
+            recipient: recipient_identity,
+            curve: VestingCurve::Tranches,
+        };
+        let tranches = vec![
+            Tranche {
+                recipient: recipient_identity,
+                timestamp: 200,
+                amount: 1000,
+            },
+            Tranche {
+                recipient: recipient_identity,
+                timestamp: 300,
+                amount: 1500,
+            },
+            Tranche {
+                recipient: recipient_identity,
+                timestamp: 400,
+                amount: 2500,
+            },
+        ];
+
+        instantiate_vesting_contract(
+            &vest,
+            &asset
+                .contract_id()
+                .asset_id(&AssetId::zeroed().into())
+                .into(),
+            vec![periodic_schedule.clone(), tranche_schedule.clone()],
+            tranches.clone(),
+            admin_identity,
+            admin_identity,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let stored_tranches = get_tranches(&vest, recipient_identity).await.unwrap().value;
+        assert_eq!(stored_tranches, tranches);
+
+        for timestamp in [50, 100, 199, 200, 250, 300, 399, 400, 1000] {
+            let res = get_redeemable_amount(&vest, timestamp, admin_identity)
+                .await
+                .unwrap();
+            assert_eq!(
+                res.value,
+                calculate_redeemable_amount(timestamp, &periodic_schedule, &[]),
+                "Periodic redeemable amount does not match the mirror"
+            );
+
+            let res = get_redeemable_amount(&vest, timestamp, recipient_identity)
+                .await
+                .unwrap();
+            assert_eq!(
+                res.value,
+                calculate_redeemable_amount(timestamp, &tranche_schedule, &tranches),
+                "Tranche redeemable amount does not match the mirror"
+            );
+        }
+
+        let res = get_redeemable_amount(&vest, 250, admin_identity)
+            .await
+            .unwrap();
+        assert_eq!(res.value, 2000);
+
+        let res = get_redeemable_amount(&vest, 350, recipient_identity)
+            .await
+            .unwrap();
+        assert_eq!(res.value, 2500);
+    }
+
+    #[tokio::test]
+    async fn proper_claim_for_and_claim_to() {
+        let (vest, admin, recipient, asset) = setup(10000).await;
+        let admin_identity = Identity::Address(admin.address().into());
+        let recipient_identity = Identity::Address(recipient.address().into());
+
+        instantiate_vesting_contract(
+            &vest,
+            &asset
+                .contract_id()
+                .asset_id(&AssetId::zeroed().into())
+                .into(),
+            vec![
+                get_vesting_schedule(0, 100, 200, 0, 5000, admin_identity),
+                get_vesting_schedule(0, 100, 200, 0, 5000, recipient_identity),
+            ],
+            vec![],
+            admin_identity,
+            admin_identity,
+            true,
+        )
+        .await
+        .unwrap();
+
+        let _ = init_and_mint_to_vesting(&asset, &vest.contract, 10000, &admin).await;
+
+        let asset_id = asset
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+        let provider = admin.provider().unwrap();
+
+        // anyone can push the vested tokens, they still go to the recipient
+        let _ = set_timestamp(&vest, 150).await.unwrap();
+        claim_for(&vest, recipient_identity).await.unwrap();
+
+        let rec_balance = provider
+            .get_asset_balance(&recipient.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(rec_balance, 2500);
+
+        let res = claim_for(&vest, recipient_identity).await;
+        assert!(res.is_err());
+
+        // the recipient sends the rest of its tokens to another destination
+        let recpient_vesting = ContractInstance::new(
+            VestingContract::new(vest.contract.contract_id().clone(), recipient.clone()),
+            vest.implementation_id,
+        );
+        let _ = set_timestamp(&vest, 200).await.unwrap();
+        claim_to(&recpient_vesting, admin_identity).await.unwrap();
+
+        let rec_balance = provider
+            .get_asset_balance(&recipient.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(rec_balance, 2500);
+
+        let admin_balance = provider
+            .get_asset_balance(&admin.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(admin_balance, 2500);
+
+        // the batch skips recipients with nothing to redeem and unknown addresses
+        claim_for_many(
+            &vest,
+            vec![
+                admin_identity,
+                recipient_identity,
+                Identity::Address(Address::zeroed()),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let admin_balance = provider
+            .get_asset_balance(&admin.address(), asset_id)
+            .await
+            .unwrap();
+        assert_eq!(admin_balance, 7500);
+
+        let schedule = get_vesting_schedule_call(&vest, admin_identity)
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(schedule.claimed_amount, 5000);
+    }
+
     #[tokio::test]
     async fn proper_json_vesting_parsing() {
         let vesting_schedules = load_vesting_schedules_from_json_file(
@@ -283,7 +659,7 @@ mod success {
 
 mod failure {
     use test_utils::interfaces::vesting::{
-        get_vesting_schedule, vesting_abi::instantiate_vesting_contract,
+        get_vesting_schedule, vesting_abi::instantiate_vesting_contract, Tranche, VestingCurve,
     };
 
     use super::*;
@@ -291,7 +667,7 @@ mod failure {
     #[tokio::test]
     async fn fails_to_initialize_vesting_with_incorrect_total_amount() {
         let total_amount = 10000;
-        let (vest, _, recipient, asset) = setup(total_amount).await;
+        let (vest, admin, recipient, asset) = setup(total_amount).await;
 
         let vesting_schedule = [get_vesting_schedule(
             3000,
@@ -309,6 +685,48 @@ mod failure {
                 .asset_id(&AssetId::zeroed().into())
                 .into(),
             vesting_schedule.to_vec(),
+            vec![],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
+            true,
+        )
+        .await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn fails_to_initialize_vesting_with_invalid_tranches() {
+        let total_amount = 10000;
+        let (vest, admin, recipient, asset) = setup(total_amount).await;
+        let recipient_identity = Identity::Address(recipient.address().into());
+
+        let mut vesting_schedule =
+            get_vesting_schedule(0, 1000, 2000, 0, total_amount, recipient_identity);
+        vesting_schedule.curve = VestingCurve::Tranches;
+
+        // tranches adding up to less than the total amount
+        let res = instantiate_vesting_contract(
+            &vest,
+            &asset
+                .contract_id()
+                .asset_id(&AssetId::zeroed().into())
+                .into(),
+            vec![vesting_schedule],
+            vec![
+                Tranche {
+                    recipient: recipient_identity,
+                    timestamp: 1000,
+                    amount: 4000,
+                },
+                Tranche {
+                    recipient: recipient_identity,
+                    timestamp: 2000,
+                    amount: 4000,
+                },
+            ],
+            Identity::Address(admin.address().into()),
+            Identity::Address(admin.address().into()),
             true,
         )
         .await;
@@ -60,7 +60,7 @@ pub async fn add_asset(symbol: &str) {
             contract: ContractId::from_str(asset_constants.pyth_contract_id).unwrap(),
             price_id: Bits256::from_hex_str(asset_constants.pyth_price_id).unwrap(),
         }),
-        redstone_oracle: None, // TODO: Add redstone oracle when it's ready
+        redstone_oracle: None, // Redstone is configured after registration with `set-redstone`
     };
 
     // Redstone oracle is not required for initialization
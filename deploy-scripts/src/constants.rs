@@ -8,6 +8,11 @@ pub const MAINNET_CONTRACTS_FILE: &str = "mainnet.contracts.json";
 pub const TESTNET_RPC: &str = "https://testnet.fuel.network/v1/playground";
 pub const MAINNET_RPC: &str = "https://mainnet.fuel.network/v1/playground";
 
+pub const VESTING_SCHEDULE_PATH: &str = "deploy-scripts/vesting/vesting.csv";
+pub const CLIFF_PERCENTAGE: f64 = 0.0; // 0% cliff
+pub const SECONDS_TO_CLIFF: u64 = 7 * 24 * 60 * 60; // 7 days
+pub const SECONDS_VESTING_DURATION: u64 = 2 * 365 * 24 * 60 * 60; // 2 years
+
 pub const TESTNET_TREASURY_IDENTITY: &str =
     "0xa5ac02c203dde9b52cb2ab29bdd0dfee1e7a17f97339ff2ead92de4eebb62305";
 pub const MAINNET_TREASURY_IDENTITY: &str =
@@ -71,6 +76,12 @@ pub const PYTH_FUEL_PRICE_ID: &str =
     "8a757d54e5d34c7ff1aea8502a2d968686027a304d00418092aaf7e60ed98d95";
 pub const PYTH_PZETH_PRICE_ID: &str = ""; // Waiting for Pyth price feed
 
+// redstone
+// Feed ids are derived from the asset symbol, see `redstone_feed_id`
+pub const TESTNET_REDSTONE_CONTRACT_ID: &str = ""; // Waiting for Redstone deployment
+pub const MAINNET_REDSTONE_CONTRACT_ID: &str = ""; // Waiting for Redstone deployment
+pub const REDSTONE_PRECISION: u32 = 8;
+
 pub struct AssetConstants {
     pub symbol: &'static str,
     pub asset_contract_id: Option<&'static str>,
@@ -7,21 +7,21 @@ use serde_json::json;
 use std::str::FromStr;
 use test_utils::data_structures::ProtocolContracts;
 use test_utils::interfaces::hint_helper::HintHelper;
+use test_utils::interfaces::lens::Lens;
 use test_utils::interfaces::multi_trove_getter::MultiTroveGetter;
 use test_utils::interfaces::vesting;
 
-use crate::constants::{MAINNET_CONTRACTS_FILE, TESTNET_CONTRACTS_FILE};
+use crate::constants::{
+    CLIFF_PERCENTAGE, MAINNET_CONTRACTS_FILE, SECONDS_TO_CLIFF, SECONDS_VESTING_DURATION,
+    TESTNET_CONTRACTS_FILE, VESTING_SCHEDULE_PATH,
+};
 use crate::utils::utils::{is_testnet, load_vesting_schedules_from_csv, setup_wallet};
 
 use test_utils::setup::common::{
-    deploy_core_contracts, deploy_hint_helper, deploy_multi_trove_getter, initialize_core_contracts,
+    deploy_core_contracts, deploy_hint_helper, deploy_lens, deploy_multi_trove_getter,
+    initialize_core_contracts,
 };
 
-const VESTING_SCHEDULE_PATH: &str = "deploy-scripts/vesting/vesting.csv";
-const CLIFF_PERCENTAGE: f64 = 0.0; // 0% cliff
-const SECONDS_TO_CLIFF: u64 = 7 * 24 * 60 * 60; // 7 days
-const SECONDS_VESTING_DURATION: u64 = 2 * 365 * 24 * 60 * 60; // 2 years
-
 pub mod deployment {
     use crate::constants::{MAINNET_TREASURY_IDENTITY, TESTNET_TREASURY_IDENTITY};
 
@@ -48,11 +48,17 @@ pub mod deployment {
         //--------------- Deploy ---------------
         let core_contracts =
             deploy_and_initialize_all_core_contracts(wallet.clone(), is_testnet).await;
-        let (hint_helper, multi_trove_getter) =
+        let (hint_helper, multi_trove_getter, lens) =
             deploy_frontend_helper_contracts(wallet.clone(), &core_contracts).await;
 
         //--------------- Write to file ---------------
-        write_contracts_to_file(core_contracts, hint_helper, multi_trove_getter, is_testnet);
+        write_contracts_to_file(
+            core_contracts,
+            hint_helper,
+            multi_trove_getter,
+            lens,
+            is_testnet,
+        );
     }
 
     pub async fn deploy_and_initialize_all_core_contracts(
@@ -81,6 +87,9 @@ pub mod deployment {
             &core_contracts.vesting_contract,
             &core_contracts.fpt_asset_id,
             vesting_schedules,
+            vec![],
+            Identity::Address(wallet.address().into()),
+            treasury_identity,
             false,
         )
         .await
@@ -92,21 +101,27 @@ pub mod deployment {
     pub async fn deploy_frontend_helper_contracts(
         wallet: WalletUnlocked,
         core_contracts: &ProtocolContracts<WalletUnlocked>,
-    ) -> (HintHelper<WalletUnlocked>, MultiTroveGetter<WalletUnlocked>) {
+    ) -> (
+        HintHelper<WalletUnlocked>,
+        MultiTroveGetter<WalletUnlocked>,
+        Lens<WalletUnlocked>,
+    ) {
         let hint_helper = deploy_hint_helper(&wallet).await;
         let multi_trove_getter = deploy_multi_trove_getter(
             &wallet,
             &core_contracts.sorted_troves.contract.contract_id().into(),
         )
         .await;
+        let lens = deploy_lens(&wallet, core_contracts).await;
 
-        return (hint_helper, multi_trove_getter);
+        return (hint_helper, multi_trove_getter, lens);
     }
 
     fn write_contracts_to_file(
         contracts: ProtocolContracts<WalletUnlocked>,
         hint_helper: HintHelper<WalletUnlocked>,
         multi_trove_getter: MultiTroveGetter<WalletUnlocked>,
+        lens: Lens<WalletUnlocked>,
         is_testnet: bool,
     ) {
         let mut file = File::create(match is_testnet {
@@ -144,6 +159,7 @@ pub mod deployment {
             "vesting_contract_implementation_id": format!("0x{}", contracts.vesting_contract.implementation_id.to_string()),
             "hint_helper": hint_helper.contract_id().to_string(),
             "multi_trove_getter": multi_trove_getter.contract_id().to_string(),
+            "lens": lens.contract_id().to_string(),
             "asset_contracts": contracts.asset_contracts.iter().map(|asset_contracts| {
                 json!({
                     "oracle": asset_contracts.oracle.contract.contract_id().to_string(),
@@ -3,5 +3,7 @@ pub mod constants;
 pub mod deploy;
 pub mod pause;
 pub mod sanity_check;
+pub mod set_redstone;
 pub mod transfer_ownership;
 pub mod utils;
+pub mod vesting;
@@ -3,7 +3,9 @@ use deploy_scripts::{
     deploy::deployment::deploy,
     pause::{pause_protocol, unpause_protocol},
     sanity_check::sanity_check,
+    set_redstone::set_redstone,
     transfer_ownership::transfer_owner,
+    vesting::{add_vesting_schedules, revoke_vesting},
 };
 
 #[tokio::main]
@@ -11,7 +13,7 @@ pub async fn main() {
     let args: Vec<String> = std::env::args().collect();
     if args.len() < 2 {
         println!(
-            "Please specify 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'sanity-check', or 'transfer-owner <address>'"
+            "Please specify 'deploy', 'add-asset <symbol>', 'set-redstone <symbol>', 'pause', 'unpause', 'sanity-check', 'transfer-owner <address>', 'add-vesting <csv path>', or 'revoke-vesting <address>'"
         );
         return;
     }
@@ -25,6 +27,13 @@ pub async fn main() {
             }
             add_asset(&args[2]).await
         },
+        "set-redstone" => {
+            if args.len() < 3 {
+                println!("Please specify an asset symbol (e.g., 'set-redstone ETH')");
+                return;
+            }
+            set_redstone(&args[2]).await
+        },
         "pause" => pause_protocol().await,
         "unpause" => unpause_protocol().await,
         "sanity-check" => sanity_check().await,
@@ -35,8 +44,22 @@ pub async fn main() {
             }
             transfer_owner(&args[2]).await
         },
+        "add-vesting" => {
+            if args.len() < 3 {
+                println!("Please specify the path of the CSV with the new vesting schedules");
+                return;
+            }
+            add_vesting_schedules(&args[2]).await
+        },
+        "revoke-vesting" => {
+            if args.len() < 3 {
+                println!("Please specify the address of the recipient to revoke");
+                return;
+            }
+            revoke_vesting(&args[2]).await
+        },
         _ => println!(
-            "Invalid argument. Use 'deploy', 'add-asset <symbol>', 'pause', 'unpause', 'sanity-check', or 'transfer-owner <address>'"
+            "Invalid argument. Use 'deploy', 'add-asset <symbol>', 'set-redstone <symbol>', 'pause', 'unpause', 'sanity-check', 'transfer-owner <address>', 'add-vesting <csv path>', or 'revoke-vesting <address>'"
         ),
     }
 }
@@ -0,0 +1,215 @@
+use crate::constants::{
+    MAINNET_CONTRACTS_FILE, MAINNET_REDSTONE_CONTRACT_ID, REDSTONE_PRECISION,
+    TESTNET_CONTRACTS_FILE, TESTNET_REDSTONE_CONTRACT_ID,
+};
+use crate::utils::utils::{is_testnet, setup_wallet};
+use dotenv::dotenv;
+use fuels::prelude::*;
+use fuels::types::{bech32::Bech32ContractId, Identity, U256};
+use serde_json::json;
+use std::fs::File;
+use std::io::Write;
+use std::str::FromStr;
+use test_utils::data_structures::ContractInstance;
+use test_utils::interfaces::oracle::{oracle_abi, Oracle, RedstoneConfig, State};
+use test_utils::interfaces::redstone_oracle::RedstoneCore;
+
+pub async fn set_redstone(symbol: &str) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address: Address = wallet.address().into();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let redstone_contract_id = match is_testnet {
+        true => TESTNET_REDSTONE_CONTRACT_ID,
+        false => MAINNET_REDSTONE_CONTRACT_ID,
+    };
+    if redstone_contract_id.is_empty() {
+        panic!(
+            "Redstone contract is not configured for {} network",
+            if is_testnet { "testnet" } else { "mainnet" }
+        );
+    }
+
+    let oracle = load_oracle(wallet.clone(), symbol, is_testnet);
+    let redstone_contract = ContractId::from_str(redstone_contract_id).unwrap();
+    let redstone = RedstoneCore::new(Bech32ContractId::from(redstone_contract), wallet.clone());
+    let config = RedstoneConfig {
+        contract_id: redstone_contract,
+        price_id: redstone_feed_id(symbol),
+        precision: REDSTONE_PRECISION,
+    };
+
+    let current_config = oracle_abi::get_redstone_config(&oracle).await.value;
+    let pending_config = oracle_abi::get_pending_redstone_config(&oracle).await.value;
+    println!("Current Redstone config: {:?}", current_config);
+    println!("Pending Redstone config: {:?}", pending_config);
+    println!("Target Redstone config: {:?}", config);
+
+    if current_config.as_ref() == Some(&config) {
+        println!("Redstone config is already up to date");
+        return;
+    }
+
+    println!(
+        "Are you sure you want to set the Redstone config for {}? (y/n)",
+        symbol
+    );
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    if input.trim().to_lowercase() != "y" {
+        println!("Operation cancelled.");
+        return;
+    }
+
+    // Oracle ownership is initialized along with the asset, in deploy and add-asset
+    match oracle_abi::owner(&oracle).await.value {
+        State::Initialized(owner) if owner == Identity::Address(address) => {}
+        state => {
+            println!(
+                "Oracle is not owned by this wallet, cannot update the Redstone config: {:?}",
+                state
+            );
+            return;
+        }
+    }
+
+    match pending_config {
+        // Nothing has ever been configured, the initializer can set it directly
+        None if current_config.is_none() => {
+            match oracle_abi::set_redstone_config(&oracle, &redstone, config.clone()).await {
+                Ok(_) => {
+                    write_redstone_config_to_file(symbol, &config, is_testnet);
+                    println!("Redstone config set successfully");
+                }
+                // A config set once before, or a wallet other than the initializer, has to go through the timelock
+                Err(error)
+                    if error.to_string().contains("Redstone config already set")
+                        || error
+                            .to_string()
+                            .contains("Only initializer can set Redstone config") =>
+                {
+                    println!("Redstone config can't be set directly: {}", error);
+                    propose(&oracle, config).await
+                }
+                Err(error) => {
+                    println!("Failed to set the Redstone config: {}", error);
+                    return;
+                }
+            }
+        }
+        Some(pending) if pending.config.as_ref() == Some(&config) => {
+            let now = tai64::Tai64::now().0;
+            if now < pending.effective_time {
+                println!(
+                    "Redstone config update is timelocked for another {} seconds",
+                    pending.effective_time - now
+                );
+                return;
+            }
+            oracle_abi::execute_redstone_config_update(&oracle)
+                .await
+                .unwrap();
+            write_redstone_config_to_file(symbol, &config, is_testnet);
+            println!("Redstone config updated successfully");
+        }
+        _ => propose(&oracle, config).await,
+    }
+}
+
+async fn propose(oracle: &ContractInstance<Oracle<WalletUnlocked>>, config: RedstoneConfig) {
+    oracle_abi::propose_redstone_config(oracle, config)
+        .await
+        .unwrap();
+    let pending = oracle_abi::get_pending_redstone_config(oracle)
+        .await
+        .value
+        .unwrap();
+    println!(
+        "Redstone config update proposed, run this command again after TAI64 timestamp {}",
+        pending.effective_time
+    );
+}
+
+// Redstone feed ids are the asset symbol as left aligned bytes, e.g. bytes32("ETH")
+pub fn redstone_feed_id(symbol: &str) -> U256 {
+    let mut bytes = [0u8; 32];
+    let symbol = symbol.to_uppercase();
+    bytes[..symbol.len()].copy_from_slice(symbol.as_bytes());
+    U256::from_big_endian(&bytes)
+}
+
+fn contracts_file(is_testnet: bool) -> &'static str {
+    match is_testnet {
+        true => TESTNET_CONTRACTS_FILE,
+        false => MAINNET_CONTRACTS_FILE,
+    }
+}
+
+fn load_oracle(
+    wallet: WalletUnlocked,
+    symbol: &str,
+    is_testnet: bool,
+) -> ContractInstance<Oracle<WalletUnlocked>> {
+    let contracts: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(contracts_file(is_testnet)).unwrap())
+            .unwrap();
+
+    let asset_contract = contracts["asset_contracts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|asset_contract| {
+            asset_contract["symbol"]
+                .as_str()
+                .map_or(false, |s| s.eq_ignore_ascii_case(symbol))
+        })
+        .unwrap_or_else(|| panic!("Asset '{}' not found in contracts file", symbol));
+
+    let oracle_contract_id: Bech32ContractId =
+        asset_contract["oracle"].as_str().unwrap().parse().unwrap();
+    let oracle_implementation_id: ContractId = asset_contract["oracle_implementation_id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    ContractInstance::new(
+        Oracle::new(oracle_contract_id, wallet),
+        oracle_implementation_id.into(),
+    )
+}
+
+fn write_redstone_config_to_file(symbol: &str, config: &RedstoneConfig, is_testnet: bool) {
+    let mut contracts: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(contracts_file(is_testnet))
+            .expect("Failed to read contracts.json"),
+    )
+    .expect("Failed to parse contracts.json");
+
+    for asset_contract in contracts["asset_contracts"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+    {
+        if asset_contract["symbol"]
+            .as_str()
+            .map_or(false, |s| s.eq_ignore_ascii_case(symbol))
+        {
+            asset_contract["redstone"] = json!({
+                "redstone_contract": format!("0x{}", config.contract_id),
+                "redstone_price_id": config.price_id.to_string(),
+                "redstone_precision": config.precision,
+            });
+        }
+    }
+
+    let mut file = File::create(contracts_file(is_testnet))
+        .expect("Failed to open contracts.json for writing");
+    file.write_all(serde_json::to_string_pretty(&contracts).unwrap().as_bytes())
+        .expect("Failed to write to contracts.json");
+}
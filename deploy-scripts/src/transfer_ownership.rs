@@ -2,7 +2,9 @@ use crate::utils::utils::{is_testnet, load_core_contracts, setup_wallet};
 use dotenv::dotenv;
 use fuels::types::Identity;
 use test_utils::interfaces::{
-    borrow_operations::borrow_operations_abi, protocol_manager::protocol_manager_abi,
+    borrow_operations::borrow_operations_abi,
+    oracle::{oracle_abi, State},
+    protocol_manager::protocol_manager_abi,
 };
 
 pub async fn transfer_owner(new_owner: &str) {
@@ -43,5 +45,22 @@ pub async fn transfer_owner(new_owner: &str) {
             .await
             .unwrap();
 
+    // Oracles are owned directly by the wallet that initialized them along with the asset
+    for asset_contract in &core_contracts.asset_contracts {
+        let oracle_id = asset_contract.oracle.contract.contract_id();
+        match oracle_abi::owner(&asset_contract.oracle).await.value {
+            State::Initialized(owner) if owner == Identity::Address(address.into()) => {
+                let _ = oracle_abi::transfer_owner(&asset_contract.oracle, new_owner_identity)
+                    .await
+                    .unwrap();
+                println!("Oracle {} ownership transferred", oracle_id);
+            }
+            state => println!(
+                "Skipping oracle {}, not owned by this wallet: {:?}",
+                oracle_id, state
+            ),
+        }
+    }
+
     println!("Ownership transferred successfully to {}", new_owner);
 }
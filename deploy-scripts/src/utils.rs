@@ -14,7 +14,9 @@ pub mod utils {
     use test_utils::interfaces::oracle::oracle_abi;
     use test_utils::interfaces::pyth_oracle::pyth_oracle_abi;
     use test_utils::interfaces::redstone_oracle::redstone_oracle_abi;
-    use test_utils::interfaces::vesting::{VestingSchedule, TOTAL_AMOUNT_VESTED};
+    use test_utils::interfaces::vesting::{
+        is_valid_vesting_schedule, VestingCurve, VestingSchedule, TOTAL_AMOUNT_VESTED,
+    };
     use test_utils::setup::common::get_absolute_path_from_relative;
     use test_utils::{
         data_structures::ProtocolContracts,
@@ -479,6 +481,57 @@ pub mod utils {
         seconds_to_cliff: u64,
         seconds_vesting_duration: u64,
         treasury_identity: Identity,
+    ) -> Vec<VestingSchedule> {
+        let now_unix = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        let end_timestamp = now_unix + seconds_to_cliff + seconds_vesting_duration;
+        let now_unix_and_5_minutes = now_unix + 5 * 60;
+
+        let now_and_5_minutes = tai64::Tai64::from_unix(now_unix_and_5_minutes.try_into().unwrap());
+        let end_timestamp = tai64::Tai64::from_unix(end_timestamp.try_into().unwrap());
+
+        let mut schedules = load_recipient_vesting_schedules_from_csv(
+            path,
+            cliff_percentage,
+            seconds_to_cliff,
+            seconds_vesting_duration,
+        );
+        // take the sum of all total_amounts
+        let total_sum: u64 = schedules.iter().map(|s| s.total_amount).sum();
+        println!("Total sum of all vesting amounts: {}", total_sum);
+        // add one more schedule with the remaining amount
+        let remaining_amount = TOTAL_AMOUNT_VESTED - total_sum;
+        println!("Remaining amount: {}", remaining_amount);
+        // treasury vesting schedule
+        schedules.push(VestingSchedule {
+            cliff_amount: remaining_amount,
+            cliff_timestamp: now_and_5_minutes.0, // cliff timestamp is now + 5 minutes
+            end_timestamp: end_timestamp.0,
+            claimed_amount: 0,
+            total_amount: remaining_amount,
+            recipient: treasury_identity,
+            curve: VestingCurve::Linear,
+        });
+        for schedule in &schedules {
+            assert!(
+                is_valid_vesting_schedule(schedule, &[]),
+                "Invalid vesting schedule: {:?}",
+                schedule
+            );
+        }
+        schedules
+    }
+
+    // Schedules of the recipients listed in the CSV, without the treasury schedule.
+    // An optional sixth column sets a release period in seconds, for step unlocks instead of a linear release
+    pub fn load_recipient_vesting_schedules_from_csv(
+        path: &str,
+        cliff_percentage: f64,
+        seconds_to_cliff: u64,
+        seconds_vesting_duration: u64,
     ) -> Vec<VestingSchedule> {
         let absolute_path = get_absolute_path_from_relative(path);
         let file = File::open(&absolute_path).expect("Failed to open file");
@@ -495,9 +548,7 @@ pub mod utils {
 
         let cliff_timestamp = now_unix + seconds_to_cliff;
         let end_timestamp = cliff_timestamp + seconds_vesting_duration;
-        let now_unix_and_5_minutes = now_unix + 5 * 60;
 
-        let now_and_5_minutes = tai64::Tai64::from_unix(now_unix_and_5_minutes.try_into().unwrap());
         let cliff_timestamp = tai64::Tai64::from_unix(cliff_timestamp.try_into().unwrap());
         let end_timestamp = tai64::Tai64::from_unix(end_timestamp.try_into().unwrap());
 
@@ -521,6 +572,15 @@ pub mod utils {
                 panic!("No valid wallet address found in row: {:?}", record);
             };
 
+            let curve = match record.get(5).filter(|period| !period.is_empty()) {
+                Some(period) => VestingCurve::Periodic(
+                    period
+                        .parse::<u64>()
+                        .expect("Invalid vesting period in CSV"),
+                ),
+                None => VestingCurve::Linear,
+            };
+
             let schedule = VestingSchedule {
                 cliff_amount: (total_amount as f64 * cliff_percentage) as u64,
                 cliff_timestamp: cliff_timestamp.0,
@@ -528,25 +588,15 @@ pub mod utils {
                 claimed_amount: 0,
                 total_amount,
                 recipient,
+                curve,
             };
 
+            if !is_valid_vesting_schedule(&schedule, &[]) {
+                panic!("Invalid vesting schedule in CSV row: {:?}", record);
+            }
+
             schedules.push(schedule);
         }
-        // take the sum of all total_amounts
-        let total_sum: u64 = schedules.iter().map(|s| s.total_amount).sum();
-        println!("Total sum of all vesting amounts: {}", total_sum);
-        // add one more schedule with the remaining amount
-        let remaining_amount = TOTAL_AMOUNT_VESTED - total_sum;
-        println!("Remaining amount: {}", remaining_amount);
-        // treasury vesting schedule
-        schedules.push(VestingSchedule {
-            cliff_amount: remaining_amount,
-            cliff_timestamp: now_and_5_minutes.0, // cliff timestamp is now + 5 minutes
-            end_timestamp: end_timestamp.0,
-            claimed_amount: 0,
-            total_amount: remaining_amount,
-            recipient: treasury_identity,
-        });
         schedules
     }
 }
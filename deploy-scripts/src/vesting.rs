@@ -0,0 +1,99 @@
+use crate::constants::{CLIFF_PERCENTAGE, SECONDS_TO_CLIFF, SECONDS_VESTING_DURATION};
+use crate::utils::utils::{
+    is_testnet, load_core_contracts, load_recipient_vesting_schedules_from_csv, setup_wallet,
+};
+use dotenv::dotenv;
+use fuels::types::{Address, Identity};
+use std::str::FromStr;
+use test_utils::interfaces::vesting::vesting_abi;
+
+// Adds the schedules listed in a CSV with the same format as vesting.csv, funded from the wallet
+pub async fn add_vesting_schedules(path: &str) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address = wallet.address();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
+
+    let schedules = load_recipient_vesting_schedules_from_csv(
+        path,
+        CLIFF_PERCENTAGE,
+        SECONDS_TO_CLIFF,
+        SECONDS_VESTING_DURATION,
+    );
+    let total_sum: u64 = schedules.iter().map(|s| s.total_amount).sum();
+
+    println!(
+        "Are you sure you want to add {} vesting schedules for a total of {} FPT? (y/n)",
+        schedules.len(),
+        total_sum
+    );
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    if input.trim().to_lowercase() != "y" {
+        println!("Operation cancelled.");
+        return;
+    }
+
+    for schedule in schedules {
+        let recipient = schedule.recipient;
+        let _ = vesting_abi::add_schedule(
+            &core_contracts.vesting_contract,
+            core_contracts.fpt_asset_id,
+            schedule,
+            vec![],
+        )
+        .await
+        .unwrap();
+        println!("Added vesting schedule for {:?}", recipient);
+    }
+
+    println!("Vesting schedules added successfully");
+}
+
+// Revokes the schedule of a recipient, the unvested tokens are returned to the treasury
+pub async fn revoke_vesting(recipient: &str) {
+    dotenv().ok();
+
+    let wallet = setup_wallet().await;
+    let address = wallet.address();
+    println!("🔑 Wallet address: {}", address);
+
+    let is_testnet = is_testnet(wallet.clone()).await;
+    let core_contracts = load_core_contracts(wallet.clone(), is_testnet);
+
+    let recipient_identity =
+        Identity::Address(Address::from_str(recipient).expect("Invalid address format"));
+
+    let schedule = vesting_abi::get_vesting_schedule_call(
+        &core_contracts.vesting_contract,
+        recipient_identity,
+    )
+    .await
+    .expect("No vesting schedule found for this address")
+    .value;
+
+    println!(
+        "Are you sure you want to revoke the vesting schedule of {} ({} of {} FPT claimed)? (y/n)",
+        recipient, schedule.claimed_amount, schedule.total_amount
+    );
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    if input.trim().to_lowercase() != "y" {
+        println!("Operation cancelled.");
+        return;
+    }
+
+    let _ = vesting_abi::revoke(&core_contracts.vesting_contract, recipient_identity)
+        .await
+        .unwrap();
+
+    println!("Vesting schedule of {} revoked successfully", recipient);
+}
@@ -9,6 +9,7 @@ abi BorrowOperations {
         coll_surplus_pool_contract: ContractId,
         active_pool: ContractId,
         sorted_troves_contract: ContractId,
+        stability_pool_contract: ContractId,
     );
 
     #[storage(read, write)]
@@ -33,6 +34,9 @@ abi BorrowOperations {
     #[storage(read, write), payable]
     fn add_coll(upper_hint: Identity, lower_hint: Identity);
 
+    #[storage(read, write), payable]
+    fn move_asset_gain_to_trove(borrower: Identity, upper_hint: Identity, lower_hint: Identity);
+
     #[storage(read, write)]
     fn withdraw_coll(
         amount: u64,
@@ -55,6 +59,9 @@ abi BorrowOperations {
     #[storage(read, write), payable]
     fn close_trove(asset: AssetId);
 
+    #[storage(read)]
+    fn set_auto_deleverage(trigger_icr: u64, target_icr: u64, asset: AssetId);
+
     #[storage(read)]
     fn claim_collateral(asset: AssetId);
 
@@ -1,5 +1,11 @@
 library;
 
+pub struct EmissionPhase {
+    // Supply cap reached at the end of the phase, ramped to linearly from the cap at the start of the phase
+    pub target_supply_cap: u64,
+    pub duration: u64,
+}
+
 abi CommunityIssuance {
     // Initialize contract
     #[storage(read, write)]
@@ -16,9 +22,24 @@ abi CommunityIssuance {
     #[storage(read, write)]
     fn public_start_rewards_increase_transition_after_deadline();
 
+    #[storage(read, write)]
+    fn set_emission_schedule(phases: Vec<EmissionPhase>);
+
     #[storage(read, write)]
     fn issue_fpt() -> u64;
 
+    #[storage(read)]
+    fn get_emission_schedule() -> Vec<EmissionPhase>;
+
+    #[storage(read)]
+    fn get_supply_cap() -> u64;
+
+    #[storage(read)]
+    fn get_total_fpt_issued() -> u64;
+
+    #[storage(read)]
+    fn get_projected_issuance(time: u64) -> u64;
+
     #[storage(read)]
     fn send_fpt(account: Identity, amount: u64);
 
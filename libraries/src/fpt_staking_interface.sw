@@ -10,6 +10,16 @@ pub struct ReadStorage {
     pub is_initialized: bool,
 }
 
+pub struct LockedPosition {
+    pub position_id: u64,
+    pub amount: u64,
+    // Amount multiplied by the lock boost, used instead of the amount for the share of fees,
+    // back to the amount once the position is unlocked and touched
+    pub weight: u64,
+    pub lock_time: u64,
+    pub unlock_time: u64,
+}
+
 abi FPTStaking {
     #[storage(read, write), payable]
     fn stake();
@@ -17,6 +27,21 @@ abi FPTStaking {
     #[storage(read, write)]
     fn unstake(amount: u64);
 
+    #[storage(read, write), payable]
+    fn stake_locked(lock_weeks: u64) -> u64;
+
+    #[storage(read, write)]
+    fn unstake_locked(position_id: u64);
+
+    #[storage(read, write)]
+    fn poke_locked_position(id: Identity, position_id: u64);
+
+    #[storage(read, write)]
+    fn claim(assets: Vec<AssetId>, include_usdf: bool);
+
+    #[storage(read, write)]
+    fn claim_to(recipient: Identity, assets: Vec<AssetId>, include_usdf: bool);
+
     #[storage(read, write)]
     fn add_asset(asset_id: AssetId);
 
@@ -26,6 +51,7 @@ abi FPTStaking {
         borrower_operations_address: ContractId,
         fpt_asset_id: AssetId,
         usdf_asset_id: AssetId,
+        debugging: bool,
     );
 
     #[storage(read)]
@@ -45,4 +71,36 @@ abi FPTStaking {
 
     #[storage(read, write)]
     fn increase_f_asset(asset_fee_amount: u64, asset_id: AssetId);
+
+    #[storage(read)]
+    fn get_pending_fpt_gain(id: Identity) -> u64;
+
+    #[storage(read)]
+    fn get_locked_positions(id: Identity) -> Vec<LockedPosition>;
+
+    #[storage(read)]
+    fn get_pending_locked_usdf_gain(id: Identity, position_id: u64) -> u64;
+
+    #[storage(read)]
+    fn get_pending_locked_asset_gain(id: Identity, position_id: u64, asset_id: AssetId) -> u64;
+
+    #[storage(read)]
+    fn get_pending_locked_fpt_gain(id: Identity, position_id: u64) -> u64;
+
+    #[storage(read)]
+    fn get_effective_weight(id: Identity) -> u64;
+
+    #[storage(read)]
+    fn get_total_weight() -> u64;
+
+    fn get_lock_boost(lock_weeks: u64) -> u64;
+
+    #[storage(read)]
+    fn get_early_exit_penalty(id: Identity, position_id: u64) -> u64;
+
+    #[storage(read)]
+    fn get_current_time() -> u64;
+
+    #[storage(write, read)]
+    fn set_current_time(time: u64);
 }
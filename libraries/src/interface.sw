@@ -15,3 +15,5 @@ pub mod fpt_staking_interface;
 pub mod fpt_token_interface;
 pub mod community_issuance_interface;
 pub mod oracle_interface;
+pub mod swap_adapter_interface;
+pub mod stability_pool_vault_interface;
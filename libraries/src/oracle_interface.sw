@@ -10,8 +10,32 @@ abi Oracle {
     #[storage(write)]
     fn set_debug_timestamp(timestamp: u64);
 
+    #[storage(read, write)]
+    fn initialize(owner: Identity);
+
+    #[storage(read, write)]
+    fn transfer_owner(new_owner: Identity);
+
     #[storage(read, write)]
     fn set_redstone_config(config: RedstoneConfig);
+
+    #[storage(read, write)]
+    fn propose_redstone_config(config: RedstoneConfig);
+
+    #[storage(read, write)]
+    fn propose_redstone_config_removal();
+
+    #[storage(read, write)]
+    fn execute_redstone_config_update();
+
+    #[storage(read, write)]
+    fn cancel_redstone_config_update();
+
+    #[storage(read)]
+    fn get_redstone_config() -> Option<RedstoneConfig>;
+
+    #[storage(read)]
+    fn get_pending_redstone_config() -> Option<PendingRedstoneConfig>;
 }
 
 pub struct Price {
@@ -28,6 +52,13 @@ pub struct RedstoneConfig {
     pub precision: u32,
 }
 
+pub struct PendingRedstoneConfig {
+    /// Config to apply, None removes the Redstone fallback
+    pub config: Option<RedstoneConfig>,
+    /// Timestamp after which the change can be executed
+    pub effective_time: u64,
+}
+
 impl Price {
     pub fn new(price: u64, time: u64) -> Self {
         Self {
@@ -1,6 +1,11 @@
 library;
 
 use standards::src5::State;
+use ::trove_manager_interface::data_structures::{
+    AuctionConfig,
+    AutoDeleverageParams,
+    LiquidationParams,
+};
 abi ProtocolManager {
     #[storage(read, write)]
     fn initialize(
@@ -28,7 +33,39 @@ abi ProtocolManager {
         partial_redemption_hint: u64,
         upper_partial_hint: Identity,
         lower_partial_hint: Identity,
+        max_fee_percentage: u64,
+        min_collateral_out: Vec<(AssetId, u64)>,
     );
     #[storage(read, write)]
+    fn create_stability_sub_pool(assets: Vec<AssetId>);
+    #[storage(read, write)]
+    fn set_stability_pool_withdrawal_cooldown(delay: u64, window: u64);
+    #[storage(read, write)]
+    fn set_stability_pool_withdrawal_cooldown_exempt(depositor: Identity, exempt: bool);
+    #[storage(read, write)]
+    fn set_sorted_troves_max_size(size: u64);
+    #[storage(read, write)]
+    fn set_sorted_troves_asset_max_size(asset: AssetId, size: u64);
+    #[storage(read, write)]
+    fn set_liquidation_auction_config(asset: AssetId, config: AuctionConfig);
+    #[storage(read, write)]
+    fn set_liquidation_params(asset: AssetId, params: LiquidationParams);
+    #[storage(read, write)]
+    fn set_auto_deleverage_params(asset: AssetId, params: AutoDeleverageParams);
+    #[storage(read, write)]
+    fn set_reserve_fee_share(asset: AssetId, share: u64);
+    #[storage(read, write)]
+    fn cover_bad_debt(asset: AssetId, borrowers: Vec<Identity>);
+    #[storage(read, write)]
     fn transfer_owner(new_owner: Identity);
+    #[storage(read)]
+    fn get_registered_assets() -> Vec<RegisteredAsset>;
+    #[storage(read)]
+    fn get_total_bad_debt() -> u64;
+}
+
+pub struct RegisteredAsset {
+    pub asset: AssetId,
+    pub trove_manager: ContractId,
+    pub oracle: ContractId,
 }
@@ -47,6 +47,18 @@ abi SortedTroves {
     #[storage(read)]
     fn get_max_size() -> u64;
 
+    #[storage(read, write)]
+    fn set_max_size(size: u64);
+
+    #[storage(read, write)]
+    fn set_asset_max_size(asset: AssetId, size: u64);
+
+    #[storage(read)]
+    fn get_asset_max_size(asset: AssetId) -> u64;
+
+    #[storage(read)]
+    fn get_remaining_capacity(asset: AssetId) -> u64;
+
     #[storage(read)]
     fn get_first(asset: AssetId) -> Identity;
 
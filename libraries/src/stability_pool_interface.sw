@@ -1,5 +1,18 @@
 library;
 
+pub struct FrontEnd {
+    // Share of the FPT gains passed on to the tagged depositors, in DECIMAL_PRECISION
+    pub kickback_rate: u64,
+    pub is_registered: bool,
+}
+
+pub struct WithdrawalRequest {
+    // Maximum amount of USDF the request allows to withdraw
+    pub amount: u64,
+    // Time after which the withdrawal can be executed
+    pub unlock_time: u64,
+}
+
 abi StabilityPool {
     #[storage(read, write)]
     fn initialize(
@@ -8,6 +21,7 @@ abi StabilityPool {
         protocol_manager: ContractId,
         active_pool: ContractId,
         sorted_troves: ContractId,
+        borrow_operations: ContractId,
     );
 
     #[storage(read, write)]
@@ -18,11 +32,41 @@ abi StabilityPool {
     );
 
     #[storage(read, write), payable]
-    fn provide_to_stability_pool();
+    fn provide_to_stability_pool(front_end_tag: Option<Identity>);
+
+    #[storage(read, write), payable]
+    fn provide_to_sub_pool(pool_id: u64, front_end_tag: Option<Identity>);
 
     #[storage(read, write)]
     fn withdraw_from_stability_pool(amount: u64);
 
+    #[storage(read, write)]
+    fn request_withdrawal(amount: u64);
+
+    #[storage(read, write)]
+    fn cancel_withdrawal_request();
+
+    #[storage(read, write)]
+    fn set_withdrawal_cooldown(delay: u64, window: u64);
+
+    #[storage(read)]
+    fn get_withdrawal_cooldown() -> (u64, u64);
+
+    #[storage(read)]
+    fn get_withdrawal_request(depositor: Identity) -> Option<WithdrawalRequest>;
+
+    #[storage(read, write)]
+    fn set_withdrawal_cooldown_exempt(depositor: Identity, exempt: bool);
+
+    #[storage(read)]
+    fn is_withdrawal_cooldown_exempt(depositor: Identity) -> bool;
+
+    #[storage(read, write)]
+    fn claim_gains();
+
+    #[storage(read, write)]
+    fn claim_gains_to_trove(asset_address: AssetId, upper_hint: Identity, lower_hint: Identity);
+
     #[storage(read, write)]
     fn offset(
         debt_to_offset: u64,
@@ -30,12 +74,24 @@ abi StabilityPool {
         asset_address: AssetId,
     );
 
+    #[storage(read, write), payable]
+    fn restore_deposits(asset_address: AssetId);
+
     #[storage(read)]
     fn get_asset(asset_address: AssetId) -> u64;
 
+    #[storage(read)]
+    fn get_assets() -> Vec<AssetId>;
+
+    #[storage(read)]
+    fn get_asset_oracle(asset_address: AssetId) -> ContractId;
+
     #[storage(read)]
     fn get_total_usdf_deposits() -> u64;
 
+    #[storage(read)]
+    fn get_total_usdf_deposits_for_asset(asset_address: AssetId) -> u64;
+
     #[storage(read)]
     fn get_depositor_asset_gain(depositor: Identity, asset_address: AssetId) -> u64;
 
@@ -44,4 +100,43 @@ abi StabilityPool {
 
     #[storage(read)]
     fn get_depositor_fpt_gain(depositor: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn register_front_end(kickback_rate: u64);
+
+    #[storage(read)]
+    fn get_front_end(front_end: Identity) -> Option<FrontEnd>;
+
+    #[storage(read)]
+    fn get_depositor_front_end(depositor: Identity) -> Option<Identity>;
+
+    #[storage(read)]
+    fn get_compounded_front_end_stake(front_end: Identity) -> u64;
+
+    #[storage(read)]
+    fn get_front_end_fpt_gain(front_end: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn create_sub_pool(assets: Vec<AssetId>);
+
+    #[storage(read)]
+    fn get_sub_pool_count() -> u64;
+
+    #[storage(read)]
+    fn get_sub_pool_id(assets: Vec<AssetId>) -> Option<u64>;
+
+    #[storage(read)]
+    fn get_sub_pool_assets(pool_id: u64) -> Vec<AssetId>;
+
+    #[storage(read)]
+    fn get_sub_pools_for_asset(asset_address: AssetId) -> Vec<u64>;
+
+    #[storage(read)]
+    fn get_depositor_pool(depositor: Identity) -> u64;
+
+    #[storage(read)]
+    fn get_pool_total_usdf_deposits(pool_id: u64) -> u64;
+
+    #[storage(read)]
+    fn get_pool_asset_gain(pool_id: u64, asset_address: AssetId) -> u64;
 }
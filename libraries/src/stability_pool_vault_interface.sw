@@ -0,0 +1,42 @@
+library;
+
+abi StabilityPoolVault {
+    #[storage(read, write)]
+    fn initialize(owner: Identity, swap_adapter: ContractId);
+
+    #[storage(read, write), payable]
+    fn deposit(receiver: Identity) -> u64;
+
+    #[storage(read, write), payable]
+    fn withdraw(receiver: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn harvest(assets: Vec<AssetId>, min_amounts_out: Vec<u64>);
+
+    #[storage(read, write)]
+    fn set_swap_adapter(swap_adapter: ContractId);
+
+    #[storage(read, write)]
+    fn set_harvester(harvester: Identity);
+
+    #[storage(read, write)]
+    fn transfer_owner(new_owner: Identity);
+
+    #[storage(read)]
+    fn get_total_usdf() -> u64;
+
+    #[storage(read)]
+    fn get_share_asset_id() -> AssetId;
+
+    #[storage(read)]
+    fn get_swap_adapter() -> ContractId;
+
+    #[storage(read)]
+    fn get_harvester() -> Identity;
+
+    #[storage(read)]
+    fn preview_deposit(usdf_amount: u64) -> u64;
+
+    #[storage(read)]
+    fn preview_withdraw(shares: u64) -> u64;
+}
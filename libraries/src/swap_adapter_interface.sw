@@ -0,0 +1,11 @@
+library;
+
+// Pluggable swap venue used by the Stability Pool vault to sell harvested gains for USDF.
+// Implementations swap the forwarded coins for `asset_out` and send the result to `recipient`.
+abi SwapAdapter {
+    #[storage(read), payable]
+    fn swap(asset_out: AssetId, min_amount_out: u64, recipient: Identity) -> u64;
+
+    #[storage(read)]
+    fn get_amount_out(asset_in: AssetId, asset_out: AssetId, amount_in: u64) -> u64;
+}
@@ -1,7 +1,17 @@
 library;
 
 pub mod data_structures;
-use data_structures::{RewardSnapshot, SingleRedemptionValues, Status};
+use data_structures::{
+    Auction,
+    AuctionConfig,
+    AutoDeleverageParams,
+    AutoDeleverageSettings,
+    LiquidationParams,
+    LiquidationSummary,
+    RewardSnapshot,
+    SingleRedemptionValues,
+    Status,
+};
 
 abi TroveManager {
     #[storage(read, write)]
@@ -60,6 +70,86 @@ abi TroveManager {
         lower_partial_hint: Identity,
     );
 
+    #[storage(read, write)]
+    fn liquidate_troves(n: u64) -> LiquidationSummary;
+
+    #[storage(read, write)]
+    fn batch_liquidate_troves_with_hints(
+        ids: Vec<Identity>,
+        partial_hints: Vec<(Identity, Identity)>,
+        skip_failures: bool,
+    ) -> LiquidationSummary;
+
+    #[storage(read, write)]
+    fn set_liquidation_params(params: LiquidationParams);
+
+    #[storage(read)]
+    fn get_liquidation_params() -> LiquidationParams;
+
+    #[storage(read, write)]
+    fn set_auto_deleverage(id: Identity, settings: AutoDeleverageSettings);
+
+    #[storage(read)]
+    fn get_auto_deleverage(id: Identity) -> AutoDeleverageSettings;
+
+    #[storage(read, write)]
+    fn set_auto_deleverage_params(params: AutoDeleverageParams);
+
+    #[storage(read)]
+    fn get_auto_deleverage_params() -> AutoDeleverageParams;
+
+    #[storage(read, write)]
+    fn auto_deleverage(id: Identity, upper_hint: Identity, lower_hint: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn set_auction_config(config: AuctionConfig);
+
+    #[storage(read)]
+    fn get_auction_config() -> AuctionConfig;
+
+    #[storage(read)]
+    fn get_auction_count() -> u64;
+
+    #[storage(read)]
+    fn get_auction(auction_id: u64) -> Auction;
+
+    #[storage(read)]
+    fn get_auction_price(auction_id: u64) -> u64;
+
+    #[storage(read, write), payable]
+    fn bid_on_auction(auction_id: u64);
+
+    #[storage(read, write)]
+    fn settle_auction(auction_id: u64);
+
+    #[storage(read, write), payable]
+    fn fund_reserve();
+
+    #[storage(read, write)]
+    fn cover_bad_debt(borrowers: Vec<Identity>);
+
+    #[storage(read)]
+    fn get_reserve_balance() -> u64;
+
+    #[storage(read)]
+    fn get_bad_debt() -> u64;
+
+    #[storage(read)]
+    fn get_stability_pool_bad_debt() -> u64;
+
+    #[storage(read)]
+    fn get_trove_bad_debt(id: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn set_reserve_fee_share(share: u64);
+
+    #[storage(read)]
+    fn get_reserve_fee_share() -> u64;
+
+    // Testing workaround
+    #[storage(write)]
+    fn set_debug_timestamp(timestamp: u64);
+
     #[storage(read, write)]
     fn update_stake_and_total_stakes(id: Identity) -> u64;
 
@@ -16,6 +16,93 @@ impl SingleRedemptionValues {
     }
 }
 
+pub struct LiquidationSummary {
+    pub liquidated: Vec<Identity>,
+    pub partially_liquidated: Vec<Identity>,
+    pub skipped: Vec<Identity>,
+}
+
+impl LiquidationSummary {
+    pub fn default() -> Self {
+        LiquidationSummary {
+            liquidated: Vec::new(),
+            partially_liquidated: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+// Per-asset liquidation parameters, ratios use 1_000_000_000 = 100%
+pub struct LiquidationParams {
+    // Collateral ratio partially liquidated troves are restored to
+    pub post_collateral_ratio: u64,
+    // Liquidation penalty on top of the repaid debt, taken from the trove collateral
+    pub stability_pool_fee: u64,
+    // Share of the liquidated collateral paid to the liquidator
+    pub liquidator_fee: u64,
+    // Share of the liquidated collateral paid to the treasury
+    pub treasury_fee: u64,
+    pub treasury: Identity,
+}
+
+// Opt-in per-trove auto-deleverage settings, ratios use 1_000_000_000 = 100%
+pub struct AutoDeleverageSettings {
+    // ICR below which anyone can deleverage the trove, zero when disabled
+    pub trigger_icr: u64,
+    // ICR the trove is restored to
+    pub target_icr: u64,
+}
+
+impl AutoDeleverageSettings {
+    pub fn default() -> Self {
+        AutoDeleverageSettings {
+            trigger_icr: 0,
+            target_icr: 0,
+        }
+    }
+}
+
+// Per-asset auto-deleverage parameters
+pub struct AutoDeleverageParams {
+    // Share of the sold collateral paid to the keeper
+    pub keeper_fee: u64,
+    // Swap venue used when the stability pool can't absorb the repaid debt, zero when unset
+    pub swap_adapter: ContractId,
+    // Maximum discount to the oracle price accepted from the swap adapter
+    pub max_slippage: u64,
+}
+
+// Dutch auction backstop for the collateral the stability pool can't absorb
+pub struct AuctionConfig {
+    pub enabled: bool,
+    // Duration of the auction in seconds
+    pub duration: u64,
+    // Start and floor prices as a ratio of the oracle price, 1_000_000_000 = 100%
+    pub start_price_ratio: u64,
+    pub floor_price_ratio: u64,
+}
+
+impl AuctionConfig {
+    pub fn default() -> Self {
+        AuctionConfig {
+            enabled: false,
+            duration: 0,
+            start_price_ratio: 0,
+            floor_price_ratio: 0,
+        }
+    }
+}
+
+pub struct Auction {
+    pub collateral: u64,
+    pub debt: u64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub start_time: u64,
+    pub duration: u64,
+    pub is_settled: bool,
+}
+
 pub enum Status {
     NonExistent: (),
     Active: (),
@@ -12,6 +12,9 @@ use fuels::{
 };
 pub const PRECISION: u64 = 1_000_000_000;
 pub const POST_LIQUIDATION_COLLATERAL_RATIO: u64 = 1_500_000_000;
+pub const MIN_NET_DEBT: u64 = 500 * PRECISION;
+pub const STABILITY_POOL_FEE: u64 = 100_000_000;
+pub const LIQUIDATOR_EXECUTION_GAS_FEE: u64 = 5_000_000;
 
 pub struct ContractInstance<C> {
     pub contract: C,
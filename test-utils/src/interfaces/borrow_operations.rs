@@ -33,6 +33,7 @@ pub mod borrow_operations_abi {
         coll_surplus_pool_contract: ContractId,
         active_pool_contract: ContractId,
         sorted_troves_contract: ContractId,
+        stability_pool_contract: ContractId,
     ) -> CallResponse<()> {
         let tx_params = TxPolicies::default()
             .with_tip(1)
@@ -48,6 +49,7 @@ pub mod borrow_operations_abi {
                 coll_surplus_pool_contract,
                 active_pool_contract,
                 sorted_troves_contract,
+                stability_pool_contract,
             )
             .with_tx_policies(tx_params)
             .with_contract_ids(&[borrow_operations.implementation_id.into()])
@@ -458,6 +460,31 @@ pub mod borrow_operations_abi {
             .await
     }
 
+    pub async fn set_auto_deleverage<T: Account>(
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        asset: AssetId,
+        trigger_icr: u64,
+        target_icr: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        borrow_operations
+            .contract
+            .methods()
+            .set_auto_deleverage(trigger_icr, target_icr, asset.into())
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                borrow_operations.contract.contract_id().into(),
+                borrow_operations.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
     pub async fn add_asset<T: Account>(
         borrow_operations: &ContractInstance<BorrowOperations<T>>,
         oracle: ContractId,
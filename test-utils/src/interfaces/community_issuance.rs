@@ -104,4 +104,94 @@ pub mod community_issuance_abi {
 
         return res;
     }
+
+    pub async fn set_emission_schedule<T: Account>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+        phases: Vec<EmissionPhase>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        let res = instance
+            .contract
+            .methods()
+            .set_emission_schedule(phases)
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await;
+
+        return res;
+    }
+
+    pub async fn get_emission_schedule<T: Account>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+    ) -> CallResponse<Vec<EmissionPhase>> {
+        let res = instance
+            .contract
+            .methods()
+            .get_emission_schedule()
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .call()
+            .await;
+
+        return res.unwrap();
+    }
+
+    pub async fn get_supply_cap<T: Account>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+    ) -> CallResponse<u64> {
+        let res = instance
+            .contract
+            .methods()
+            .get_supply_cap()
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .call()
+            .await;
+
+        return res.unwrap();
+    }
+
+    pub async fn get_total_fpt_issued<T: Account>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+    ) -> CallResponse<u64> {
+        let res = instance
+            .contract
+            .methods()
+            .get_total_fpt_issued()
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .call()
+            .await;
+
+        return res.unwrap();
+    }
+
+    pub async fn get_projected_issuance<T: Account>(
+        instance: &ContractInstance<CommunityIssuance<T>>,
+        time: u64,
+    ) -> CallResponse<u64> {
+        let res = instance
+            .contract
+            .methods()
+            .get_projected_issuance(time)
+            .with_contract_ids(&[
+                instance.contract.contract_id().into(),
+                instance.implementation_id.into(),
+            ])
+            .call()
+            .await;
+
+        return res.unwrap();
+    }
 }
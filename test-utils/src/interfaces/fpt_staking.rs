@@ -22,6 +22,7 @@ pub mod fpt_staking_abi {
         borrower_operations_address: ContractId,
         fpt_asset_id: AssetId,
         usdf_asset_id: AssetId,
+        debugging: bool,
     ) -> CallResponse<()> {
         let tx_params = TxPolicies::default().with_tip(1);
 
@@ -33,6 +34,7 @@ pub mod fpt_staking_abi {
                 borrower_operations_address,
                 fpt_asset_id.into(),
                 usdf_asset_id.into(),
+                debugging,
             )
             .with_contract_ids(&[
                 fpt_staking.contract.contract_id().into(),
@@ -122,6 +124,158 @@ pub mod fpt_staking_abi {
             .await
     }
 
+    pub async fn claim<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        assets: Vec<AssetId>,
+        include_usdf: bool,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+        let outputs = assets.len() + 1;
+
+        fpt_staking
+            .contract
+            .methods()
+            .claim(assets, include_usdf)
+            .with_tx_policies(tx_params)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(outputs))
+            .call()
+            .await
+    }
+
+    pub async fn claim_to<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        recipient: Identity,
+        assets: Vec<AssetId>,
+        include_usdf: bool,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+        let outputs = assets.len() + 1;
+
+        fpt_staking
+            .contract
+            .methods()
+            .claim_to(recipient, assets, include_usdf)
+            .with_tx_policies(tx_params)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(outputs))
+            .call()
+            .await
+    }
+
+    pub async fn stake_locked<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        fpt_asset_id: AssetId,
+        fpt_deposit_amount: u64,
+        lock_weeks: u64,
+    ) -> Result<CallResponse<u64>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(fpt_deposit_amount)
+            .with_asset_id(fpt_asset_id);
+
+        fpt_staking
+            .contract
+            .methods()
+            .stake_locked(lock_weeks)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call_params(call_params)
+            .unwrap()
+            .call()
+            .await
+    }
+
+    pub async fn unstake_locked<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        mock_token: &Token<T>,
+        fpt_token: &Token<T>,
+        position_id: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_witness_limit(2000000)
+            .with_script_gas_limit(2000000);
+
+        fpt_staking
+            .contract
+            .methods()
+            .unstake_locked(position_id)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[&usdf_token.contract, mock_token, fpt_token])
+            .with_contract_ids(&[
+                usdf_token.contract.contract_id().into(),
+                usdf_token.implementation_id.into(),
+                mock_token.contract_id().into(),
+                fpt_token.contract_id().into(),
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(10))
+            .call()
+            .await
+    }
+
+    pub async fn poke_locked_position<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+        position_id: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        fpt_staking
+            .contract
+            .methods()
+            .poke_locked_position(id, position_id)
+            .with_tx_policies(tx_params)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(10))
+            .call()
+            .await
+    }
+
+    pub async fn set_current_time<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        time: u64,
+    ) -> CallResponse<()> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        fpt_staking
+            .contract
+            .methods()
+            .set_current_time(time)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn add_asset<T: Account>(
         fpt_staking: &ContractInstance<FPTStaking<T>>,
         asset_address: AssetId,
@@ -218,4 +372,161 @@ pub mod fpt_staking_abi {
             .await
             .unwrap()
     }
+
+    pub async fn get_pending_fpt_gain<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_pending_fpt_gain(id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_locked_positions<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+    ) -> CallResponse<Vec<LockedPosition>> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_locked_positions(id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_pending_locked_usdf_gain<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+        position_id: u64,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_pending_locked_usdf_gain(id, position_id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_pending_locked_asset_gain<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+        position_id: u64,
+        asset_address: AssetId,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_pending_locked_asset_gain(id, position_id, asset_address.into())
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_pending_locked_fpt_gain<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+        position_id: u64,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_pending_locked_fpt_gain(id, position_id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_effective_weight<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_effective_weight(id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_total_weight<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_total_weight()
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_lock_boost<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        lock_weeks: u64,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_lock_boost(lock_weeks)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_early_exit_penalty<T: Account>(
+        fpt_staking: &ContractInstance<FPTStaking<T>>,
+        id: Identity,
+        position_id: u64,
+    ) -> CallResponse<u64> {
+        fpt_staking
+            .contract
+            .methods()
+            .get_early_exit_penalty(id, position_id)
+            .with_contract_ids(&[
+                fpt_staking.contract.contract_id().into(),
+                fpt_staking.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
 }
@@ -63,4 +63,171 @@ pub mod hint_helper_abi {
             .await
             .unwrap()
     }
+
+    pub async fn get_exact_insert_position<T: Account>(
+        hint_helper: &HintHelper<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        nicr: u64,
+        approx_hint: Identity,
+        max_steps: u64,
+    ) -> CallResponse<(Identity, Identity)> {
+        hint_helper
+            .methods()
+            .get_exact_insert_position(
+                asset_id.clone(),
+                trove_manager.contract.contract_id(),
+                nicr,
+                approx_hint,
+                max_steps,
+            )
+            .with_contracts(&[&sorted_troves.contract, &trove_manager.contract])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_redemption_hints<T: Account>(
+        hint_helper: &HintHelper<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+    ) -> CallResponse<(Identity, u64, u64)> {
+        hint_helper
+            .methods()
+            .get_redemption_hints(
+                asset_id.clone(),
+                trove_manager.contract.contract_id(),
+                usdf_amount,
+                price,
+                max_iterations,
+            )
+            .with_contracts(&[&sorted_troves.contract, &trove_manager.contract])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+}
+
+pub mod hint_helper_utils {
+    use super::*;
+    use crate::data_structures::ContractInstance;
+    use crate::interfaces::sorted_troves::SortedTroves;
+    use crate::interfaces::trove_manager::TroveManagerContract;
+    use fuels::prelude::Account;
+    use fuels::types::{Address, AssetId, Identity};
+
+    pub struct RedemptionHints {
+        pub first_redemption_hint: Identity,
+        pub partial_redemption_hint_nicr: u64,
+        pub truncated_usdf_amount: u64,
+        pub upper_partial_hint: Identity,
+        pub lower_partial_hint: Identity,
+    }
+
+    /// Finds the exact (prev, next) pair for `nicr` by sampling an approximate hint
+    /// and walking the list from it.
+    pub async fn get_insert_hints<T: Account>(
+        hint_helper: &HintHelper<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        nicr: u64,
+        num_trials: u64,
+        random_seed: u64,
+        max_steps: u64,
+    ) -> (Identity, Identity) {
+        let approx_hint = hint_helper_abi::get_approx_hint(
+            hint_helper,
+            trove_manager,
+            sorted_troves,
+            asset_id,
+            nicr,
+            num_trials,
+            random_seed,
+        )
+        .await
+        .value
+        .0;
+
+        hint_helper_abi::get_exact_insert_position(
+            hint_helper,
+            trove_manager,
+            sorted_troves,
+            asset_id,
+            nicr,
+            approx_hint,
+            max_steps,
+        )
+        .await
+        .value
+    }
+
+    /// Computes everything `redeem_collateral` needs for one asset: the first trove to redeem from,
+    /// the partial redemption NICR with its insert position and the redeemable USDF amount.
+    pub async fn get_redemption_and_insert_hints<T: Account>(
+        hint_helper: &HintHelper<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+        num_trials: u64,
+        random_seed: u64,
+    ) -> RedemptionHints {
+        let (first_redemption_hint, partial_redemption_hint_nicr, truncated_usdf_amount) =
+            hint_helper_abi::get_redemption_hints(
+                hint_helper,
+                trove_manager,
+                sorted_troves,
+                asset_id,
+                usdf_amount,
+                price,
+                max_iterations,
+            )
+            .await
+            .value;
+
+        let null_hint = Identity::Address(Address::zeroed());
+        let (upper_partial_hint, lower_partial_hint) = if partial_redemption_hint_nicr == 0 {
+            (null_hint.clone(), null_hint)
+        } else {
+            get_insert_hints(
+                hint_helper,
+                trove_manager,
+                sorted_troves,
+                asset_id,
+                partial_redemption_hint_nicr,
+                num_trials,
+                random_seed,
+                0,
+            )
+            .await
+        };
+
+        RedemptionHints {
+            first_redemption_hint,
+            partial_redemption_hint_nicr,
+            truncated_usdf_amount,
+            upper_partial_hint,
+            lower_partial_hint,
+        }
+    }
 }
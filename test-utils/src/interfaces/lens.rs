@@ -0,0 +1,98 @@
+use fuels::prelude::abigen;
+
+use fuels::programs::responses::CallResponse;
+abigen!(Contract(
+    name = "Lens",
+    abi = "contracts/lens-contract/out/debug/lens-contract-abi.json"
+));
+
+pub mod lens_abi {
+    use super::*;
+    use crate::data_structures::ProtocolContracts;
+    use fuels::prelude::{Account, ContractDependency};
+    use fuels::types::bech32::Bech32ContractId;
+    use fuels::types::Identity;
+
+    // Every contract the lens reads from, including the oracles of each registered asset
+    fn lens_dependencies<T: Account>(
+        contracts: &ProtocolContracts<T>,
+    ) -> (Vec<&dyn ContractDependency>, Vec<Bech32ContractId>) {
+        let mut with_contracts: Vec<&dyn ContractDependency> = Vec::new();
+        with_contracts.push(&contracts.protocol_manager.contract);
+        with_contracts.push(&contracts.borrow_operations.contract);
+        with_contracts.push(&contracts.stability_pool.contract);
+        with_contracts.push(&contracts.fpt_staking.contract);
+        with_contracts.push(&contracts.usdf.contract);
+        with_contracts.push(&contracts.active_pool.contract);
+        with_contracts.push(&contracts.default_pool.contract);
+        with_contracts.push(&contracts.coll_surplus_pool.contract);
+        with_contracts.push(&contracts.vesting_contract.contract);
+
+        let mut with_contract_ids: Vec<Bech32ContractId> = Vec::new();
+        with_contract_ids.push(contracts.protocol_manager.contract.contract_id().into());
+        with_contract_ids.push(contracts.protocol_manager.implementation_id.into());
+        with_contract_ids.push(contracts.borrow_operations.contract.contract_id().into());
+        with_contract_ids.push(contracts.borrow_operations.implementation_id.into());
+        with_contract_ids.push(contracts.stability_pool.contract.contract_id().into());
+        with_contract_ids.push(contracts.stability_pool.implementation_id.into());
+        with_contract_ids.push(contracts.fpt_staking.contract.contract_id().into());
+        with_contract_ids.push(contracts.fpt_staking.implementation_id.into());
+        with_contract_ids.push(contracts.usdf.contract.contract_id().into());
+        with_contract_ids.push(contracts.usdf.implementation_id.into());
+        with_contract_ids.push(contracts.active_pool.contract.contract_id().into());
+        with_contract_ids.push(contracts.active_pool.implementation_id.into());
+        with_contract_ids.push(contracts.default_pool.contract.contract_id().into());
+        with_contract_ids.push(contracts.default_pool.implementation_id.into());
+        with_contract_ids.push(contracts.coll_surplus_pool.contract.contract_id().into());
+        with_contract_ids.push(contracts.coll_surplus_pool.implementation_id.into());
+        with_contract_ids.push(contracts.vesting_contract.contract.contract_id().into());
+        with_contract_ids.push(contracts.vesting_contract.implementation_id.into());
+
+        for asset_contracts in contracts.asset_contracts.iter() {
+            with_contracts.push(&asset_contracts.trove_manager.contract);
+            with_contracts.push(&asset_contracts.oracle.contract);
+            with_contracts.push(&asset_contracts.mock_pyth_oracle);
+            with_contracts.push(&asset_contracts.mock_redstone_oracle);
+
+            with_contract_ids.push(asset_contracts.trove_manager.contract.contract_id().into());
+            with_contract_ids.push(asset_contracts.trove_manager.implementation_id.into());
+            with_contract_ids.push(asset_contracts.oracle.contract.contract_id().into());
+            with_contract_ids.push(asset_contracts.oracle.implementation_id.into());
+            with_contract_ids.push(asset_contracts.mock_pyth_oracle.contract_id().into());
+            with_contract_ids.push(asset_contracts.mock_redstone_oracle.contract_id().into());
+        }
+
+        (with_contracts, with_contract_ids)
+    }
+
+    pub async fn get_system_overview<T: Account>(
+        lens: &Lens<T>,
+        contracts: &ProtocolContracts<T>,
+    ) -> CallResponse<SystemOverview> {
+        let (with_contracts, with_contract_ids) = lens_dependencies(contracts);
+
+        lens.methods()
+            .get_system_overview()
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_user_overview<T: Account>(
+        lens: &Lens<T>,
+        contracts: &ProtocolContracts<T>,
+        user: Identity,
+    ) -> CallResponse<UserOverview> {
+        let (with_contracts, with_contract_ids) = lens_dependencies(contracts);
+
+        lens.methods()
+            .get_user_overview(user)
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+            .unwrap()
+    }
+}
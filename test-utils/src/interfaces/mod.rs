@@ -6,6 +6,7 @@ pub mod default_pool;
 pub mod fpt_staking;
 pub mod fpt_token;
 pub mod hint_helper;
+pub mod lens;
 pub mod multi_trove_getter;
 pub mod oracle;
 pub mod protocol_manager;
@@ -14,6 +15,8 @@ pub mod pyth_oracle;
 pub mod redstone_oracle;
 pub mod sorted_troves;
 pub mod stability_pool;
+pub mod stability_pool_vault;
+pub mod swap_adapter;
 pub mod token;
 pub mod trove_manager;
 pub mod usdf_token;
@@ -8,10 +8,12 @@ abigen!(Contract(
 
 pub mod multi_trove_getter_abi {
     use super::*;
-    use crate::data_structures::ContractInstance;
+    use crate::data_structures::{AssetContracts, ContractInstance};
+    use crate::interfaces::protocol_manager::ProtocolManager;
     use crate::interfaces::{sorted_troves::SortedTroves, trove_manager::TroveManagerContract};
-    use fuels::prelude::Account;
-    use fuels::types::AssetId;
+    use fuels::prelude::{Account, ContractDependency};
+    use fuels::types::bech32::Bech32ContractId;
+    use fuels::types::{AssetId, Identity};
 
     pub async fn get_multiple_sorted_troves<T: Account>(
         multi_trove_getter: &MultiTroveGetter<T>,
@@ -40,6 +42,111 @@ pub mod multi_trove_getter_abi {
             .await
             .unwrap()
     }
+
+    pub async fn get_sorted_troves_after<T: Account>(
+        multi_trove_getter: &MultiTroveGetter<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+    ) -> CallResponse<(Vec<TroveData>, Identity)> {
+        multi_trove_getter
+            .methods()
+            .get_sorted_troves_after(
+                trove_manager.contract.contract_id(),
+                asset_id.clone(),
+                cursor,
+                ascending,
+                count,
+                price,
+            )
+            .with_contracts(&[&sorted_troves.contract, &trove_manager.contract])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_sorted_troves_in_icr_range<T: Account>(
+        multi_trove_getter: &MultiTroveGetter<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+        cursor: Identity,
+        ascending: bool,
+        count: u8,
+        price: u64,
+        min_icr: u64,
+        max_icr: u64,
+    ) -> CallResponse<(Vec<TroveData>, Identity)> {
+        multi_trove_getter
+            .methods()
+            .get_sorted_troves_in_icr_range(
+                trove_manager.contract.contract_id(),
+                asset_id.clone(),
+                cursor,
+                ascending,
+                count,
+                price,
+                min_icr,
+                max_icr,
+            )
+            .with_contracts(&[&sorted_troves.contract, &trove_manager.contract])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_user_troves<T: Account>(
+        multi_trove_getter: &MultiTroveGetter<T>,
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset_contracts: &Vec<AssetContracts<T>>,
+        user: Identity,
+    ) -> CallResponse<Vec<TroveData>> {
+        let mut with_contracts: Vec<&dyn ContractDependency> = Vec::new();
+        with_contracts.push(&protocol_manager.contract);
+
+        let mut with_contract_ids: Vec<Bech32ContractId> = Vec::new();
+        with_contract_ids.push(protocol_manager.contract.contract_id().into());
+        with_contract_ids.push(protocol_manager.implementation_id.into());
+
+        for contracts in asset_contracts.iter() {
+            with_contracts.push(&contracts.trove_manager.contract);
+            with_contracts.push(&contracts.oracle.contract);
+            with_contracts.push(&contracts.mock_pyth_oracle);
+            with_contracts.push(&contracts.mock_redstone_oracle);
+
+            with_contract_ids.push(contracts.trove_manager.contract.contract_id().into());
+            with_contract_ids.push(contracts.trove_manager.implementation_id.into());
+            with_contract_ids.push(contracts.oracle.contract.contract_id().into());
+            with_contract_ids.push(contracts.oracle.implementation_id.into());
+            with_contract_ids.push(contracts.mock_pyth_oracle.contract_id().into());
+            with_contract_ids.push(contracts.mock_redstone_oracle.contract_id().into());
+        }
+
+        multi_trove_getter
+            .methods()
+            .get_user_troves(protocol_manager.contract.contract_id(), user)
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+            .unwrap()
+    }
 }
 
 pub mod multi_trove_getter_utils {
@@ -9,6 +9,8 @@ abigen!(Contract(
 
 // 10 minutes
 pub const ORACLE_TIMEOUT: u64 = 600;
+// 2 days
+pub const REDSTONE_CONFIG_TIMELOCK: u64 = 172_800;
 
 pub mod oracle_abi {
 
@@ -18,7 +20,7 @@ pub mod oracle_abi {
     use fuels::{
         prelude::{Account, TxPolicies},
         programs::calls::ContractDependency,
-        types::{bech32::Bech32ContractId, errors::Error},
+        types::{bech32::Bech32ContractId, errors::Error, Identity},
     };
 
     pub async fn get_price<T: Account>(
@@ -94,4 +96,170 @@ pub mod oracle_abi {
             .call()
             .await
     }
+
+    pub async fn initialize<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+        owner: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .initialize(owner)
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn transfer_owner<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+        new_owner: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .transfer_owner(new_owner)
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn owner<T: Account>(oracle: &ContractInstance<Oracle<T>>) -> CallResponse<State> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .owner()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn propose_redstone_config<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+        config: RedstoneConfig,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .propose_redstone_config(config)
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn propose_redstone_config_removal<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .propose_redstone_config_removal()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn execute_redstone_config_update<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .execute_redstone_config_update()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn cancel_redstone_config_update<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .cancel_redstone_config_update()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_redstone_config<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+    ) -> CallResponse<Option<RedstoneConfig>> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .get_redstone_config()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_pending_redstone_config<T: Account>(
+        oracle: &ContractInstance<Oracle<T>>,
+    ) -> CallResponse<Option<PendingRedstoneConfig>> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        oracle
+            .contract
+            .methods()
+            .get_pending_redstone_config()
+            .with_contract_ids(&[
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
 }
@@ -12,10 +12,12 @@ pub mod protocol_manager_abi {
     use crate::interfaces::active_pool::ActivePool;
     use crate::interfaces::borrow_operations::BorrowOperations;
     use crate::interfaces::coll_surplus_pool::CollSurplusPool;
+    use crate::interfaces::community_issuance::CommunityIssuance;
     use crate::interfaces::default_pool::DefaultPool;
     use crate::interfaces::fpt_staking::FPTStaking;
     use crate::interfaces::sorted_troves::SortedTroves;
     use crate::interfaces::stability_pool::StabilityPool;
+    use crate::interfaces::trove_manager::TroveManagerContract;
     use crate::interfaces::usdf_token::USDFToken;
     use data_structures::AssetContracts;
     use fuels::prelude::{Account, CallParameters, ContractDependency};
@@ -129,6 +131,8 @@ pub mod protocol_manager_abi {
         partial_redemption_hint: u64,
         upper_partial_hint: Option<Identity>,
         lower_partial_hint: Option<Identity>,
+        max_fee_percentage: u64,
+        min_collateral_out: Vec<(AssetId, u64)>,
         usdf: &ContractInstance<USDFToken<T>>,
         fpt_staking: &ContractInstance<FPTStaking<T>>,
         coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
@@ -136,7 +140,7 @@ pub mod protocol_manager_abi {
         active_pool: &ContractInstance<ActivePool<T>>,
         sorted_troves: &ContractInstance<SortedTroves<T>>,
         aswith_contracts: &Vec<AssetContracts<T>>,
-    ) -> CallResponse<()> {
+    ) -> Result<CallResponse<()>, Error> {
         let tx_params = TxPolicies::default()
             .with_tip(1)
             .with_witness_limit(2000000)
@@ -202,6 +206,8 @@ pub mod protocol_manager_abi {
                 partial_redemption_hint,
                 upper_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
                 lower_partial_hint.unwrap_or(Identity::Address(Address::zeroed())),
+                max_fee_percentage,
+                min_collateral_out,
             )
             .with_tx_policies(tx_params)
             .call_params(call_params)
@@ -211,7 +217,6 @@ pub mod protocol_manager_abi {
             .with_variable_output_policy(VariableOutputPolicy::Exactly(10))
             .call()
             .await
-            .unwrap()
     }
 
     pub async fn owner<T: Account>(
@@ -257,6 +262,311 @@ pub mod protocol_manager_abi {
             .await
     }
 
+    pub async fn create_stability_sub_pool<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        assets: Vec<AssetId>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .create_stability_sub_pool(assets)
+            .with_contracts(&[&stability_pool.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_stability_pool_withdrawal_cooldown<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        delay: u64,
+        window: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_stability_pool_withdrawal_cooldown(delay, window)
+            .with_contracts(&[&stability_pool.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_stability_pool_withdrawal_cooldown_exempt<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        depositor: Identity,
+        exempt: bool,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_stability_pool_withdrawal_cooldown_exempt(depositor, exempt)
+            .with_contracts(&[&stability_pool.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_sorted_troves_max_size<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        size: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_sorted_troves_max_size(size)
+            .with_contracts(&[&sorted_troves.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_sorted_troves_asset_max_size<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset: AssetId,
+        size: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_sorted_troves_asset_max_size(asset.into(), size)
+            .with_contracts(&[&sorted_troves.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_liquidation_auction_config<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        asset: AssetId,
+        config: AuctionConfig,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_liquidation_auction_config(asset.into(), config)
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_liquidation_params<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        asset: AssetId,
+        params: LiquidationParams,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_liquidation_params(asset.into(), params)
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_auto_deleverage_params<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        asset: AssetId,
+        params: AutoDeleverageParams,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_auto_deleverage_params(asset.into(), params)
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_reserve_fee_share<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        asset: AssetId,
+        share: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .set_reserve_fee_share(asset.into(), share)
+            .with_contracts(&[&trove_manager.contract])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn cover_bad_debt<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        asset_contracts: &AssetContracts<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        borrowers: Vec<Identity>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        protocol_manager
+            .contract
+            .methods()
+            .cover_bad_debt(asset_contracts.asset_id.into(), borrowers)
+            .with_contracts(&[
+                &asset_contracts.trove_manager.contract,
+                &stability_pool.contract,
+                &community_issuance.contract,
+                &sorted_troves.contract,
+                &active_pool.contract,
+                &default_pool.contract,
+                &usdf.contract,
+            ])
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+                asset_contracts.trove_manager.contract.contract_id().into(),
+                asset_contracts.trove_manager.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+                default_pool.contract.contract_id().into(),
+                default_pool.implementation_id.into(),
+                usdf.contract.contract_id().into(),
+                usdf.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_registered_assets<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+    ) -> CallResponse<Vec<RegisteredAsset>> {
+        protocol_manager
+            .contract
+            .methods()
+            .get_registered_assets()
+            .with_contract_ids(&[
+                protocol_manager.contract.contract_id().into(),
+                protocol_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_total_bad_debt<T: Account>(
+        protocol_manager: &ContractInstance<ProtocolManager<T>>,
+        aswith_contracts: &Vec<AssetContracts<T>>,
+    ) -> CallResponse<u64> {
+        let mut with_contracts: Vec<&dyn ContractDependency> = Vec::new();
+        let mut with_contract_ids: Vec<Bech32ContractId> = Vec::new();
+        with_contract_ids.push(protocol_manager.contract.contract_id().into());
+        with_contract_ids.push(protocol_manager.implementation_id.into());
+
+        for contracts in aswith_contracts.iter() {
+            with_contracts.push(&contracts.trove_manager.contract);
+            with_contract_ids.push(contracts.trove_manager.contract.contract_id().into());
+            with_contract_ids.push(contracts.trove_manager.implementation_id.into());
+        }
+
+        protocol_manager
+            .contract
+            .methods()
+            .get_total_bad_debt()
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn transfer_owner<T: Account>(
         protocol_manager: &ContractInstance<ProtocolManager<T>>,
         new_owner: Identity,
@@ -157,6 +157,48 @@ pub mod sorted_troves_abi {
             .unwrap()
     }
 
+    pub async fn get_asset_max_size<T: Account>(
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset: AssetId,
+    ) -> CallResponse<u64> {
+        sorted_troves
+            .contract
+            .methods()
+            .get_asset_max_size(asset.into())
+            .with_contract_ids(&[sorted_troves.implementation_id.into()])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_remaining_capacity<T: Account>(
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset: AssetId,
+    ) -> CallResponse<u64> {
+        sorted_troves
+            .contract
+            .methods()
+            .get_remaining_capacity(asset.into())
+            .with_contract_ids(&[sorted_troves.implementation_id.into()])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn is_full<T: Account>(
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset: AssetId,
+    ) -> CallResponse<bool> {
+        sorted_troves
+            .contract
+            .methods()
+            .is_full(asset.into())
+            .with_contract_ids(&[sorted_troves.implementation_id.into()])
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub async fn contains<T: Account>(
         sorted_troves: &ContractInstance<SortedTroves<T>>,
         id: Identity,
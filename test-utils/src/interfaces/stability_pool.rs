@@ -1,5 +1,7 @@
 use fuels::prelude::abigen;
 
+use crate::interfaces::active_pool::ActivePool;
+use crate::interfaces::borrow_operations::BorrowOperations;
 use crate::interfaces::community_issuance::CommunityIssuance;
 use crate::interfaces::oracle::Oracle;
 use crate::interfaces::pyth_oracle::PythCore;
@@ -32,6 +34,7 @@ pub mod stability_pool_abi {
         protocol_manager_contract: ContractId,
         active_pool: ContractId,
         sorted_troves: ContractId,
+        borrow_operations: ContractId,
     ) -> Result<CallResponse<()>, Error> {
         let tx_params = TxPolicies::default().with_tip(1);
 
@@ -44,6 +47,7 @@ pub mod stability_pool_abi {
                 protocol_manager_contract,
                 active_pool,
                 sorted_troves,
+                borrow_operations,
             )
             .with_contract_ids(&[
                 stability_pool.contract.contract_id().into(),
@@ -81,6 +85,71 @@ pub mod stability_pool_abi {
         usdf_token: &ContractInstance<USDFToken<T>>,
         mock_token: &Token<T>,
         amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        provide_to_stability_pool_with_front_end(
+            stability_pool,
+            community_issuance,
+            usdf_token,
+            mock_token,
+            amount,
+            None,
+        )
+        .await
+    }
+
+    pub async fn provide_to_stability_pool_with_front_end<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        mock_token: &Token<T>,
+        amount: u64,
+        front_end_tag: Option<Identity>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        let usdf_asset_id = usdf_token
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into());
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdf_asset_id);
+
+        stability_pool
+            .contract
+            .methods()
+            .provide_to_stability_pool(front_end_tag)
+            .with_tx_policies(tx_params)
+            .call_params(call_params)
+            .unwrap()
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .with_contracts(&[
+                &usdf_token.contract,
+                mock_token,
+                &community_issuance.contract,
+            ])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                usdf_token.contract.contract_id().into(),
+                usdf_token.implementation_id.into(),
+                mock_token.contract_id().into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn provide_to_sub_pool<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        mock_token: &Token<T>,
+        amount: u64,
+        pool_id: u64,
+        front_end_tag: Option<Identity>,
     ) -> Result<CallResponse<()>, Error> {
         let tx_params = TxPolicies::default().with_tip(1);
 
@@ -96,11 +165,11 @@ pub mod stability_pool_abi {
         stability_pool
             .contract
             .methods()
-            .provide_to_stability_pool()
+            .provide_to_sub_pool(pool_id, front_end_tag)
             .with_tx_policies(tx_params)
             .call_params(call_params)
             .unwrap()
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
             .with_contracts(&[
                 &usdf_token.contract,
                 mock_token,
@@ -119,6 +188,269 @@ pub mod stability_pool_abi {
             .await
     }
 
+    pub async fn claim_gains<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        mock_token: &Token<T>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        stability_pool
+            .contract
+            .methods()
+            .claim_gains()
+            .with_tx_policies(tx_params)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .with_contracts(&[mock_token, &community_issuance.contract])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                mock_token.contract_id().into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn claim_gains_to_trove<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        borrow_operations: &ContractInstance<BorrowOperations<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        pyth_oracle: &PythCore<T>,
+        redstone_oracle: &RedstoneCore<T>,
+        mock_token: &Token<T>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let mock_asset_id: AssetId = mock_token
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        stability_pool
+            .contract
+            .methods()
+            .claim_gains_to_trove(mock_asset_id, upper_hint, lower_hint)
+            .with_tx_policies(tx_params)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .with_contracts(&[
+                &community_issuance.contract,
+                &borrow_operations.contract,
+                &oracle.contract,
+                pyth_oracle,
+                redstone_oracle,
+                mock_token,
+                &usdf_token.contract,
+                &sorted_troves.contract,
+                &trove_manager.contract,
+                &active_pool.contract,
+            ])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+                borrow_operations.contract.contract_id().into(),
+                borrow_operations.implementation_id.into(),
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+                pyth_oracle.contract_id().into(),
+                redstone_oracle.contract_id().into(),
+                mock_token.contract_id().into(),
+                usdf_token.contract.contract_id().into(),
+                usdf_token.implementation_id.into(),
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn request_withdrawal<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        stability_pool
+            .contract
+            .methods()
+            .request_withdrawal(amount)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[&community_issuance.contract])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn cancel_withdrawal_request<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        stability_pool
+            .contract
+            .methods()
+            .cancel_withdrawal_request()
+            .with_tx_policies(tx_params)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_withdrawal_cooldown<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+    ) -> Result<CallResponse<(u64, u64)>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_withdrawal_cooldown()
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn is_withdrawal_cooldown_exempt<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        depositor: Identity,
+    ) -> Result<CallResponse<bool>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .is_withdrawal_cooldown_exempt(depositor)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_withdrawal_request<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        depositor: Identity,
+    ) -> Result<CallResponse<Option<WithdrawalRequest>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_withdrawal_request(depositor)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn register_front_end<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        kickback_rate: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        stability_pool
+            .contract
+            .methods()
+            .register_front_end(kickback_rate)
+            .with_tx_policies(tx_params)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_front_end<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        front_end: Identity,
+    ) -> Result<CallResponse<Option<FrontEnd>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_front_end(front_end)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_depositor_front_end<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        depositor: Identity,
+    ) -> Result<CallResponse<Option<Identity>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_depositor_front_end(depositor)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_compounded_front_end_stake<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        front_end: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_compounded_front_end_stake(front_end)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_front_end_fpt_gain<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        front_end: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_front_end_fpt_gain(front_end)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
     pub async fn get_asset<T: Account>(
         stability_pool: &ContractInstance<StabilityPool<T>>,
         asset_address: AssetId,
@@ -150,6 +482,134 @@ pub mod stability_pool_abi {
             .await
     }
 
+    pub async fn get_total_usdf_deposits_for_asset<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        asset_id: AssetId,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_total_usdf_deposits_for_asset(asset_id.into())
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_sub_pool_count<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_sub_pool_count()
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_sub_pool_id<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        assets: Vec<AssetId>,
+    ) -> Result<CallResponse<Option<u64>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_sub_pool_id(assets)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_sub_pool_assets<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        pool_id: u64,
+    ) -> Result<CallResponse<Vec<AssetId>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_sub_pool_assets(pool_id)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_sub_pools_for_asset<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        asset_id: AssetId,
+    ) -> Result<CallResponse<Vec<u64>>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_sub_pools_for_asset(asset_id.into())
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_depositor_pool<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        depositor: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_depositor_pool(depositor)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_pool_total_usdf_deposits<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        pool_id: u64,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_pool_total_usdf_deposits(pool_id)
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_pool_asset_gain<T: Account>(
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        pool_id: u64,
+        asset_id: AssetId,
+    ) -> Result<CallResponse<u64>, Error> {
+        stability_pool
+            .contract
+            .methods()
+            .get_pool_asset_gain(pool_id, asset_id.into())
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
     pub async fn get_depositor_asset_gain<T: Account>(
         stability_pool: &ContractInstance<StabilityPool<T>>,
         depositor: Identity,
@@ -220,7 +680,7 @@ pub mod stability_pool_abi {
             .methods()
             .withdraw_from_stability_pool(amount)
             .with_tx_policies(tx_params)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
             .with_contracts(&[
                 &usdf_token.contract,
                 mock_token,
@@ -0,0 +1,287 @@
+use fuels::prelude::abigen;
+use fuels::programs::responses::CallResponse;
+
+abigen!(Contract(
+    name = "StabilityPoolVault",
+    abi =
+        "contracts/stability-pool-vault-contract/out/debug/stability-pool-vault-contract-abi.json"
+));
+
+pub mod stability_pool_vault_abi {
+    use super::*;
+    use crate::data_structures::{AssetContracts, ContractInstance};
+    use crate::interfaces::community_issuance::CommunityIssuance;
+    use crate::interfaces::sorted_troves::SortedTroves;
+    use crate::interfaces::stability_pool::StabilityPool;
+    use crate::interfaces::swap_adapter::MockSwapAdapter;
+    use crate::interfaces::usdf_token::USDFToken;
+    use fuels::prelude::{Account, CallParameters, ContractDependency, Error, TxPolicies};
+    use fuels::types::bech32::Bech32ContractId;
+    use fuels::types::transaction_builders::VariableOutputPolicy;
+    use fuels::types::{AssetId, ContractId, Identity};
+
+    pub async fn initialize<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        owner: Identity,
+        swap_adapter: ContractId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        vault
+            .methods()
+            .initialize(owner, swap_adapter)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn deposit<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        asset_contracts: &Vec<AssetContracts<T>>,
+        amount: u64,
+        receiver: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let usdf_asset_id = usdf_token
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into());
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdf_asset_id);
+
+        let mut with_contracts: Vec<&dyn ContractDependency> = vec![
+            &stability_pool.contract,
+            &community_issuance.contract,
+            &usdf_token.contract,
+        ];
+        let mut with_contract_ids: Vec<Bech32ContractId> = vec![
+            stability_pool.contract.contract_id().into(),
+            stability_pool.implementation_id.into(),
+            community_issuance.contract.contract_id().into(),
+            community_issuance.implementation_id.into(),
+            usdf_token.contract.contract_id().into(),
+            usdf_token.implementation_id.into(),
+        ];
+        push_oracle_contracts(asset_contracts, &mut with_contracts, &mut with_contract_ids);
+
+        vault
+            .methods()
+            .deposit(receiver)
+            .with_tx_policies(tx_params)
+            .call_params(call_params)
+            .unwrap()
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3 + asset_contracts.len()))
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+    }
+
+    pub async fn withdraw<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_contracts: &Vec<AssetContracts<T>>,
+        shares: u64,
+        receiver: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let share_asset_id = vault.contract_id().asset_id(&AssetId::zeroed().into());
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(shares)
+            .with_asset_id(share_asset_id);
+
+        let mut with_contracts: Vec<&dyn ContractDependency> = vec![
+            &stability_pool.contract,
+            &community_issuance.contract,
+            &usdf_token.contract,
+            &sorted_troves.contract,
+        ];
+        let mut with_contract_ids: Vec<Bech32ContractId> = vec![
+            stability_pool.contract.contract_id().into(),
+            stability_pool.implementation_id.into(),
+            community_issuance.contract.contract_id().into(),
+            community_issuance.implementation_id.into(),
+            usdf_token.contract.contract_id().into(),
+            usdf_token.implementation_id.into(),
+            sorted_troves.contract.contract_id().into(),
+            sorted_troves.implementation_id.into(),
+        ];
+
+        for contracts in asset_contracts.iter() {
+            with_contracts.push(&contracts.trove_manager.contract);
+            with_contracts.push(&contracts.oracle.contract);
+            with_contracts.push(&contracts.mock_pyth_oracle);
+            with_contracts.push(&contracts.mock_redstone_oracle);
+            with_contract_ids.push(contracts.trove_manager.contract.contract_id().into());
+            with_contract_ids.push(contracts.trove_manager.implementation_id.into());
+            with_contract_ids.push(contracts.oracle.contract.contract_id().into());
+            with_contract_ids.push(contracts.oracle.implementation_id.into());
+            with_contract_ids.push(contracts.mock_pyth_oracle.contract_id().into());
+            with_contract_ids.push(contracts.mock_redstone_oracle.contract_id().into());
+        }
+
+        vault
+            .methods()
+            .withdraw(receiver)
+            .with_tx_policies(tx_params)
+            .call_params(call_params)
+            .unwrap()
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(4 + asset_contracts.len()))
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+    }
+
+    pub async fn harvest<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        usdf_token: &ContractInstance<USDFToken<T>>,
+        swap_adapter: &MockSwapAdapter<T>,
+        assets: Vec<AssetId>,
+        min_amounts_out: Vec<u64>,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default()
+            .with_tip(1)
+            .with_script_gas_limit(2000000);
+
+        let num_outputs = assets.len() + 3;
+
+        vault
+            .methods()
+            .harvest(assets, min_amounts_out)
+            .with_tx_policies(tx_params)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(num_outputs))
+            .with_contracts(&[
+                &stability_pool.contract,
+                &community_issuance.contract,
+                &usdf_token.contract,
+                swap_adapter,
+            ])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+                usdf_token.contract.contract_id().into(),
+                usdf_token.implementation_id.into(),
+                swap_adapter.contract_id().into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn set_swap_adapter<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        swap_adapter: ContractId,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        vault
+            .methods()
+            .set_swap_adapter(swap_adapter)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn set_harvester<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        harvester: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        vault
+            .methods()
+            .set_harvester(harvester)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+    }
+
+    pub async fn get_total_usdf<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        asset_contracts: &Vec<AssetContracts<T>>,
+    ) -> CallResponse<u64> {
+        let mut with_contracts: Vec<&dyn ContractDependency> = vec![&stability_pool.contract];
+        let mut with_contract_ids: Vec<Bech32ContractId> = vec![
+            stability_pool.contract.contract_id().into(),
+            stability_pool.implementation_id.into(),
+        ];
+        push_oracle_contracts(asset_contracts, &mut with_contracts, &mut with_contract_ids);
+
+        vault
+            .methods()
+            .get_total_usdf()
+            .with_contracts(&with_contracts)
+            .with_contract_ids(&with_contract_ids)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn total_supply<T: Account>(
+        vault: &StabilityPoolVault<T>,
+    ) -> CallResponse<Option<u64>> {
+        let share_asset_id = vault.contract_id().asset_id(&AssetId::zeroed().into());
+
+        vault
+            .methods()
+            .total_supply(share_asset_id)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn preview_withdraw<T: Account>(
+        vault: &StabilityPoolVault<T>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        shares: u64,
+    ) -> CallResponse<u64> {
+        vault
+            .methods()
+            .preview_withdraw(shares)
+            .with_contracts(&[&stability_pool.contract])
+            .with_contract_ids(&[
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    // Unharvested collateral gains are priced with the oracle of each asset
+    fn push_oracle_contracts<'a, T: Account>(
+        asset_contracts: &'a Vec<AssetContracts<T>>,
+        with_contracts: &mut Vec<&'a dyn ContractDependency>,
+        with_contract_ids: &mut Vec<Bech32ContractId>,
+    ) {
+        for contracts in asset_contracts.iter() {
+            with_contracts.push(&contracts.oracle.contract);
+            with_contracts.push(&contracts.mock_pyth_oracle);
+            with_contracts.push(&contracts.mock_redstone_oracle);
+            with_contract_ids.push(contracts.oracle.contract.contract_id().into());
+            with_contract_ids.push(contracts.oracle.implementation_id.into());
+            with_contract_ids.push(contracts.mock_pyth_oracle.contract_id().into());
+            with_contract_ids.push(contracts.mock_redstone_oracle.contract_id().into());
+        }
+    }
+}
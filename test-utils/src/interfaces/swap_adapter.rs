@@ -0,0 +1,44 @@
+use fuels::prelude::abigen;
+use fuels::programs::responses::CallResponse;
+
+abigen!(Contract(
+    name = "MockSwapAdapter",
+    abi = "contracts/mock-swap-adapter-contract/out/debug/mock-swap-adapter-contract-abi.json"
+));
+
+pub mod swap_adapter_abi {
+    use super::*;
+    use fuels::prelude::{Account, TxPolicies};
+    use fuels::types::AssetId;
+
+    pub async fn set_rate<T: Account>(
+        swap_adapter: &MockSwapAdapter<T>,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        rate: u64,
+    ) -> CallResponse<()> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        swap_adapter
+            .methods()
+            .set_rate(asset_in, asset_out, rate)
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_amount_out<T: Account>(
+        swap_adapter: &MockSwapAdapter<T>,
+        asset_in: AssetId,
+        asset_out: AssetId,
+        amount_in: u64,
+    ) -> CallResponse<u64> {
+        swap_adapter
+            .methods()
+            .get_amount_out(asset_in, asset_out, amount_in)
+            .call()
+            .await
+            .unwrap()
+    }
+}
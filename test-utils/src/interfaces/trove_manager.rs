@@ -7,6 +7,7 @@ use crate::interfaces::pyth_oracle::PythCore;
 use crate::interfaces::redstone_oracle::RedstoneCore;
 use crate::interfaces::sorted_troves::SortedTroves;
 use crate::interfaces::stability_pool::StabilityPool;
+use crate::interfaces::swap_adapter::MockSwapAdapter;
 use crate::interfaces::usdf_token::USDFToken;
 use fuels::prelude::abigen;
 use fuels::programs::responses::CallResponse;
@@ -19,8 +20,11 @@ abigen!(Contract(
 pub mod trove_manager_abi {
 
     use fuels::{
-        prelude::{Account, Error, TxPolicies},
-        types::{transaction_builders::VariableOutputPolicy, AssetId, ContractId, Identity},
+        prelude::{Account, CallParameters, Error, TxPolicies},
+        types::{
+            bech32::Bech32ContractId, transaction_builders::VariableOutputPolicy, AssetId,
+            ContractId, Identity,
+        },
     };
 
     use crate::data_structures::ContractInstance;
@@ -106,6 +110,128 @@ pub mod trove_manager_abi {
             .await
     }
 
+    pub async fn batch_liquidate_troves_with_hints<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        pyth: &PythCore<T>,
+        redstone: &RedstoneCore<T>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        ids: Vec<Identity>,
+        partial_hints: Vec<(Identity, Identity)>,
+        skip_failures: bool,
+    ) -> Result<CallResponse<LiquidationSummary>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        trove_manager
+            .contract
+            .methods()
+            .batch_liquidate_troves_with_hints(ids, partial_hints, skip_failures)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[
+                &stability_pool.contract,
+                &oracle.contract,
+                pyth,
+                redstone,
+                &sorted_troves.contract,
+                &active_pool.contract,
+                &default_pool.contract,
+                &coll_surplus_pool.contract,
+                &usdf.contract,
+                &community_issuance.contract,
+            ])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+                pyth.contract_id().into(),
+                redstone.contract_id().into(),
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+                default_pool.contract.contract_id().into(),
+                default_pool.implementation_id.into(),
+                coll_surplus_pool.contract.contract_id().into(),
+                coll_surplus_pool.implementation_id.into(),
+                usdf.contract.contract_id().into(),
+                usdf.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .call()
+            .await
+    }
+
+    pub async fn liquidate_troves<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        pyth: &PythCore<T>,
+        redstone: &RedstoneCore<T>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        coll_surplus_pool: &ContractInstance<CollSurplusPool<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        n: u64,
+    ) -> Result<CallResponse<LiquidationSummary>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        trove_manager
+            .contract
+            .methods()
+            .liquidate_troves(n)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[
+                &stability_pool.contract,
+                &oracle.contract,
+                pyth,
+                redstone,
+                &sorted_troves.contract,
+                &active_pool.contract,
+                &default_pool.contract,
+                &coll_surplus_pool.contract,
+                &usdf.contract,
+                &community_issuance.contract,
+            ])
+            .with_contract_ids(&[
+                sorted_troves.contract.contract_id().into(),
+                sorted_troves.implementation_id.into(),
+                stability_pool.contract.contract_id().into(),
+                stability_pool.implementation_id.into(),
+                oracle.contract.contract_id().into(),
+                oracle.implementation_id.into(),
+                pyth.contract_id().into(),
+                redstone.contract_id().into(),
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+                default_pool.contract.contract_id().into(),
+                default_pool.implementation_id.into(),
+                coll_surplus_pool.contract.contract_id().into(),
+                coll_surplus_pool.implementation_id.into(),
+                usdf.contract.contract_id().into(),
+                usdf.implementation_id.into(),
+                community_issuance.contract.contract_id().into(),
+                community_issuance.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .call()
+            .await
+    }
+
     pub async fn liquidate<T: Account>(
         trove_manager: &ContractInstance<TroveManagerContract<T>>,
         community_issuance: &ContractInstance<CommunityIssuance<T>>,
@@ -379,6 +505,392 @@ pub mod trove_manager_abi {
             .unwrap()
     }
 
+    pub async fn set_debug_timestamp<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        timestamp: u64,
+    ) {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        trove_manager
+            .contract
+            .methods()
+            .set_debug_timestamp(timestamp)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_tx_policies(tx_params)
+            .call()
+            .await
+            .unwrap();
+    }
+
+    pub async fn auto_deleverage<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        community_issuance: &ContractInstance<CommunityIssuance<T>>,
+        stability_pool: &ContractInstance<StabilityPool<T>>,
+        oracle: &ContractInstance<Oracle<T>>,
+        pyth: &PythCore<T>,
+        redstone: &RedstoneCore<T>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        swap_adapter: Option<&MockSwapAdapter<T>>,
+        id: Identity,
+        upper_hint: Identity,
+        lower_hint: Identity,
+    ) -> Result<CallResponse<u64>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        let mut with_contract_ids: Vec<Bech32ContractId> = vec![
+            sorted_troves.contract.contract_id().into(),
+            sorted_troves.implementation_id.into(),
+            stability_pool.contract.contract_id().into(),
+            stability_pool.implementation_id.into(),
+            oracle.contract.contract_id().into(),
+            oracle.implementation_id.into(),
+            pyth.contract_id().into(),
+            redstone.contract_id().into(),
+            active_pool.contract.contract_id().into(),
+            active_pool.implementation_id.into(),
+            default_pool.contract.contract_id().into(),
+            default_pool.implementation_id.into(),
+            usdf.contract.contract_id().into(),
+            usdf.implementation_id.into(),
+            community_issuance.contract.contract_id().into(),
+            community_issuance.implementation_id.into(),
+            trove_manager.contract.contract_id().into(),
+            trove_manager.implementation_id.into(),
+        ];
+        if let Some(swap_adapter) = swap_adapter {
+            with_contract_ids.push(swap_adapter.contract_id().into());
+        }
+
+        trove_manager
+            .contract
+            .methods()
+            .auto_deleverage(id, upper_hint, lower_hint)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[
+                &stability_pool.contract,
+                &oracle.contract,
+                pyth,
+                redstone,
+                &sorted_troves.contract,
+                &active_pool.contract,
+                &default_pool.contract,
+                &usdf.contract,
+                &community_issuance.contract,
+            ])
+            .with_contract_ids(&with_contract_ids)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(3))
+            .call()
+            .await
+    }
+
+    pub async fn get_auto_deleverage<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        id: Identity,
+    ) -> CallResponse<AutoDeleverageSettings> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auto_deleverage(id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_auto_deleverage_params<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<AutoDeleverageParams> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auto_deleverage_params()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_liquidation_params<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<LiquidationParams> {
+        trove_manager
+            .contract
+            .methods()
+            .get_liquidation_params()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_auction_config<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<AuctionConfig> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auction_config()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_auction_count<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auction_count()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_auction<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        auction_id: u64,
+    ) -> CallResponse<Auction> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auction(auction_id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_auction_price<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        auction_id: u64,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_auction_price(auction_id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn bid_on_auction<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        auction_id: u64,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+        let usdf_asset_id: AssetId = usdf
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdf_asset_id);
+
+        trove_manager
+            .contract
+            .methods()
+            .bid_on_auction(auction_id)
+            .call_params(call_params)
+            .unwrap()
+            .with_tx_policies(tx_params)
+            .with_contracts(&[
+                &usdf.contract,
+                &active_pool.contract,
+                &default_pool.contract,
+            ])
+            .with_contract_ids(&[
+                usdf.contract.contract_id().into(),
+                usdf.implementation_id.into(),
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+                default_pool.contract.contract_id().into(),
+                default_pool.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .call()
+            .await
+    }
+
+    pub async fn settle_auction<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        active_pool: &ContractInstance<ActivePool<T>>,
+        default_pool: &ContractInstance<DefaultPool<T>>,
+        auction_id: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+
+        trove_manager
+            .contract
+            .methods()
+            .settle_auction(auction_id)
+            .with_tx_policies(tx_params)
+            .with_contracts(&[&active_pool.contract, &default_pool.contract])
+            .with_contract_ids(&[
+                active_pool.contract.contract_id().into(),
+                active_pool.implementation_id.into(),
+                default_pool.contract.contract_id().into(),
+                default_pool.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn fund_reserve<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        usdf: &ContractInstance<USDFToken<T>>,
+        amount: u64,
+    ) -> Result<CallResponse<()>, Error> {
+        let tx_params = TxPolicies::default().with_tip(1);
+        let usdf_asset_id: AssetId = usdf
+            .contract
+            .contract_id()
+            .asset_id(&AssetId::zeroed().into())
+            .into();
+
+        let call_params: CallParameters = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(usdf_asset_id);
+
+        trove_manager
+            .contract
+            .methods()
+            .fund_reserve()
+            .call_params(call_params)
+            .unwrap()
+            .with_tx_policies(tx_params)
+            .with_contracts(&[&usdf.contract])
+            .with_contract_ids(&[
+                usdf.contract.contract_id().into(),
+                usdf.implementation_id.into(),
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+    }
+
+    pub async fn get_reserve_balance<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_reserve_balance()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_bad_debt<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_bad_debt()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_stability_pool_bad_debt<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_stability_pool_bad_debt()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_trove_bad_debt<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        id: Identity,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_trove_bad_debt(id)
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_reserve_fee_share<T: Account>(
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+    ) -> CallResponse<u64> {
+        trove_manager
+            .contract
+            .methods()
+            .get_reserve_fee_share()
+            .with_contract_ids(&[
+                trove_manager.contract.contract_id().into(),
+                trove_manager.implementation_id.into(),
+            ])
+            .call()
+            .await
+            .unwrap()
+    }
+
     pub fn get_redemption_fee(asset_drawdown: u64) -> u64 {
         return asset_drawdown * 1 / 100;
     }
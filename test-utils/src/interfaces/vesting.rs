@@ -20,6 +20,7 @@ abigen!(Contract(
 pub const TOTAL_AMOUNT_VESTED: u64 = 100_000_000 * 68 / 100 * PRECISION;
 
 pub mod vesting_abi {
+    use fuels::prelude::CallParameters;
     use fuels::types::transaction_builders::VariableOutputPolicy;
 
     use crate::data_structures::ContractInstance;
@@ -29,12 +30,22 @@ pub mod vesting_abi {
         contract: &ContractInstance<VestingContract<T>>,
         asset_contract: &AssetId,
         schedules: Vec<VestingSchedule>,
+        tranches: Vec<Tranche>,
+        admin: Identity,
+        treasury: Identity,
         debug: bool,
     ) -> Result<CallResponse<()>, Error> {
         contract
             .contract
             .methods()
-            .constructor(asset_contract.clone().into(), schedules, debug)
+            .constructor(
+                asset_contract.clone().into(),
+                schedules,
+                tranches,
+                admin,
+                treasury,
+                debug,
+            )
             .with_contract_ids(&[contract.implementation_id.into()])
             .call()
             .await
@@ -91,6 +102,149 @@ pub mod vesting_abi {
             .call()
             .await
     }
+
+    pub async fn claim_for<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipient: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        contract
+            .contract
+            .methods()
+            .claim_for(recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+    }
+
+    pub async fn claim_to<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        destination: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        contract
+            .contract
+            .methods()
+            .claim_to(destination)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+    }
+
+    pub async fn claim_for_many<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipients: Vec<Identity>,
+    ) -> Result<CallResponse<()>, Error> {
+        let outputs = recipients.len();
+        contract
+            .contract
+            .methods()
+            .claim_for_many(recipients)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(outputs))
+            .call()
+            .await
+    }
+
+    pub async fn add_schedule<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        asset_id: AssetId,
+        schedule: VestingSchedule,
+        tranches: Vec<Tranche>,
+    ) -> Result<CallResponse<()>, Error> {
+        let call_params = CallParameters::default()
+            .with_amount(schedule.total_amount)
+            .with_asset_id(asset_id);
+
+        contract
+            .contract
+            .methods()
+            .add_schedule(schedule, tranches)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call_params(call_params)
+            .unwrap()
+            .call()
+            .await
+    }
+
+    pub async fn revoke<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipient: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        contract
+            .contract
+            .methods()
+            .revoke(recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+            .call()
+            .await
+    }
+
+    pub async fn propose_beneficiary<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        new_recipient: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        contract
+            .contract
+            .methods()
+            .propose_beneficiary(new_recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
+
+    pub async fn accept_beneficiary<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        previous_recipient: Identity,
+    ) -> Result<CallResponse<()>, Error> {
+        contract
+            .contract
+            .methods()
+            .accept_beneficiary(previous_recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
+
+    pub async fn get_pending_beneficiary<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipient: Identity,
+    ) -> Result<CallResponse<Option<Identity>>, Error> {
+        contract
+            .contract
+            .methods()
+            .get_pending_beneficiary(recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
+
+    pub async fn get_tranches<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipient: Identity,
+    ) -> Result<CallResponse<Vec<Tranche>>, Error> {
+        contract
+            .contract
+            .methods()
+            .get_tranches(recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
+
+    pub async fn is_revoked<T: Account>(
+        contract: &ContractInstance<VestingContract<T>>,
+        recipient: Identity,
+    ) -> Result<CallResponse<bool>, Error> {
+        contract
+            .contract
+            .methods()
+            .is_revoked(recipient)
+            .with_contract_ids(&[contract.implementation_id.into()])
+            .call()
+            .await
+    }
 }
 pub fn load_vesting_schedules_from_json_file(path: &str) -> Vec<VestingSchedule> {
     let absolute_path = get_absolute_path_from_relative(path);
@@ -136,7 +290,96 @@ pub fn get_vesting_schedule(
         claimed_amount,
         total_amount,
         recipient,
+        curve: VestingCurve::Linear,
+    }
+}
+
+// Mirrors of vesting-contract/src/utils.sw, used to validate schedules before they are deployed
+pub fn calculate_vested_amount(
+    current_time: u64,
+    schedule: &VestingSchedule,
+    tranches: &[Tranche],
+) -> u64 {
+    if current_time < schedule.cliff_timestamp {
+        return 0;
     }
+    if current_time >= schedule.end_timestamp {
+        return schedule.total_amount;
+    }
+
+    let total_minus_cliff = schedule.total_amount - schedule.cliff_amount;
+    let total_vesting_duration = schedule.end_timestamp - schedule.cliff_timestamp;
+    let time_elapsed = current_time - schedule.cliff_timestamp;
+    match schedule.curve {
+        VestingCurve::Linear => {
+            schedule.cliff_amount
+                + multiply_ratio(total_minus_cliff, time_elapsed, total_vesting_duration)
+        }
+        VestingCurve::Periodic(period) => {
+            schedule.cliff_amount
+                + multiply_ratio(
+                    total_minus_cliff,
+                    time_elapsed / period,
+                    total_vesting_duration / period,
+                )
+        }
+        VestingCurve::Tranches => tranches
+            .iter()
+            .filter(|tranche| tranche.timestamp <= current_time)
+            .map(|tranche| tranche.amount)
+            .sum(),
+    }
+}
+
+pub fn calculate_redeemable_amount(
+    current_time: u64,
+    schedule: &VestingSchedule,
+    tranches: &[Tranche],
+) -> u64 {
+    calculate_vested_amount(current_time, schedule, tranches) - schedule.claimed_amount
+}
+
+pub fn is_valid_vesting_schedule(schedule: &VestingSchedule, tranches: &[Tranche]) -> bool {
+    if schedule.cliff_timestamp >= schedule.end_timestamp {
+        return false;
+    }
+    if schedule.cliff_amount > schedule.total_amount {
+        return false;
+    }
+    if schedule.claimed_amount != 0 {
+        return false;
+    }
+    match schedule.curve {
+        VestingCurve::Linear => tranches.is_empty(),
+        VestingCurve::Periodic(period) => {
+            let total_vesting_duration = schedule.end_timestamp - schedule.cliff_timestamp;
+            period > 0 && total_vesting_duration % period == 0 && tranches.is_empty()
+        }
+        VestingCurve::Tranches => is_valid_tranche_list(schedule, tranches),
+    }
+}
+
+fn is_valid_tranche_list(schedule: &VestingSchedule, tranches: &[Tranche]) -> bool {
+    if tranches.is_empty() || schedule.cliff_amount != 0 {
+        return false;
+    }
+    if tranches[0].timestamp != schedule.cliff_timestamp
+        || tranches[tranches.len() - 1].timestamp != schedule.end_timestamp
+    {
+        return false;
+    }
+    let ordered = tranches
+        .windows(2)
+        .all(|pair| pair[0].timestamp < pair[1].timestamp);
+    let valid_tranches = tranches
+        .iter()
+        .all(|tranche| tranche.recipient == schedule.recipient && tranche.amount > 0);
+    let total_amount: u64 = tranches.iter().map(|tranche| tranche.amount).sum();
+    ordered && valid_tranches && total_amount == schedule.total_amount
+}
+
+fn multiply_ratio(value: u64, numerator: u64, denominator: u64) -> u64 {
+    (value as u128 * numerator as u128 / denominator as u128) as u64
 }
 
 // Intermediary struct matching the JSON structure
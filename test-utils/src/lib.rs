@@ -1,5 +1,7 @@
 pub mod data_structures;
 pub mod interfaces;
+pub mod off_chain_hints;
 pub mod paths;
+pub mod price_path;
 pub mod setup;
 pub mod utils;
@@ -0,0 +1,409 @@
+// Off-chain mirror of the SortedTroves list, used to compute insert positions and redemption hints
+// without dry-running `HintHelper`. The list is rebuilt from `MultiTroveGetter` data and kept up to
+// date by applying the events logged by borrow operations and the trove managers.
+//
+// Redistributed liquidation rewards don't emit per-trove events, so a `sync_from_chain` is needed
+// after a redistribution to pick up the new pending rewards.
+use std::collections::HashMap;
+
+use fuels::prelude::Account;
+use fuels::programs::responses::CallResponse;
+use fuels::types::{Address, AssetId, Identity};
+
+use crate::data_structures::{ContractInstance, MIN_NET_DEBT};
+use crate::interfaces::borrow_operations::{AdjustTroveEvent, CloseTroveEvent, OpenTroveEvent};
+use crate::interfaces::multi_trove_getter::{
+    multi_trove_getter_abi, CombinedTroveData, MultiTroveGetter,
+};
+use crate::interfaces::sorted_troves::SortedTroves;
+use crate::interfaces::trove_manager::{
+    RedemptionEvent, TroveFullLiquidationEvent, TroveManagerContract, TrovePartialLiquidationEvent,
+};
+
+const DECIMAL_PRECISION: u64 = 1_000_000_000;
+const MCR: u64 = 1_350_000_000;
+const MAX_PAGE_SIZE: u8 = u8::MAX;
+
+// Mirror of `fm_compute_nominal_cr` in libraries/src/fluid_math.sw
+pub fn compute_nominal_cr(coll: u64, debt: u64) -> u64 {
+    if debt > 0 {
+        let ncr = coll as u128 * DECIMAL_PRECISION as u128 / debt as u128;
+        return ncr.try_into().unwrap();
+    }
+    u64::MAX
+}
+
+// Mirror of `fm_compute_cr` in libraries/src/fluid_math.sw
+pub fn compute_cr(coll: u64, debt: u64, price: u64) -> u64 {
+    if debt > 0 {
+        let cr = coll as u128 * price as u128 / debt as u128;
+        return cr.try_into().unwrap();
+    }
+    u64::MAX
+}
+
+fn null_identity() -> Identity {
+    Identity::Address(Address::zeroed())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffChainTrove {
+    pub id: Identity,
+    pub coll: u64,
+    pub debt: u64,
+}
+
+impl OffChainTrove {
+    pub fn nicr(&self) -> u64 {
+        compute_nominal_cr(self.coll, self.debt)
+    }
+
+    pub fn icr(&self, price: u64) -> u64 {
+        compute_cr(self.coll, self.debt, price)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffChainRedemptionHints {
+    pub first_redemption_hint: Identity,
+    pub partial_redemption_hint_nicr: u64,
+    pub truncated_usdf_amount: u64,
+}
+
+/// Sorted list of the troves of a single asset, ordered from head (highest NICR) to tail.
+#[derive(Debug, Clone, Default)]
+pub struct OffChainSortedTroves {
+    troves: Vec<OffChainTrove>,
+}
+
+impl OffChainSortedTroves {
+    /// Builds the list from `get_multiple_sorted_troves` pages, which are returned tail first.
+    pub fn from_combined_trove_data(data: &[CombinedTroveData]) -> Self {
+        let troves = data
+            .iter()
+            .rev()
+            .map(|trove| OffChainTrove {
+                id: trove.address.clone(),
+                coll: trove.collateral,
+                debt: trove.debt,
+            })
+            .collect();
+
+        Self { troves }
+    }
+
+    pub fn troves(&self) -> &[OffChainTrove] {
+        &self.troves
+    }
+
+    pub fn len(&self) -> usize {
+        self.troves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.troves.is_empty()
+    }
+
+    pub fn contains(&self, id: &Identity) -> bool {
+        self.position(id).is_some()
+    }
+
+    pub fn get(&self, id: &Identity) -> Option<&OffChainTrove> {
+        self.troves.iter().find(|trove| &trove.id == id)
+    }
+
+    pub fn get_first(&self) -> Identity {
+        self.troves
+            .first()
+            .map(|trove| trove.id.clone())
+            .unwrap_or(null_identity())
+    }
+
+    pub fn get_last(&self) -> Identity {
+        self.troves
+            .last()
+            .map(|trove| trove.id.clone())
+            .unwrap_or(null_identity())
+    }
+
+    fn position(&self, id: &Identity) -> Option<usize> {
+        self.troves.iter().position(|trove| &trove.id == id)
+    }
+
+    // Same index `internal_descend_list` lands on when walking from the head:
+    // before the first node whose NICR is lower or equal
+    fn insert_index(&self, nicr: u64) -> usize {
+        self.troves
+            .iter()
+            .position(|trove| trove.nicr() <= nicr)
+            .unwrap_or(self.troves.len())
+    }
+
+    fn pair_at(&self, index: usize) -> (Identity, Identity) {
+        let prev_id = if index == 0 {
+            null_identity()
+        } else {
+            self.troves[index - 1].id.clone()
+        };
+        let next_id = self
+            .troves
+            .get(index)
+            .map(|trove| trove.id.clone())
+            .unwrap_or(null_identity());
+
+        (prev_id, next_id)
+    }
+
+    /// Exact (prev, next) hints for inserting a new trove with the given NICR.
+    pub fn find_insert_position(&self, nicr: u64) -> (Identity, Identity) {
+        self.pair_at(self.insert_index(nicr))
+    }
+
+    /// Exact (prev, next) hints for re-inserting an existing trove, ignoring its current node.
+    pub fn find_reinsert_position(&self, id: &Identity, nicr: u64) -> (Identity, Identity) {
+        let mut list = self.clone();
+        list.remove(id);
+        list.find_insert_position(nicr)
+    }
+
+    /// Mirror of `HintHelper::get_redemption_hints`. A `max_iterations` of 0 means no limit.
+    pub fn get_redemption_hints(
+        &self,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+    ) -> OffChainRedemptionHints {
+        let mut remaining_usdf = usdf_amount;
+        let mut index = self.troves.len();
+
+        while index > 0 && self.troves[index - 1].icr(price) < MCR {
+            index -= 1;
+        }
+
+        let first_redemption_hint = if index == 0 {
+            null_identity()
+        } else {
+            self.troves[index - 1].id.clone()
+        };
+        let mut partial_redemption_hint_nicr = 0;
+        let mut remaining_iterations = if max_iterations == 0 {
+            u64::MAX
+        } else {
+            max_iterations
+        };
+
+        while index > 0 && remaining_usdf > 0 && remaining_iterations > 0 {
+            remaining_iterations -= 1;
+            let trove = &self.troves[index - 1];
+
+            if trove.debt > remaining_usdf {
+                if trove.debt > MIN_NET_DEBT {
+                    let max_redeemable_usdf = remaining_usdf.min(trove.debt - MIN_NET_DEBT);
+                    let asset_lot = (max_redeemable_usdf as u128 * DECIMAL_PRECISION as u128
+                        / price as u128) as u64;
+                    partial_redemption_hint_nicr = compute_nominal_cr(
+                        trove.coll - asset_lot,
+                        trove.debt - max_redeemable_usdf,
+                    );
+                    remaining_usdf -= max_redeemable_usdf;
+                }
+                break;
+            }
+
+            remaining_usdf -= trove.debt;
+            index -= 1;
+        }
+
+        OffChainRedemptionHints {
+            first_redemption_hint,
+            partial_redemption_hint_nicr,
+            truncated_usdf_amount: usdf_amount - remaining_usdf,
+        }
+    }
+
+    /// Inserts or moves a trove to the position matching its new collateral and debt.
+    pub fn upsert(&mut self, id: Identity, coll: u64, debt: u64) {
+        self.remove(&id);
+        if debt == 0 {
+            return;
+        }
+        let trove = OffChainTrove { id, coll, debt };
+        let index = self.insert_index(trove.nicr());
+        self.troves.insert(index, trove);
+    }
+
+    pub fn remove(&mut self, id: &Identity) -> Option<OffChainTrove> {
+        self.position(id).map(|index| self.troves.remove(index))
+    }
+
+    pub fn apply_open_trove(&mut self, event: &OpenTroveEvent) {
+        self.upsert(event.user.clone(), event.collateral, event.debt);
+    }
+
+    pub fn apply_adjust_trove(&mut self, event: &AdjustTroveEvent) {
+        self.upsert(event.user.clone(), event.total_collateral, event.total_debt);
+    }
+
+    pub fn apply_close_trove(&mut self, event: &CloseTroveEvent) {
+        self.remove(&event.user);
+    }
+
+    pub fn apply_full_liquidation(&mut self, event: &TroveFullLiquidationEvent) {
+        self.remove(&event.borrower);
+    }
+
+    pub fn apply_partial_liquidation(&mut self, event: &TrovePartialLiquidationEvent) {
+        self.upsert(
+            event.borrower.clone(),
+            event.remaining_collateral,
+            event.remaining_debt,
+        );
+    }
+
+    pub fn apply_redemption(&mut self, event: &RedemptionEvent) {
+        if let Some(trove) = self.get(&event.borrower).cloned() {
+            self.upsert(
+                trove.id,
+                trove.coll.saturating_sub(event.collateral_amount),
+                trove.debt.saturating_sub(event.usdf_amount),
+            );
+        }
+    }
+}
+
+/// Off-chain sorted lists for every asset of the protocol.
+#[derive(Debug, Clone, Default)]
+pub struct OffChainHintEngine {
+    lists: HashMap<AssetId, OffChainSortedTroves>,
+}
+
+impl OffChainHintEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn list(&self, asset_id: &AssetId) -> Option<&OffChainSortedTroves> {
+        self.lists.get(asset_id)
+    }
+
+    pub fn list_mut(&mut self, asset_id: &AssetId) -> &mut OffChainSortedTroves {
+        self.lists.entry(*asset_id).or_default()
+    }
+
+    /// Replaces the list of an asset with the current on-chain state.
+    pub async fn sync_from_chain<T: Account>(
+        &mut self,
+        multi_trove_getter: &MultiTroveGetter<T>,
+        trove_manager: &ContractInstance<TroveManagerContract<T>>,
+        sorted_troves: &ContractInstance<SortedTroves<T>>,
+        asset_id: &AssetId,
+    ) {
+        let mut data: Vec<CombinedTroveData> = vec![];
+        loop {
+            let page = multi_trove_getter_abi::get_multiple_sorted_troves(
+                multi_trove_getter,
+                trove_manager,
+                sorted_troves,
+                asset_id,
+                data.len() as u64,
+                MAX_PAGE_SIZE,
+            )
+            .await
+            .value;
+            let page_len = page.len();
+            data.extend(page);
+
+            if page_len < MAX_PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        self.lists.insert(
+            *asset_id,
+            OffChainSortedTroves::from_combined_trove_data(&data),
+        );
+    }
+
+    pub fn find_insert_position(&self, asset_id: &AssetId, nicr: u64) -> (Identity, Identity) {
+        self.lists
+            .get(asset_id)
+            .map(|list| list.find_insert_position(nicr))
+            .unwrap_or((null_identity(), null_identity()))
+    }
+
+    pub fn find_reinsert_position(
+        &self,
+        asset_id: &AssetId,
+        id: &Identity,
+        nicr: u64,
+    ) -> (Identity, Identity) {
+        self.lists
+            .get(asset_id)
+            .map(|list| list.find_reinsert_position(id, nicr))
+            .unwrap_or((null_identity(), null_identity()))
+    }
+
+    pub fn get_redemption_hints(
+        &self,
+        asset_id: &AssetId,
+        usdf_amount: u64,
+        price: u64,
+        max_iterations: u64,
+    ) -> OffChainRedemptionHints {
+        self.lists
+            .get(asset_id)
+            .map(|list| list.get_redemption_hints(usdf_amount, price, max_iterations))
+            .unwrap_or(OffChainRedemptionHints {
+                first_redemption_hint: null_identity(),
+                partial_redemption_hint_nicr: 0,
+                truncated_usdf_amount: 0,
+            })
+    }
+
+    /// Applies the `OpenTroveEvent`, `AdjustTroveEvent` and `CloseTroveEvent` logs of a borrow operations call.
+    pub fn apply_borrow_operations_logs<D>(&mut self, response: &CallResponse<D>) {
+        for event in response
+            .decode_logs_with_type::<OpenTroveEvent>()
+            .unwrap_or_default()
+        {
+            self.list_mut(&event.asset_id).apply_open_trove(&event);
+        }
+        for event in response
+            .decode_logs_with_type::<AdjustTroveEvent>()
+            .unwrap_or_default()
+        {
+            self.list_mut(&event.asset_id).apply_adjust_trove(&event);
+        }
+        for event in response
+            .decode_logs_with_type::<CloseTroveEvent>()
+            .unwrap_or_default()
+        {
+            self.list_mut(&event.asset_id).apply_close_trove(&event);
+        }
+    }
+
+    /// Applies the liquidation and redemption logs of a trove manager. Trove manager events
+    /// don't carry the asset, so the asset of the trove manager has to be passed in. Redemptions
+    /// spanning several assets log from every trove manager, so resync those lists instead.
+    pub fn apply_trove_manager_logs<D>(&mut self, asset_id: &AssetId, response: &CallResponse<D>) {
+        let list = self.list_mut(asset_id);
+        for event in response
+            .decode_logs_with_type::<TroveFullLiquidationEvent>()
+            .unwrap_or_default()
+        {
+            list.apply_full_liquidation(&event);
+        }
+        for event in response
+            .decode_logs_with_type::<TrovePartialLiquidationEvent>()
+            .unwrap_or_default()
+        {
+            list.apply_partial_liquidation(&event);
+        }
+        for event in response
+            .decode_logs_with_type::<RedemptionEvent>()
+            .unwrap_or_default()
+        {
+            list.apply_redemption(&event);
+        }
+    }
+}
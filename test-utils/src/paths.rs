@@ -89,7 +89,19 @@ pub const MULTI_TROVE_GETTER_CONTRACT_BINARY_PATH: &str =
 pub const MULTI_TROVE_GETTER_CONTRACT_STORAGE_PATH: &str =
     "contracts/multi-trove-getter-contract/out/debug/multi-trove-getter-contract-storage_slots.json";
 
+pub const LENS_CONTRACT_BINARY_PATH: &str = "contracts/lens-contract/out/debug/lens-contract.bin";
+pub const LENS_CONTRACT_STORAGE_PATH: &str =
+    "contracts/lens-contract/out/debug/lens-contract-storage_slots.json";
+
 pub const PROXY_CONTRACT_BINARY_PATH: &str =
     "contracts/proxy-contract/out/debug/proxy-contract.bin";
 pub const PROXY_CONTRACT_STORAGE_PATH: &str =
     "contracts/proxy-contract/out/debug/proxy-contract-storage_slots.json";
+
+pub const STABILITY_POOL_VAULT_CONTRACT_BINARY_PATH: &str =
+    "contracts/stability-pool-vault-contract/out/debug/stability-pool-vault-contract.bin";
+pub const STABILITY_POOL_VAULT_CONTRACT_STORAGE_PATH: &str =
+    "contracts/stability-pool-vault-contract/out/debug/stability-pool-vault-contract-storage_slots.json";
+
+pub const MOCK_SWAP_ADAPTER_CONTRACT_BINARY_PATH: &str =
+    "contracts/mock-swap-adapter-contract/out/debug/mock-swap-adapter-contract.bin";
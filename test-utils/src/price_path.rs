@@ -0,0 +1,300 @@
+// Historical price paths for replaying market moves into the mock Pyth and Redstone contracts.
+//
+// A price path is a series of (timestamp, price, confidence) points per asset, loaded from
+// CSV or JSON. Prices and confidences are expressed in USD as decimals (e.g. 3012.55) and are
+// converted to the fixed point representation expected by each mock oracle when replayed.
+//
+// CSV format, one row per point, header required:
+//   asset,timestamp,price,confidence
+//   ETH,1583971200,194.81,0.35
+//
+// JSON format, keyed by asset:
+//   { "ETH": [{ "timestamp": 1583971200, "price": 194.81, "confidence": 0.35 }] }
+//
+// The confidence column/field is optional and defaults to 0.
+use crate::{
+    data_structures::{AssetContracts, ContractInstance},
+    interfaces::{
+        oracle::{oracle_abi, Oracle},
+        pyth_oracle::{pyth_oracle_abi, Price, PythCore, PYTH_PRECISION},
+        redstone_oracle::{redstone_oracle_abi, RedstoneCore},
+    },
+    setup::common::get_absolute_path_from_relative,
+};
+use csv::ReaderBuilder;
+use fuels::{
+    prelude::Account,
+    types::{Bits256, U256},
+};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs::File, io::Read};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct PricePoint {
+    pub timestamp: u64,
+    pub price: f64,
+    #[serde(default)]
+    pub confidence: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PricePathRecord {
+    asset: String,
+    timestamp: u64,
+    price: f64,
+    #[serde(default)]
+    confidence: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PricePath {
+    pub points: Vec<PricePoint>,
+}
+
+impl PricePath {
+    pub fn new(mut points: Vec<PricePoint>) -> Self {
+        points.sort_by_key(|point| point.timestamp);
+        Self { points }
+    }
+
+    pub fn start_timestamp(&self) -> Option<u64> {
+        self.points.first().map(|point| point.timestamp)
+    }
+
+    pub fn end_timestamp(&self) -> Option<u64> {
+        self.points.last().map(|point| point.timestamp)
+    }
+
+    // Latest point published at or before the given timestamp
+    pub fn price_at(&self, timestamp: u64) -> Option<&PricePoint> {
+        self.points
+            .iter()
+            .take_while(|point| point.timestamp <= timestamp)
+            .last()
+    }
+
+    // Shifts every point so the path starts at the given timestamp, keeping relative spacing
+    pub fn rebased_to(&self, start_timestamp: u64) -> Self {
+        let offset = self.start_timestamp().unwrap_or(start_timestamp);
+        Self {
+            points: self
+                .points
+                .iter()
+                .map(|point| PricePoint {
+                    timestamp: point.timestamp - offset + start_timestamp,
+                    ..*point
+                })
+                .collect(),
+        }
+    }
+}
+
+pub fn load_price_paths_from_csv(path: &str) -> BTreeMap<String, PricePath> {
+    let absolute_path = get_absolute_path_from_relative(path);
+    let file = File::open(&absolute_path).expect("Failed to open price path file");
+    price_paths_from_csv_reader(file)
+}
+
+pub fn price_paths_from_csv_reader<R: Read>(reader: R) -> BTreeMap<String, PricePath> {
+    let mut reader = ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .has_headers(true)
+        .from_reader(reader);
+
+    let mut points: BTreeMap<String, Vec<PricePoint>> = BTreeMap::new();
+    for result in reader.deserialize() {
+        let record: PricePathRecord = result.expect("Failed to read price path record");
+        points
+            .entry(record.asset.to_uppercase())
+            .or_default()
+            .push(PricePoint {
+                timestamp: record.timestamp,
+                price: record.price,
+                confidence: record.confidence,
+            });
+    }
+
+    points
+        .into_iter()
+        .map(|(asset, points)| (asset, PricePath::new(points)))
+        .collect()
+}
+
+pub fn load_price_paths_from_json(path: &str) -> BTreeMap<String, PricePath> {
+    let absolute_path = get_absolute_path_from_relative(path);
+    let json = std::fs::read_to_string(&absolute_path).expect("Failed to read price path file");
+    price_paths_from_json_str(&json)
+}
+
+pub fn price_paths_from_json_str(json: &str) -> BTreeMap<String, PricePath> {
+    let paths: BTreeMap<String, Vec<PricePoint>> =
+        serde_json::from_str(json).expect("Failed to parse price path json");
+
+    paths
+        .into_iter()
+        .map(|(asset, points)| (asset.to_uppercase(), PricePath::new(points)))
+        .collect()
+}
+
+// Sorted, deduplicated timestamps of all paths, used to step several assets in lockstep
+pub fn merged_timestamps(paths: &[&PricePath]) -> Vec<u64> {
+    let mut timestamps: Vec<u64> = paths
+        .iter()
+        .flat_map(|path| path.points.iter().map(|point| point.timestamp))
+        .collect();
+    timestamps.sort_unstable();
+    timestamps.dedup();
+    timestamps
+}
+
+pub fn to_fixed_point(value: f64, decimals: u32) -> u64 {
+    (value * 10f64.powi(decimals as i32)).round() as u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSource {
+    Pyth,
+    Redstone,
+    PythAndRedstone,
+}
+
+// The oracle of an asset together with the mock feeds it reads from
+pub struct OracleFeeds<'a, T: Account> {
+    pub oracle: &'a ContractInstance<Oracle<T>>,
+    pub pyth: &'a PythCore<T>,
+    pub pyth_price_id: Bits256,
+    pub redstone: Option<&'a RedstoneCore<T>>,
+    pub redstone_price_id: U256,
+    pub redstone_precision: u32,
+}
+
+impl<'a, T: Account> OracleFeeds<'a, T> {
+    pub fn new(
+        oracle: &'a ContractInstance<Oracle<T>>,
+        pyth: &'a PythCore<T>,
+        pyth_price_id: Bits256,
+    ) -> Self {
+        Self {
+            oracle,
+            pyth,
+            pyth_price_id,
+            redstone: None,
+            redstone_price_id: U256::zero(),
+            redstone_precision: 9,
+        }
+    }
+
+    pub fn with_redstone(
+        mut self,
+        redstone: &'a RedstoneCore<T>,
+        redstone_price_id: U256,
+        redstone_precision: u32,
+    ) -> Self {
+        self.redstone = Some(redstone);
+        self.redstone_price_id = redstone_price_id;
+        self.redstone_precision = redstone_precision;
+        self
+    }
+
+    pub fn from_asset_contracts(asset_contracts: &'a AssetContracts<T>) -> Self {
+        Self::new(
+            &asset_contracts.oracle,
+            &asset_contracts.mock_pyth_oracle,
+            asset_contracts.pyth_price_id,
+        )
+        .with_redstone(
+            &asset_contracts.mock_redstone_oracle,
+            asset_contracts.redstone_price_id,
+            asset_contracts.redstone_precision,
+        )
+    }
+}
+
+// Publishes a single point to the selected mock feeds and moves the oracle clock to its timestamp
+pub async fn apply_price_point<T: Account>(
+    feeds: &OracleFeeds<'_, T>,
+    point: &PricePoint,
+    source: PriceSource,
+) {
+    if source != PriceSource::Redstone {
+        pyth_oracle_abi::update_price_feeds(
+            feeds.pyth,
+            vec![(
+                feeds.pyth_price_id,
+                Price {
+                    confidence: to_fixed_point(point.confidence, PYTH_PRECISION),
+                    exponent: PYTH_PRECISION,
+                    price: to_fixed_point(point.price, PYTH_PRECISION),
+                    publish_time: point.timestamp,
+                },
+            )],
+        )
+        .await;
+    }
+
+    if source != PriceSource::Pyth {
+        let redstone = feeds
+            .redstone
+            .expect("Redstone feed is required to replay Redstone prices");
+        redstone_oracle_abi::write_prices(
+            redstone,
+            vec![(
+                feeds.redstone_price_id,
+                U256::from(to_fixed_point(point.price, feeds.redstone_precision)),
+            )],
+        )
+        .await;
+        redstone_oracle_abi::set_timestamp(redstone, point.timestamp).await;
+    }
+
+    oracle_abi::set_debug_timestamp(feeds.oracle, point.timestamp).await;
+}
+
+// Replays a path one point at a time, the caller runs its scenario between steps
+pub struct PricePathReplay<'a> {
+    path: &'a PricePath,
+    source: PriceSource,
+    next_index: usize,
+}
+
+impl<'a> PricePathReplay<'a> {
+    pub fn new(path: &'a PricePath, source: PriceSource) -> Self {
+        Self {
+            path,
+            source,
+            next_index: 0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_index >= self.path.points.len()
+    }
+
+    pub async fn step<T: Account>(&mut self, feeds: &OracleFeeds<'_, T>) -> Option<PricePoint> {
+        let point = *self.path.points.get(self.next_index)?;
+        apply_price_point(feeds, &point, self.source).await;
+        self.next_index += 1;
+        Some(point)
+    }
+
+    // Skips to the latest point at or before the timestamp, only that point is published
+    pub async fn advance_to<T: Account>(
+        &mut self,
+        feeds: &OracleFeeds<'_, T>,
+        timestamp: u64,
+    ) -> Option<PricePoint> {
+        let mut latest = None;
+        while let Some(point) = self.path.points.get(self.next_index) {
+            if point.timestamp > timestamp {
+                break;
+            }
+            latest = Some(*point);
+            self.next_index += 1;
+        }
+
+        if let Some(point) = latest {
+            apply_price_point(feeds, &point, self.source).await;
+        }
+        latest
+    }
+}
@@ -7,6 +7,7 @@ use super::interfaces::{
     fpt_staking::{FPTStaking, FPTStakingConfigurables},
     fpt_token::{FPTToken, FPTTokenConfigurables},
     hint_helper::HintHelper,
+    lens::{Lens, LensConfigurables},
     multi_trove_getter::{MultiTroveGetter, MultiTroveGetterConfigurables},
     oracle::{Oracle, OracleConfigurables},
     protocol_manager::{ProtocolManager, ProtocolManagerConfigurables},
@@ -14,6 +15,8 @@ use super::interfaces::{
     redstone_oracle::{RedstoneCore, DEFAULT_REDSTONE_PRICE_ID},
     sorted_troves::{SortedTroves, SortedTrovesConfigurables},
     stability_pool::{StabilityPool, StabilityPoolConfigurables},
+    stability_pool_vault::{StabilityPoolVault, StabilityPoolVaultConfigurables},
+    swap_adapter::MockSwapAdapter,
     token::Token,
     trove_manager::{TroveManagerContract, TroveManagerContractConfigurables},
     usdf_token::{USDFToken, USDFTokenConfigurables},
@@ -257,6 +260,7 @@ pub mod common {
             contracts.coll_surplus_pool.contract.contract_id().into(),
             contracts.active_pool.contract.contract_id().into(),
             contracts.sorted_troves.contract.contract_id().into(),
+            contracts.stability_pool.contract.contract_id().into(),
         )
         .await;
         if verbose {
@@ -270,6 +274,7 @@ pub mod common {
             contracts.protocol_manager.contract.contract_id().into(),
             contracts.active_pool.contract.contract_id().into(),
             contracts.sorted_troves.contract.contract_id().into(),
+            contracts.borrow_operations.contract.contract_id().into(),
         )
         .await
         .unwrap();
@@ -283,6 +288,7 @@ pub mod common {
             contracts.borrow_operations.contract.contract_id().into(),
             contracts.fpt_asset_id,
             contracts.usdf_asset_id,
+            debug,
         )
         .await;
         if verbose {
@@ -480,6 +486,7 @@ pub mod common {
 
     pub async fn deploy_trove_manager_contract(
         wallet: &WalletUnlocked,
+        debug: bool,
     ) -> ContractInstance<TroveManagerContract<WalletUnlocked>> {
         let mut rng = rand::thread_rng();
         let salt = rng.gen::<[u8; 32]>();
@@ -488,6 +495,8 @@ pub mod common {
         let initializer = Identity::Address(wallet.address().into());
         let configurables = TroveManagerContractConfigurables::default()
             .with_INITIALIZER(initializer)
+            .unwrap()
+            .with_DEBUG(debug)
             .unwrap();
 
         let id = Contract::load_from(
@@ -870,7 +879,7 @@ pub mod common {
         .await;
         pb.inc();
 
-        let trove_manager = deploy_trove_manager_contract(&wallet).await;
+        let trove_manager = deploy_trove_manager_contract(&wallet, debug).await;
         pb.inc();
 
         // Set up price feeds if we deployed new oracles
@@ -926,9 +935,13 @@ pub mod common {
             Identity::Address(wallet.address().into()),
         )
         .await;
-        let trove_manager = deploy_trove_manager_contract(wallet).await;
+        let trove_manager = deploy_trove_manager_contract(wallet, true).await;
         let asset = deploy_token(wallet).await;
 
+        oracle_abi::initialize(&oracle, Identity::Address(wallet.address().into()))
+            .await
+            .unwrap();
+
         token_abi::initialize(
             &asset,
             1_000_000_000,
@@ -1004,7 +1017,16 @@ pub mod common {
         asset_contracts: &AssetContractsOptionalRedstone<T>,
     ) -> Result<CallResponse<()>> {
         println!("Initializing asset contracts...");
-        let mut pb = ProgressBar::new(2);
+        let mut pb = ProgressBar::new(3);
+
+        // The deployer, set as initializer of the oracle, becomes its owner
+        let _ = oracle_abi::initialize(
+            &asset_contracts.oracle,
+            Identity::Address(asset_contracts.oracle.contract.account().address().into()),
+        )
+        .await
+        .unwrap();
+        pb.inc();
 
         let _ = trove_manager_abi::initialize(
             &asset_contracts.trove_manager,
@@ -1361,6 +1383,101 @@ pub mod common {
         MultiTroveGetter::new(id, wallet.clone())
     }
 
+    pub async fn deploy_lens(
+        wallet: &WalletUnlocked,
+        contracts: &ProtocolContracts<WalletUnlocked>,
+    ) -> Lens<WalletUnlocked> {
+        let mut rng = rand::thread_rng();
+        let salt = rng.gen::<[u8; 32]>();
+
+        let configurables = LensConfigurables::default()
+            .with_PROTOCOL_MANAGER_CONTRACT(
+                contracts.protocol_manager.contract.contract_id().into(),
+            )
+            .unwrap()
+            .with_BORROW_OPERATIONS_CONTRACT(
+                contracts.borrow_operations.contract.contract_id().into(),
+            )
+            .unwrap()
+            .with_STABILITY_POOL_CONTRACT(contracts.stability_pool.contract.contract_id().into())
+            .unwrap()
+            .with_FPT_STAKING_CONTRACT(contracts.fpt_staking.contract.contract_id().into())
+            .unwrap()
+            .with_USDF_TOKEN_CONTRACT(contracts.usdf.contract.contract_id().into())
+            .unwrap()
+            .with_ACTIVE_POOL_CONTRACT(contracts.active_pool.contract.contract_id().into())
+            .unwrap()
+            .with_DEFAULT_POOL_CONTRACT(contracts.default_pool.contract.contract_id().into())
+            .unwrap()
+            .with_COLL_SURPLUS_POOL_CONTRACT(
+                contracts.coll_surplus_pool.contract.contract_id().into(),
+            )
+            .unwrap()
+            .with_VESTING_CONTRACT(contracts.vesting_contract.contract.contract_id().into())
+            .unwrap();
+
+        let id = Contract::load_from(
+            &get_absolute_path_from_relative(LENS_CONTRACT_BINARY_PATH),
+            LoadConfiguration::default()
+                .with_salt(salt)
+                .with_configurables(configurables.clone()),
+        )
+        .unwrap()
+        .deploy(&wallet.clone(), TxPolicies::default().with_tip(1))
+        .await
+        .unwrap();
+
+        Lens::new(id, wallet.clone())
+    }
+
+    pub async fn deploy_stability_pool_vault(
+        wallet: &WalletUnlocked,
+        stability_pool_contract_id: &ContractId,
+        usdf_asset_id: &AssetId,
+    ) -> StabilityPoolVault<WalletUnlocked> {
+        let mut rng = rand::thread_rng();
+        let salt = rng.gen::<[u8; 32]>();
+
+        let configurables = StabilityPoolVaultConfigurables::default()
+            .with_INITIALIZER(Identity::Address(wallet.address().into()))
+            .unwrap()
+            .with_STABILITY_POOL_CONTRACT(stability_pool_contract_id.clone())
+            .unwrap()
+            .with_USDF_ASSET_ID(usdf_asset_id.clone())
+            .unwrap();
+
+        let id = Contract::load_from(
+            &get_absolute_path_from_relative(STABILITY_POOL_VAULT_CONTRACT_BINARY_PATH),
+            LoadConfiguration::default()
+                .with_salt(salt)
+                .with_configurables(configurables.clone()),
+        )
+        .unwrap()
+        .deploy(&wallet.clone(), TxPolicies::default().with_tip(1))
+        .await
+        .unwrap();
+
+        StabilityPoolVault::new(id, wallet.clone())
+    }
+
+    pub async fn deploy_mock_swap_adapter(
+        wallet: &WalletUnlocked,
+    ) -> MockSwapAdapter<WalletUnlocked> {
+        let mut rng = rand::thread_rng();
+        let salt = rng.gen::<[u8; 32]>();
+
+        let id = Contract::load_from(
+            &get_absolute_path_from_relative(MOCK_SWAP_ADAPTER_CONTRACT_BINARY_PATH),
+            LoadConfiguration::default().with_salt(salt),
+        )
+        .unwrap()
+        .deploy(&wallet.clone(), TxPolicies::default().with_tip(1))
+        .await
+        .unwrap();
+
+        MockSwapAdapter::new(id, wallet.clone())
+    }
+
     pub fn print_response<T>(response: &CallResponse<T>)
     where
         T: std::fmt::Debug,
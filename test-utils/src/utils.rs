@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use fuels::programs::responses::CallResponse;
 
+use crate::interfaces::community_issuance::EmissionPhase;
+
 const DECIMAL_PRECISION: u64 = 1_000_000_000;
 
 // 0.5% min borrow fee
@@ -29,9 +31,99 @@ pub fn calculate_cr(price: u64, coll: u64, debt: u64) -> u64 {
     return cr.try_into().unwrap_or(0);
 }
 
-pub fn with_liquidation_penalty(amount: u64) -> u64 {
-    let amount_with_penalty = amount * 1_10 / 1_00;
-    return amount_with_penalty;
+pub fn with_liquidation_penalty(amount: u64, stability_pool_fee: u64) -> u64 {
+    let amount_with_penalty = amount as u128 * (DECIMAL_PRECISION + stability_pool_fee) as u128
+        / DECIMAL_PRECISION as u128;
+    return amount_with_penalty.try_into().unwrap();
+}
+
+// Share of the liquidated collateral taken by a fee, mirrors fm_multiply_ratio
+pub fn liquidation_fee_share(coll_liquidated: u64, fee: u64) -> u64 {
+    let share = coll_liquidated as u128 * fee as u128 / DECIMAL_PRECISION as u128;
+    return share.try_into().unwrap();
+}
+
+// Mirrors of the community issuance emission curve, kept in sync with community-issuance-contract/src/utils.sw
+pub const FPT_SUPPLY_CAP: u64 = 32_000_000_000_000_000;
+const SECONDS_IN_ONE_MINUTE: u64 = 60;
+const ISSUANCE_FACTOR: u64 = 999_998_681;
+
+pub fn get_fpt_supply_cap(
+    time_transition_started: u64,
+    total_transition_time_seconds: u64,
+    current_time: u64,
+    has_transitioned_rewards: bool,
+) -> u64 {
+    if !has_transitioned_rewards {
+        return FPT_SUPPLY_CAP / 2;
+    }
+    let time_diff = (current_time - time_transition_started) as u128;
+    let supply_cap_over_2 = (FPT_SUPPLY_CAP / 2) as u128;
+    let transition_completed_ratio =
+        time_diff * DECIMAL_PRECISION as u128 / total_transition_time_seconds as u128;
+    if transition_completed_ratio > DECIMAL_PRECISION as u128 {
+        return FPT_SUPPLY_CAP;
+    }
+    let current_supply_cap = supply_cap_over_2
+        + (supply_cap_over_2 * transition_completed_ratio / DECIMAL_PRECISION as u128);
+    return current_supply_cap.try_into().unwrap();
+}
+
+pub fn get_scheduled_supply_cap(
+    start_supply_cap: u64,
+    phases: &[EmissionPhase],
+    schedule_start_time: u64,
+    current_time: u64,
+) -> u64 {
+    let mut supply_cap = start_supply_cap;
+    let mut time_remaining = current_time - schedule_start_time;
+    for phase in phases {
+        if time_remaining >= phase.duration {
+            supply_cap = phase.target_supply_cap;
+            time_remaining -= phase.duration;
+        } else {
+            let increase = (phase.target_supply_cap - supply_cap) as u128 * time_remaining as u128
+                / phase.duration as u128;
+            return supply_cap + increase as u64;
+        }
+    }
+    return supply_cap;
+}
+
+fn dec_mul(a: u128, b: u128) -> u128 {
+    (a * b + (DECIMAL_PRECISION / 2) as u128) / DECIMAL_PRECISION as u128
+}
+
+pub fn dec_pow(base: u64, minutes: u64) -> u128 {
+    let minutes = minutes.min(525_600_000);
+    let mut y = DECIMAL_PRECISION as u128;
+    let mut x = base as u128;
+    let mut n = minutes as u128;
+    while n > 1 {
+        if n % 2 == 0 {
+            x = dec_mul(x, x);
+            n = n / 2;
+        } else {
+            y = dec_mul(x, y);
+            x = dec_mul(x, x);
+            n = (n - 1) / 2;
+        }
+    }
+    return dec_mul(x, y);
+}
+
+pub fn get_cumulative_issuance_fraction(current_time: u64, deployment_time: u64) -> u64 {
+    let time_passed_in_minutes = (current_time - deployment_time) / SECONDS_IN_ONE_MINUTE;
+    let power = dec_pow(ISSUANCE_FACTOR, time_passed_in_minutes);
+    return (DECIMAL_PRECISION as u128 - power).try_into().unwrap();
+}
+
+// Total FPT issued by current_time for a given supply cap, as issue_fpt computes it
+pub fn get_total_fpt_issued(supply_cap: u64, current_time: u64, deployment_time: u64) -> u64 {
+    let total = supply_cap as u128
+        * get_cumulative_issuance_fraction(current_time, deployment_time) as u128
+        / DECIMAL_PRECISION as u128;
+    return total.try_into().unwrap();
 }
 
 pub fn resolve_relative_path(path: &str) -> String {